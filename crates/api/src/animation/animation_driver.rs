@@ -0,0 +1,259 @@
+use dces::prelude::{Entity, EntityComponentManager};
+
+use crate::{
+    application::mark_dirty,
+    css_engine::Transition,
+    layout::invalidate_property_layout,
+    prelude::*,
+    tree::Tree,
+    utils::{Brush, Color, Thickness},
+};
+
+/// Value of a property that could be animated.
+#[derive(Clone, PartialEq, Debug)]
+pub enum AnimationValue {
+    Brush(Brush),
+    /// Value of a `f64` property.
+    Float(f64),
+    /// Value of a `f32` property, e.g. the opacity.
+    Float32(f32),
+    Thickness(Thickness),
+}
+
+impl AnimationValue {
+    /// Interpolates between this value and `to` by the given progress from 0.0 to 1.0. Brushes
+    /// that are not both solid colors and values of different kinds are not interpolated, `to` is
+    /// returned instead.
+    pub fn interpolate(&self, to: &AnimationValue, progress: f64) -> AnimationValue {
+        match (self, to) {
+            (
+                AnimationValue::Brush(Brush::SolidColor(from)),
+                AnimationValue::Brush(Brush::SolidColor(to)),
+            ) => AnimationValue::Brush(Brush::SolidColor(Color::interpolate(*from, *to, progress))),
+            (AnimationValue::Float(from), AnimationValue::Float(to)) => {
+                AnimationValue::Float(lerp(*from, *to, progress))
+            }
+            (AnimationValue::Float32(from), AnimationValue::Float32(to)) => {
+                AnimationValue::Float32(lerp(*from as f64, *to as f64, progress) as f32)
+            }
+            (AnimationValue::Thickness(from), AnimationValue::Thickness(to)) => {
+                AnimationValue::Thickness(Thickness::new(
+                    lerp(from.left, to.left, progress),
+                    lerp(from.top, to.top, progress),
+                    lerp(from.right, to.right, progress),
+                    lerp(from.bottom, to.bottom, progress),
+                ))
+            }
+            _ => to.clone(),
+        }
+    }
+}
+
+fn lerp(from: f64, to: f64, progress: f64) -> f64 {
+    from + (to - from) * progress
+}
+
+#[derive(Clone)]
+struct Animation {
+    entity: Entity,
+    key: String,
+    from: AnimationValue,
+    to: AnimationValue,
+    start: f64,
+    transition: Transition,
+}
+
+/// The `AnimationDriver` animates properties of the widgets of a window from frame to frame. It is
+/// stored as `animations` property of the window. While animations are running, the window
+/// requests redraws.
+#[derive(Clone, Default)]
+pub struct AnimationDriver {
+    animations: Vec<Animation>,
+}
+
+impl AnimationDriver {
+    /// Starts to animate the property with the given key from `from` to `to`. A running animation
+    /// of the same property is replaced.
+    pub fn start(
+        &mut self,
+        entity: Entity,
+        key: impl Into<String>,
+        from: AnimationValue,
+        to: AnimationValue,
+        transition: Transition,
+    ) {
+        let key = key.into();
+        self.stop(entity, &key);
+
+        self.animations.push(Animation {
+            entity,
+            key,
+            from,
+            to,
+            start: now(),
+            transition,
+        });
+    }
+
+    /// Stops the animation of the property with the given key.
+    pub fn stop(&mut self, entity: Entity, key: &str) {
+        self.animations
+            .retain(|a| a.entity != entity || a.key != key);
+    }
+
+    /// Returns `true` if at least one animation is running.
+    pub fn is_running(&self) -> bool {
+        !self.animations.is_empty()
+    }
+
+    // Calculates the current values of all animations and removes the finished ones.
+    fn advance(&mut self, time: f64) -> Vec<(Entity, String, AnimationValue)> {
+        let values = self
+            .animations
+            .iter()
+            .map(|a| {
+                let progress = a.transition.progress(time - a.start);
+                (a.entity, a.key.clone(), a.from.interpolate(&a.to, progress))
+            })
+            .collect();
+
+        self.animations
+            .retain(|a| !a.transition.is_finished(time - a.start));
+
+        values
+    }
+}
+
+/// Gets the current time in milliseconds since the first call on the current thread. The time is
+/// monotonic, changes of the system clock do not affect running animations.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn now() -> f64 {
+    use std::time::Instant;
+
+    thread_local! {
+        static START: Instant = Instant::now();
+    }
+
+    START.with(|start| start.elapsed().as_secs_f64() * 1000.0)
+}
+
+/// Gets the current time in milliseconds.
+#[cfg(target_arch = "wasm32")]
+pub fn now() -> f64 {
+    stdweb::web::Date::now()
+}
+
+/// Gets the value of the property with the given key. The property is read with the type of the
+/// given value, it must be the type of the property.
+pub(crate) fn animation_value(
+    ecm: &EntityComponentManager<Tree, StringComponentStore>,
+    entity: Entity,
+    key: &str,
+    value: &AnimationValue,
+) -> Option<AnimationValue> {
+    let store = ecm.component_store();
+
+    match value {
+        AnimationValue::Brush(_) => store
+            .get::<Brush>(key, entity)
+            .ok()
+            .map(|brush| AnimationValue::Brush(brush.clone())),
+        AnimationValue::Float(_) => store
+            .get::<f64>(key, entity)
+            .ok()
+            .map(|float| AnimationValue::Float(*float)),
+        AnimationValue::Float32(_) => store
+            .get::<f32>(key, entity)
+            .ok()
+            .map(|float| AnimationValue::Float32(*float)),
+        AnimationValue::Thickness(_) => store
+            .get::<Thickness>(key, entity)
+            .ok()
+            .map(|thickness| AnimationValue::Thickness(*thickness)),
+    }
+}
+
+/// Sets the value of the property with the given key. Nothing happens if the widget does not
+/// contains the property, the type of the value must be the type of the property.
+pub(crate) fn set_animation_value(
+    ecm: &mut EntityComponentManager<Tree, StringComponentStore>,
+    entity: Entity,
+    key: &str,
+    value: AnimationValue,
+) {
+    mark_dirty(ecm, entity, key);
+    invalidate_property_layout(ecm, entity, key);
+
+    let store = ecm.component_store_mut();
+
+    match value {
+        AnimationValue::Brush(value) => {
+            if let Ok(brush) = store.get_mut::<Brush>(key, entity) {
+                *brush = value;
+            }
+        }
+        AnimationValue::Float(value) => {
+            if let Ok(float) = store.get_mut::<f64>(key, entity) {
+                *float = value;
+            }
+        }
+        AnimationValue::Float32(value) => {
+            if let Ok(float) = store.get_mut::<f32>(key, entity) {
+                *float = value;
+            }
+        }
+        AnimationValue::Thickness(value) => {
+            if let Ok(thickness) = store.get_mut::<Thickness>(key, entity) {
+                *thickness = value;
+            }
+        }
+    }
+}
+
+/// Sets the current values of all running animations of the window and removes the finished
+/// ones.
+pub(crate) fn run_animations(ecm: &mut EntityComponentManager<Tree, StringComponentStore>) {
+    let root = ecm.entity_store().root();
+
+    let values = match ecm
+        .component_store_mut()
+        .get_mut::<AnimationDriver>("animations", root)
+    {
+        Ok(driver) if driver.is_running() => driver.advance(now()),
+        _ => return,
+    };
+
+    for (entity, key, value) in values {
+        set_animation_value(ecm, entity, &key, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolate() {
+        let from = AnimationValue::Brush(Brush::from("#000000"));
+        let to = AnimationValue::Brush(Brush::from("#ffffff"));
+        assert_eq!(
+            from.interpolate(&to, 0.5),
+            AnimationValue::Brush(Brush::from("#7f7f7f"))
+        );
+
+        assert_eq!(
+            AnimationValue::Float(2.0).interpolate(&AnimationValue::Float(4.0), 0.25),
+            AnimationValue::Float(2.5)
+        );
+
+        assert_eq!(
+            AnimationValue::Thickness(Thickness::new(0.0, 0.0, 0.0, 0.0)).interpolate(
+                &AnimationValue::Thickness(Thickness::new(2.0, 4.0, 6.0, 8.0)),
+                0.5
+            ),
+            AnimationValue::Thickness(Thickness::new(1.0, 2.0, 3.0, 4.0))
+        );
+
+        assert_eq!(AnimationValue::Float(2.0).interpolate(&to, 0.5), to);
+    }
+}
@@ -0,0 +1,5 @@
+//! This module contains the animation driver that animates property changes by css transitions.
+
+pub use self::animation_driver::*;
+
+mod animation_driver;
@@ -9,7 +9,7 @@ use crate::{
     application::WindowAdapter,
     prelude::*,
     shell::{ShellRequest, WindowRequest},
-    utils::Point,
+    utils::{Point, Rectangle},
 };
 
 /// Temporary solution to share dependencies. Will be refactored soon.
@@ -21,8 +21,17 @@ pub struct ContextProvider {
     pub states: Rc<RefCell<BTreeMap<Entity, Box<dyn State>>>>,
     pub event_queue: Rc<RefCell<EventQueue>>,
     pub mouse_position: Rc<Cell<Point>>,
+
+    /// The region of the window that is currently repainted, `None` if the whole window is
+    /// repainted. Widgets outside of the region are not drawn.
+    pub render_region: Rc<Cell<Option<Rectangle>>>,
     pub window_sender: mpsc::Sender<WindowRequest>,
     pub shell_sender: mpsc::Sender<ShellRequest<WindowAdapter>>,
+    pub theme_sender: mpsc::Sender<ThemeValue>,
+    pub theme_receiver: Rc<mpsc::Receiver<ThemeValue>>,
+
+    /// Keeps the thread of the watched theme running, it stops if the sender is dropped.
+    pub theme_watcher: Rc<RefCell<Option<mpsc::Sender<()>>>>,
     pub application_name: String,
 }
 
@@ -33,6 +42,8 @@ impl ContextProvider {
         shell_sender: mpsc::Sender<ShellRequest<WindowAdapter>>,
         application_name: impl Into<String>,
     ) -> Self {
+        let (theme_sender, theme_receiver) = mpsc::channel();
+
         ContextProvider {
             render_objects: Rc::new(RefCell::new(BTreeMap::new())),
             layouts: Rc::new(RefCell::new(BTreeMap::new())),
@@ -40,8 +51,12 @@ impl ContextProvider {
             states: Rc::new(RefCell::new(BTreeMap::new())),
             event_queue: Rc::new(RefCell::new(EventQueue::new())),
             mouse_position: Rc::new(Cell::new(Point::new(0.0, 0.0))),
+            render_region: Rc::new(Cell::new(None)),
             window_sender,
             shell_sender,
+            theme_sender,
+            theme_receiver: Rc::new(theme_receiver),
+            theme_watcher: Rc::new(RefCell::new(None)),
             application_name: application_name.into(),
         }
     }
@@ -0,0 +1,168 @@
+use std::collections::BTreeSet;
+
+use dces::prelude::{Entity, EntityComponentManager, StringComponentStore};
+
+use crate::{
+    tree::Tree,
+    utils::{BoxShadow, Rectangle},
+    widget::sharing_widgets,
+};
+
+// Above this number of damaged rectangles all of them are merged into one.
+const MAX_RECTS: usize = 8;
+
+/// Collects the widgets whose properties are changed since the last rendered frame. It is
+/// registered as `dirty_widgets` on the window.
+#[derive(Default, Clone)]
+pub struct DirtyWidgets {
+    entities: BTreeSet<Entity>,
+}
+
+impl DirtyWidgets {
+    /// Marks the given widget as changed.
+    pub fn insert(&mut self, entity: Entity) {
+        self.entities.insert(entity);
+    }
+
+    /// Checks if the given widget is changed.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+
+    /// Checks if no widget is changed.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Removes all marks.
+    pub fn clear(&mut self) {
+        self.entities.clear();
+    }
+}
+
+/// Marks the widget as changed by the property with the given key, so the area it covers is
+/// repainted by the next frame. All widgets that share the property are marked too.
+pub(crate) fn mark_dirty(
+    ecm: &mut EntityComponentManager<Tree, StringComponentStore>,
+    entity: Entity,
+    key: &str,
+) {
+    let root = ecm.entity_store().root();
+    let widgets = sharing_widgets(ecm.component_store(), entity, key);
+
+    if let Ok(dirty_widgets) = ecm
+        .component_store_mut()
+        .get_mut::<DirtyWidgets>("dirty_widgets", root)
+    {
+        for widget in widgets {
+            dirty_widgets.insert(widget);
+        }
+    }
+}
+
+/// Gets the area the widget paints into from its global bounds, that is the bounds extended by
+/// the shadow of the widget.
+pub(crate) fn paint_area(
+    store: &StringComponentStore,
+    entity: Entity,
+    bounds: Rectangle,
+) -> Rectangle {
+    match store.get::<BoxShadow>("box_shadow", entity) {
+        Ok(shadow) if shadow.is_visible() => bounds.union(&shadow.bounds(&bounds)),
+        _ => bounds,
+    }
+}
+
+/// Describes the regions of a window that are changed and have to be repainted.
+#[derive(Default, Clone, Debug, PartialEq)]
+pub struct Damage {
+    rects: Vec<Rectangle>,
+}
+
+impl Damage {
+    /// Adds a damaged rectangle. It is extended to whole pixels and merged with the rectangles
+    /// it overlaps.
+    pub fn add(&mut self, rect: Rectangle) {
+        if rect.width <= 0.0 || rect.height <= 0.0 {
+            return;
+        }
+
+        let (x, y) = (rect.x.floor(), rect.y.floor());
+        let mut rect = Rectangle::new(
+            x,
+            y,
+            (rect.x + rect.width).ceil() - x,
+            (rect.y + rect.height).ceil() - y,
+        );
+
+        // merging could cause new overlaps
+        while let Some(index) = self.rects.iter().position(|r| r.intersects(&rect)) {
+            rect = rect.union(&self.rects.remove(index));
+        }
+
+        self.rects.push(rect);
+
+        if self.rects.len() > MAX_RECTS {
+            if let Some(bounds) = self.bounds() {
+                self.rects = vec![bounds];
+            }
+        }
+    }
+
+    /// Gets the damaged rectangles.
+    pub fn rects(&self) -> &[Rectangle] {
+        &self.rects
+    }
+
+    /// Checks if nothing is damaged.
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Gets the smallest rectangle that contains all damaged rectangles.
+    pub fn bounds(&self) -> Option<Rectangle> {
+        let first = *self.rects.first()?;
+
+        Some(
+            self.rects
+                .iter()
+                .skip(1)
+                .fold(first, |bounds, rect| bounds.union(rect)),
+        )
+    }
+
+    /// Removes all damaged rectangles.
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_merges_overlapping_rects() {
+        let mut damage = Damage::default();
+        damage.add(Rectangle::new(0.0, 0.0, 10.0, 10.0));
+        damage.add(Rectangle::new(50.0, 0.0, 10.0, 10.0));
+        damage.add(Rectangle::new(0.0, 0.0, 0.0, 10.0));
+
+        assert_eq!(damage.rects().len(), 2);
+
+        damage.add(Rectangle::new(5.0, 5.0, 50.0, 2.0));
+
+        assert_eq!(damage.rects(), &[Rectangle::new(0.0, 0.0, 60.0, 10.0)]);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut damage = Damage::default();
+        assert_eq!(damage.bounds(), None);
+
+        damage.add(Rectangle::new(0.5, 1.5, 10.0, 10.0));
+        damage.add(Rectangle::new(20.0, 20.0, 5.2, 5.0));
+
+        assert_eq!(damage.bounds(), Some(Rectangle::new(0.0, 1.0, 26.0, 24.0)));
+    }
+}
@@ -10,13 +10,17 @@ use crate::{
 };
 
 pub use self::context_provider::*;
+pub use self::damage::*;
 pub use self::global::*;
 pub use self::overlay::*;
+pub(crate) use self::theme_switch::*;
 pub use self::window_adapter::*;
 
 mod context_provider;
+mod damage;
 mod global;
 mod overlay;
+mod theme_switch;
 mod window_adapter;
 
 /// The `Application` represents the entry point of an OrbTk based application.
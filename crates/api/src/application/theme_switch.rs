@@ -0,0 +1,117 @@
+use dces::prelude::EntityComponentManager;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    fs,
+    sync::mpsc::{self, TryRecvError},
+    thread,
+    time::Duration,
+    time::SystemTime,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::shell::WindowRequest;
+
+use crate::{prelude::*, tree::Tree};
+
+// Interval in which the css files of a watched theme are checked for changes.
+#[cfg(not(target_arch = "wasm32"))]
+const THEME_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Replaces the theme of the window and updates the properties of all widgets by the new theme.
+pub(crate) fn switch_theme(
+    ecm: &mut EntityComponentManager<Tree, StringComponentStore>,
+    theme: ThemeValue,
+) {
+    let root = ecm.entity_store().root();
+
+    if let Ok(current_theme) = ecm
+        .component_store_mut()
+        .get_mut::<ThemeValue>("theme", root)
+    {
+        *current_theme = theme.clone();
+    }
+
+    // the new theme could change the size of any widget
+    if let Ok(cache) = ecm
+        .component_store_mut()
+        .get_mut::<LayoutCache>("layout_cache", root)
+    {
+        cache.invalidate_all();
+    }
+
+    WidgetContainer::new(root, ecm, &theme).update_theme_by_state(true);
+}
+
+/// Starts a thread that rebuilds the theme each time one of the css files of the builder has
+/// changed and sends it to the window. The thread stops as soon as the sender of `stop` is
+/// dropped, e.g. if the window is closed.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn watch_theme(
+    builder: ThemeBuilder,
+    theme_sender: mpsc::Sender<ThemeValue>,
+    window_sender: mpsc::Sender<WindowRequest>,
+    stop: mpsc::Receiver<()>,
+) {
+    thread::spawn(move || {
+        let paths = builder.paths();
+        let mut modified = modified_times(&paths);
+
+        loop {
+            if theme_sender.send(builder.clone().build()).is_err()
+                || window_sender.send(WindowRequest::Redraw).is_err()
+            {
+                return;
+            }
+
+            loop {
+                thread::sleep(THEME_WATCH_INTERVAL);
+
+                if let Err(TryRecvError::Disconnected) = stop.try_recv() {
+                    return;
+                }
+
+                let current = modified_times(&paths);
+                if current != modified {
+                    modified = current;
+                    break;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn modified_times(paths: &[String]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_theme_stops() {
+        let (theme_sender, themes) = mpsc::channel();
+        let (window_sender, _window_requests) = mpsc::channel();
+        let (stop_sender, stop) = mpsc::channel();
+
+        watch_theme(
+            ThemeValue::create_from_css("a { color: #ff0000; }"),
+            theme_sender,
+            window_sender,
+            stop,
+        );
+        assert!(themes.recv().is_ok());
+
+        // the thread drops its theme sender as soon as it stops
+        drop(stop_sender);
+        assert_eq!(
+            themes.recv_timeout(THEME_WATCH_INTERVAL * 4).err(),
+            Some(mpsc::RecvTimeoutError::Disconnected)
+        );
+    }
+}
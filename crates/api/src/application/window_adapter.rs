@@ -1,7 +1,8 @@
-use dces::prelude::{Entity, World};
+use dces::prelude::{Entity, EntityComponentManager, World};
 use std::{cell::RefCell, collections::HashMap, sync::mpsc};
 
 use crate::{
+    animation::run_animations,
     prelude::*,
     properties::Constraint,
     render, shell,
@@ -10,6 +11,8 @@ use crate::{
     utils::{Point, Rectangle},
 };
 
+use super::{mark_dirty, switch_theme};
+
 /// Represents a window. Each window has its own tree, event pipeline and shell.
 pub struct WindowAdapter {
     world: World<Tree, StringComponentStore, render::RenderContext2D>,
@@ -24,6 +27,18 @@ impl WindowAdapter {
     ) -> Self {
         WindowAdapter { world, ctx }
     }
+
+    /// Gets the entity component manager of the window.
+    pub fn entity_component_manager(
+        &mut self,
+    ) -> &mut EntityComponentManager<Tree, StringComponentStore> {
+        self.world.entity_component_manager()
+    }
+
+    /// Gets the context provider of the window.
+    pub fn context_provider(&self) -> &ContextProvider {
+        &self.ctx
+    }
 }
 
 impl WindowAdapter {
@@ -49,6 +64,17 @@ impl shell::WindowAdapter for WindowAdapter {
             );
     }
 
+    fn scale_factor(&mut self, scale_factor: f64) {
+        let root = self.root();
+        let ecm = self.world.entity_component_manager();
+
+        ecm.component_store_mut()
+            .register("scale_factor", root, scale_factor);
+
+        // the pixel buffer is resized, so the whole window is drawn again
+        mark_dirty(ecm, root, "scale_factor");
+    }
+
     fn mouse(&mut self, x: f64, y: f64) {
         let root = self.root();
         self.ctx.mouse_position.set(Point::new(x, y));
@@ -143,7 +169,26 @@ impl shell::WindowAdapter for WindowAdapter {
     }
 
     fn run(&mut self, render_context: &mut render::RenderContext2D) {
+        // only the latest requested theme is applied
+        if let Some(theme) = self.ctx.theme_receiver.try_iter().last() {
+            switch_theme(self.world.entity_component_manager(), theme);
+        }
+
+        run_animations(self.world.entity_component_manager());
+
         self.world.run_with_context(render_context);
+
+        // keep on running while animations are running, including the ones started by this run
+        let root = self.root();
+        if self
+            .world
+            .entity_component_manager()
+            .component_store()
+            .get::<AnimationDriver>("animations", root)
+            .map_or(false, |driver| driver.is_running())
+        {
+            let _ = self.ctx.window_sender.send(WindowRequest::Redraw);
+        }
     }
 }
 
@@ -204,6 +249,13 @@ pub fn create_window<F: Fn(&mut BuildContext) -> Entity + 'static>(
             tree.set_root(window);
         }
 
+        // the widgets are built from the leaves to the root, rules with descendant or child
+        // combinators could match the whole ancestor chain only now
+        if theme.has_selector_relations() {
+            WidgetContainer::new(window, world.entity_component_manager(), &theme)
+                .update_theme_by_state(true);
+        }
+
         window
     };
 
@@ -257,6 +309,12 @@ pub fn create_window<F: Fn(&mut BuildContext) -> Entity + 'static>(
             .unwrap(),
         position: (position.x, position.y),
         size: (constraint.width(), constraint.height()),
+        scale_factor: world
+            .entity_component_manager()
+            .component_store()
+            .get::<f64>("scale_factor", window)
+            .ok()
+            .cloned(),
         fonts,
     };
 
@@ -268,6 +326,18 @@ pub fn create_window<F: Fn(&mut BuildContext) -> Entity + 'static>(
         .entity_component_manager()
         .component_store_mut()
         .register("global", window, Global::default());
+    world
+        .entity_component_manager()
+        .component_store_mut()
+        .register("animations", window, AnimationDriver::default());
+    world
+        .entity_component_manager()
+        .component_store_mut()
+        .register("dirty_widgets", window, DirtyWidgets::default());
+    world
+        .entity_component_manager()
+        .component_store_mut()
+        .register("layout_cache", window, LayoutCache::default());
     world
         .entity_component_manager()
         .component_store_mut()
@@ -4,7 +4,7 @@ use dces::prelude::Entity;
 
 use crate::{prelude::*, render::RenderContext2D, tree::Tree, utils::prelude::*};
 
-use super::{component, component_try_mut, Layout};
+use super::{arrange_layout, component, component_try_mut, measure_layout, Layout};
 
 /// Place widgets absolute on the screen.
 #[derive(Default)]
@@ -38,9 +38,15 @@ impl Layout for AbsoluteLayout {
         for index in 0..ecm.entity_store().children[&entity].len() {
             let child = ecm.entity_store().children[&entity][index];
             if let Some(child_layout) = layouts.get(&child) {
-                let dirty = child_layout
-                    .measure(render_context_2_d, child, ecm, layouts, theme)
-                    .dirty()
+                let dirty = measure_layout(
+                    child_layout.as_ref(),
+                    render_context_2_d,
+                    child,
+                    ecm,
+                    layouts,
+                    theme,
+                )
+                .dirty()
                     || self.desired_size.borrow().dirty();
 
                 self.desired_size.borrow_mut().set_dirty(dirty);
@@ -72,7 +78,8 @@ impl Layout for AbsoluteLayout {
         for index in 0..ecm.entity_store().children[&entity].len() {
             let child = ecm.entity_store().children[&entity][index];
             if let Some(child_layout) = layouts.get(&child) {
-                child_layout.arrange(
+                arrange_layout(
+                    child_layout.as_ref(),
                     render_context_2_d,
                     (
                         self.desired_size.borrow().width(),
@@ -5,9 +5,17 @@ use std::{
 
 use dces::prelude::Entity;
 
-use crate::{prelude::*, render::Image, render::RenderContext2D, tree::Tree, utils::prelude::*};
+use crate::{
+    prelude::*,
+    render::{layout_text, AnimatedImage, Image, RenderContext2D, Svg},
+    render_object::text_layout_config,
+    tree::Tree,
+    utils::prelude::*,
+};
 
-use super::{component, component_try_mut, Layout};
+use super::{
+    arrange_layout, component, component_or_default, component_try_mut, measure_layout, Layout,
+};
 
 /// Fixed size layout is defined by fixed bounds like the size of an image or the size of a text.
 #[derive(Default)]
@@ -47,35 +55,58 @@ impl Layout for FixedSizeLayout {
             self.desired_size.borrow_mut().set_dirty(true);
         }
 
+        let stretch = widget.clone_or_default::<Stretch>("stretch");
         let size = widget
-            .try_get::<Image>("image")
-            .map(|image| (image.width(), image.height()))
+            .try_get::<Svg>("svg")
+            .filter(|svg| !svg.is_empty())
+            .map(|svg| match widget.try_get::<f64>("icon_size") {
+                // icons are scaled to the icon size, it is their height
+                Some(icon_size) if svg.height() > 0.0 => {
+                    (icon_size * svg.width() / svg.height(), *icon_size)
+                }
+                _ => (svg.width(), svg.height()),
+            })
+            .or_else(|| {
+                widget
+                    .try_get::<AnimatedImage>("animated_image")
+                    .filter(|animated_image| !animated_image.is_empty())
+                    .map(|animated_image| (animated_image.width(), animated_image.height()))
+            })
+            .or_else(|| {
+                widget
+                    .try_get::<Image>("image")
+                    .map(|image| (image.width(), image.height()))
+            })
             .or_else(|| {
                 widget.try_get::<String16>("text").and_then(|text| {
                     let font = widget.get::<String>("font");
                     let font_size = widget.get::<f64>("font_size");
-
-                    if text.is_empty() {
+                    render_context_2_d
+                        .set_font_weight(widget.clone_or_default::<FontWeight>("font_weight"));
+                    render_context_2_d
+                        .set_font_style(widget.clone_or_default::<FontStyle>("font_style"));
+                    render_context_2_d
+                        .set_font_stretch(widget.clone_or_default::<FontStretch>("font_stretch"));
+
+                    let text = if text.is_empty() {
                         widget
                             .try_get::<String16>("water_mark")
-                            .filter(|water_mark| !water_mark.is_empty())
-                            .map(|water_mark| {
-                                let text_metrics = render_context_2_d.measure(
-                                    water_mark.to_string().as_str(),
-                                    *font_size,
-                                    font.as_str(),
-                                );
-                                (text_metrics.width, text_metrics.height)
-                            })
+                            .filter(|water_mark| !water_mark.is_empty())?
+                            .to_string()
                     } else {
-                        let text_metrics = render_context_2_d.measure(
-                            text.to_string().as_str(),
-                            *font_size,
-                            font.as_str(),
-                        );
-
-                        Some((text_metrics.width, text_metrics.height))
-                    }
+                        text.to_string()
+                    };
+
+                    // lines are wrapped at the maximum width of the constraint
+                    let max_width = widget.get::<Constraint>("constraint").max_width();
+                    let layout =
+                        layout_text(&text, &text_layout_config(&widget, max_width), |line| {
+                            render_context_2_d
+                                .measure(line, *font_size, font.as_str())
+                                .width
+                        });
+
+                    Some((layout.width, layout.height))
                 })
             })
             .or_else(|| {
@@ -84,6 +115,9 @@ impl Layout for FixedSizeLayout {
                     .filter(|font_icon| !font_icon.is_empty())
                     .map(|font_icon| {
                         let icon_size = widget.get::<f64>("icon_size");
+                        render_context_2_d.set_font_weight(FontWeight::default());
+                        render_context_2_d.set_font_style(FontStyle::default());
+                        render_context_2_d.set_font_stretch(FontStretch::default());
                         let text_metrics = render_context_2_d.measure(
                             &font_icon,
                             *icon_size,
@@ -94,9 +128,17 @@ impl Layout for FixedSizeLayout {
             });
 
         if let Some(size) = size {
-            if let Some(constraint) = component_try_mut::<Constraint>(ecm, entity, "constraint") {
-                constraint.set_width(size.0 as f64);
-                constraint.set_height(size.1 as f64);
+            if stretch == Stretch::None {
+                if let Some(constraint) = component_try_mut::<Constraint>(ecm, entity, "constraint")
+                {
+                    constraint.set_width(size.0 as f64);
+                    constraint.set_height(size.1 as f64);
+                }
+            } else {
+                // a stretched image keeps the size of its constraint, the image size is only used
+                // if no size is set
+                let size = component::<Constraint>(ecm, entity, "constraint").perform(size);
+                self.desired_size.borrow_mut().set_size(size.0, size.1);
             }
         }
 
@@ -115,9 +157,15 @@ impl Layout for FixedSizeLayout {
         for index in 0..ecm.entity_store().children[&entity].len() {
             let child = ecm.entity_store().children[&entity][index];
             if let Some(child_layout) = layouts.get(&child) {
-                let dirty = child_layout
-                    .measure(render_context_2_d, child, ecm, layouts, theme)
-                    .dirty()
+                let dirty = measure_layout(
+                    child_layout.as_ref(),
+                    render_context_2_d,
+                    child,
+                    ecm,
+                    layouts,
+                    theme,
+                )
+                .dirty()
                     || self.desired_size.borrow().dirty();
 
                 self.desired_size.borrow_mut().set_dirty(dirty);
@@ -130,7 +178,7 @@ impl Layout for FixedSizeLayout {
     fn arrange(
         &self,
         render_context_2_d: &mut RenderContext2D,
-        _parent_size: (f64, f64),
+        parent_size: (f64, f64),
         entity: Entity,
         ecm: &mut EntityComponentManager<Tree, StringComponentStore>,
         layouts: &BTreeMap<Entity, Box<dyn Layout>>,
@@ -141,15 +189,41 @@ impl Layout for FixedSizeLayout {
             return (0.0, 0.0);
         }
 
+        let mut size = self.desired_size.borrow().size();
+
+        // stretched images fill their parent by their alignment
+        if component_or_default::<Stretch>(ecm, entity, "stretch") != Stretch::None {
+            let horizontal_alignment: Alignment = component(ecm, entity, "h_align");
+            let vertical_alignment: Alignment = component(ecm, entity, "v_align");
+            let margin: Thickness = component(ecm, entity, "margin");
+
+            size = component::<Constraint>(ecm, entity, "constraint").perform((
+                horizontal_alignment.align_measure(
+                    parent_size.0,
+                    size.0,
+                    margin.left(),
+                    margin.right(),
+                ),
+                vertical_alignment.align_measure(
+                    parent_size.1,
+                    size.1,
+                    margin.top(),
+                    margin.bottom(),
+                ),
+            ));
+            self.desired_size.borrow_mut().set_size(size.0, size.1);
+        }
+
         if let Some(bounds) = component_try_mut::<Rectangle>(ecm, entity, "bounds") {
-            bounds.set_width(self.desired_size.borrow().width());
-            bounds.set_height(self.desired_size.borrow().height());
+            bounds.set_width(size.0);
+            bounds.set_height(size.1);
         }
 
         for index in 0..ecm.entity_store().children[&entity].len() {
             let child = ecm.entity_store().children[&entity][index];
             if let Some(child_layout) = layouts.get(&child) {
-                child_layout.arrange(
+                arrange_layout(
+                    child_layout.as_ref(),
                     render_context_2_d,
                     (
                         self.desired_size.borrow().width(),
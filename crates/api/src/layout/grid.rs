@@ -7,7 +7,7 @@ use dces::prelude::Entity;
 
 use crate::{prelude::*, render::RenderContext2D, tree::Tree, utils::prelude::*};
 
-use super::{component, component_try_mut, Layout};
+use super::{arrange_layout, component, component_try_mut, measure_layout, Layout};
 
 /// Orders its children in a grid layout with columns and rows. If no columns and rows are defined
 /// the grid layout could also be used as an alignment layout.
@@ -270,8 +270,14 @@ impl Layout for GridLayout {
         for index in 0..ecm.entity_store().children[&entity].len() {
             let child = ecm.entity_store().children[&entity][index];
             if let Some(child_layout) = layouts.get(&child) {
-                let child_desired_size =
-                    child_layout.measure(render_context_2_d, child, ecm, layouts, theme);
+                let child_desired_size = measure_layout(
+                    child_layout.as_ref(),
+                    render_context_2_d,
+                    child,
+                    ecm,
+                    layouts,
+                    theme,
+                );
 
                 let dirty = child_desired_size.dirty() || self.desired_size.borrow().dirty();
 
@@ -460,7 +466,8 @@ impl Layout for GridLayout {
 
             let mut child_desired_size = (0.0, 0.0);
             if let Some(child_layout) = layouts.get(&child) {
-                child_desired_size = child_layout.arrange(
+                child_desired_size = arrange_layout(
+                    child_layout.as_ref(),
                     render_context_2_d,
                     available_size,
                     child,
@@ -0,0 +1,297 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use dces::prelude::{Entity, EntityComponentManager};
+
+use crate::{prelude::*, render::RenderContext2D, tree::Tree, utils::*, widget::sharing_widgets};
+
+use super::Layout;
+
+/// Properties that change the size or the position of a widget or its children. Changing one of
+/// them invalidates the layout of the widget.
+const LAYOUT_PROPERTIES: [&str; 38] = [
+    "animated_image",
+    "column",
+    "column_span",
+    "columns",
+    "constraint",
+    "delta",
+    "expanded",
+    "font",
+    "font_size",
+    "font_stretch",
+    "font_style",
+    "font_weight",
+    "h_align",
+    "icon",
+    "icon_font",
+    "icon_size",
+    "image",
+    "line_height",
+    "margin",
+    "max_lines",
+    "orientation",
+    "padding",
+    "row",
+    "row_span",
+    "rows",
+    "scroll_offset",
+    "scroll_viewer_mode",
+    "spacing",
+    "stretch",
+    "svg",
+    "target",
+    "text",
+    "text_align",
+    "text_selection",
+    "text_wrap",
+    "v_align",
+    "visibility",
+    "water_mark",
+];
+
+#[derive(Copy, Clone, Default)]
+struct CacheEntry {
+    desired_size: Option<DirtySize>,
+
+    // the parent size and the result of the last arrangement
+    arrangement: Option<((f64, f64), (f64, f64))>,
+}
+
+/// Caches the measured and arranged sizes of the widgets, so only widgets with an invalidated
+/// layout and their ancestors are laid out again. It is registered as `layout_cache` on the
+/// window.
+#[derive(Clone, Default)]
+pub struct LayoutCache {
+    entries: BTreeMap<Entity, CacheEntry>,
+    invalid: BTreeSet<Entity>,
+    laid_out: BTreeSet<Entity>,
+    laid_out_count: usize,
+}
+
+impl LayoutCache {
+    /// Creates a new empty layout cache.
+    pub fn new() -> Self {
+        LayoutCache::default()
+    }
+
+    /// Marks the layout of the given widget as invalid. The ancestors of the widget have to be
+    /// invalidated too.
+    pub fn invalidate(&mut self, entity: Entity) {
+        self.invalid.insert(entity);
+    }
+
+    /// Removes all cached sizes, so the whole tree is laid out by the next frame. It is used if
+    /// the theme of the window is switched.
+    pub fn invalidate_all(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Checks if the given widget has a cached layout that is not invalidated.
+    pub fn is_valid(&self, entity: Entity) -> bool {
+        !self.invalid.contains(&entity) && self.entries.contains_key(&entity)
+    }
+
+    /// Gets the number of widgets that are measured or arranged by the last frame.
+    pub fn laid_out_nodes(&self) -> usize {
+        self.laid_out_count
+    }
+
+    /// Gets the cached desired size of the given widget if its layout is valid.
+    pub fn desired_size(&self, entity: Entity) -> Option<DirtySize> {
+        if self.invalid.contains(&entity) {
+            return None;
+        }
+
+        self.entries.get(&entity).and_then(|e| e.desired_size)
+    }
+
+    /// Gets the cached size of the given widget if its layout is valid and it is arranged with
+    /// the same parent size before.
+    pub fn arranged_size(&self, entity: Entity, parent_size: (f64, f64)) -> Option<(f64, f64)> {
+        if self.invalid.contains(&entity) {
+            return None;
+        }
+
+        self.entries
+            .get(&entity)
+            .and_then(|e| e.arrangement)
+            .filter(|(p, _)| *p == parent_size)
+            .map(|(_, size)| size)
+    }
+
+    /// Stores the measured desired size of the given widget.
+    pub fn set_desired_size(&mut self, entity: Entity, desired_size: DirtySize) {
+        self.entries.entry(entity).or_default().desired_size = Some(desired_size);
+        self.laid_out.insert(entity);
+    }
+
+    /// Stores the size of the given widget after it is arranged in the given parent size.
+    pub fn set_arranged_size(&mut self, entity: Entity, parent_size: (f64, f64), size: (f64, f64)) {
+        self.entries.entry(entity).or_default().arrangement = Some((parent_size, size));
+        self.laid_out.insert(entity);
+    }
+
+    /// Starts to count the laid out widgets of a new frame.
+    pub fn begin_frame(&mut self) {
+        self.laid_out.clear();
+    }
+
+    /// Finishes a frame: all layouts are valid again and the entries of the widgets that are
+    /// removed from the tree are dropped.
+    pub fn end_frame<F: Fn(Entity) -> bool>(&mut self, contains: F) {
+        self.laid_out_count = self.laid_out.len();
+        self.invalid.clear();
+        self.entries.retain(|entity, _| contains(*entity));
+    }
+}
+
+/// Checks if changing the property with the given key invalidates the layout of its widget.
+pub(crate) fn affects_layout(key: &str) -> bool {
+    LAYOUT_PROPERTIES.binary_search(&key).is_ok()
+}
+
+/// Invalidates the layout of the given widget and its ancestors.
+pub(crate) fn invalidate_layout(
+    ecm: &mut EntityComponentManager<Tree, StringComponentStore>,
+    entity: Entity,
+) {
+    let root = ecm.entity_store().root();
+    let mut ancestors = vec![entity];
+
+    while let Some(Some(parent)) = ecm.entity_store().parent.get(ancestors.last().unwrap()) {
+        ancestors.push(*parent);
+    }
+
+    if let Ok(cache) = ecm
+        .component_store_mut()
+        .get_mut::<LayoutCache>("layout_cache", root)
+    {
+        for entity in ancestors {
+            cache.invalidate(entity);
+        }
+    }
+}
+
+/// Invalidates the layout of the given widget and of all widgets that share the property with
+/// the given key, if the property affects the layout.
+pub(crate) fn invalidate_property_layout(
+    ecm: &mut EntityComponentManager<Tree, StringComponentStore>,
+    entity: Entity,
+    key: &str,
+) {
+    if !affects_layout(key) {
+        return;
+    }
+
+    for widget in sharing_widgets(ecm.component_store(), entity, key) {
+        invalidate_layout(ecm, widget);
+    }
+}
+
+/// Measures the widget with its layout. If the layout of the widget is valid the cached desired
+/// size is returned and its children are not measured.
+pub fn measure_layout(
+    layout: &dyn Layout,
+    render_context_2_d: &mut RenderContext2D,
+    entity: Entity,
+    ecm: &mut EntityComponentManager<Tree, StringComponentStore>,
+    layouts: &BTreeMap<Entity, Box<dyn Layout>>,
+    theme: &ThemeValue,
+) -> DirtySize {
+    let root = ecm.entity_store().root();
+
+    if let Some(mut desired_size) = ecm
+        .component_store()
+        .get::<LayoutCache>("layout_cache", root)
+        .ok()
+        .and_then(|cache| cache.desired_size(entity))
+    {
+        desired_size.set_dirty(false);
+        return desired_size;
+    }
+
+    let desired_size = layout.measure(render_context_2_d, entity, ecm, layouts, theme);
+
+    if let Ok(cache) = ecm
+        .component_store_mut()
+        .get_mut::<LayoutCache>("layout_cache", root)
+    {
+        cache.set_desired_size(entity, desired_size);
+    }
+
+    desired_size
+}
+
+/// Arranges the widget with its layout. If the layout of the widget is valid and the parent size
+/// is unchanged the cached size is returned and its children are not arranged.
+pub fn arrange_layout(
+    layout: &dyn Layout,
+    render_context_2_d: &mut RenderContext2D,
+    parent_size: (f64, f64),
+    entity: Entity,
+    ecm: &mut EntityComponentManager<Tree, StringComponentStore>,
+    layouts: &BTreeMap<Entity, Box<dyn Layout>>,
+    theme: &ThemeValue,
+) -> (f64, f64) {
+    let root = ecm.entity_store().root();
+
+    if let Some(size) = ecm
+        .component_store()
+        .get::<LayoutCache>("layout_cache", root)
+        .ok()
+        .and_then(|cache| cache.arranged_size(entity, parent_size))
+    {
+        return size;
+    }
+
+    let size = layout.arrange(render_context_2_d, parent_size, entity, ecm, layouts, theme);
+
+    if let Ok(cache) = ecm
+        .component_store_mut()
+        .get_mut::<LayoutCache>("layout_cache", root)
+    {
+        cache.set_arranged_size(entity, parent_size, size);
+    }
+
+    size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_properties_are_sorted() {
+        let mut sorted = LAYOUT_PROPERTIES;
+        sorted.sort();
+
+        assert_eq!(sorted, LAYOUT_PROPERTIES);
+        assert!(affects_layout("margin"));
+        assert!(!affects_layout("background"));
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let mut cache = LayoutCache::new();
+        let entity = Entity::from(1);
+
+        cache.begin_frame();
+        cache.set_desired_size(entity, DirtySize::new());
+        cache.set_arranged_size(entity, (10.0, 10.0), (5.0, 5.0));
+        cache.end_frame(|_| true);
+
+        assert_eq!(cache.laid_out_nodes(), 1);
+        assert!(cache.is_valid(entity));
+        assert_eq!(cache.arranged_size(entity, (10.0, 10.0)), Some((5.0, 5.0)));
+        assert_eq!(cache.arranged_size(entity, (20.0, 10.0)), None);
+
+        cache.invalidate(entity);
+        assert!(cache.desired_size(entity).is_none());
+
+        cache.begin_frame();
+        cache.end_frame(|_| false);
+
+        assert_eq!(cache.laid_out_nodes(), 0);
+        assert!(!cache.is_valid(entity));
+    }
+}
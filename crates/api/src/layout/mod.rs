@@ -7,6 +7,7 @@ use crate::{prelude::*, render::RenderContext2D, tree::Tree, utils::*};
 pub use self::absolute::*;
 pub use self::fixed_size::*;
 pub use self::grid::*;
+pub use self::layout_cache::*;
 pub use self::padding::*;
 pub use self::popup::*;
 pub use self::scroll::*;
@@ -16,6 +17,7 @@ pub use self::text_selection::*;
 mod absolute;
 mod fixed_size;
 mod grid;
+mod layout_cache;
 mod padding;
 mod popup;
 mod scroll;
@@ -7,7 +7,7 @@ use dces::prelude::Entity;
 
 use crate::{prelude::*, render::RenderContext2D, tree::Tree, utils::prelude::*};
 
-use super::{component, component_try_mut, try_component, Layout};
+use super::{arrange_layout, component, component_try_mut, measure_layout, try_component, Layout};
 
 /// Add padding to the widget.
 #[derive(Default)]
@@ -69,8 +69,14 @@ impl Layout for PopupLayout {
             let child = ecm.entity_store().children[&entity][index];
 
             if let Some(child_layout) = layouts.get(&child) {
-                let child_desired_size =
-                    child_layout.measure(render_context_2_d, child, ecm, layouts, theme);
+                let child_desired_size = measure_layout(
+                    child_layout.as_ref(),
+                    render_context_2_d,
+                    child,
+                    ecm,
+                    layouts,
+                    theme,
+                );
                 let mut desired_size = self.desired_size.borrow().size();
 
                 let dirty = child_desired_size.dirty() || self.desired_size.borrow().dirty();
@@ -163,7 +169,8 @@ impl Layout for PopupLayout {
             let child_margin: Thickness = component(ecm, entity, "margin");
 
             if let Some(child_layout) = layouts.get(&child) {
-                child_layout.arrange(
+                arrange_layout(
+                    child_layout.as_ref(),
                     render_context_2_d,
                     available_size,
                     child,
@@ -8,7 +8,7 @@ use dces::prelude::Entity;
 
 use crate::{prelude::*, render::RenderContext2D, tree::Tree, utils::prelude::*};
 
-use super::{component, component_try_mut, Layout};
+use super::{arrange_layout, component, component_try_mut, measure_layout, Layout};
 
 /// IMPORTANT: The scroll layout will only work for the text box now. A update will follow!!!!
 #[derive(Default)]
@@ -64,9 +64,15 @@ impl Layout for ScrollLayout {
             let child = ecm.entity_store().children[&entity][index];
 
             if let Some(child_layout) = layouts.get(&child) {
-                let dirty = child_layout
-                    .measure(render_context_2_d, child, ecm, layouts, theme)
-                    .dirty()
+                let dirty = measure_layout(
+                    child_layout.as_ref(),
+                    render_context_2_d,
+                    child,
+                    ecm,
+                    layouts,
+                    theme,
+                )
+                .dirty()
                     || self.desired_size.borrow().dirty();
 
                 self.desired_size.borrow_mut().set_dirty(dirty);
@@ -171,7 +177,8 @@ impl Layout for ScrollLayout {
             let child_margin: Thickness = component(ecm, entity, "margin");
 
             if let Some(child_layout) = layouts.get(&child) {
-                child_size = child_layout.arrange(
+                child_size = arrange_layout(
+                    child_layout.as_ref(),
                     render_context_2_d,
                     available_size,
                     child,
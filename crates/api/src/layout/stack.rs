@@ -7,7 +7,9 @@ use dces::prelude::Entity;
 
 use crate::{prelude::*, render::RenderContext2D, tree::Tree, utils::prelude::*};
 
-use super::{component, component_or_default, component_try_mut, Layout};
+use super::{
+    arrange_layout, component, component_or_default, component_try_mut, measure_layout, Layout,
+};
 
 /// Stacks visual the children widgets vertical or horizontal.
 #[derive(Default)]
@@ -60,8 +62,14 @@ impl Layout for StackLayout {
             let child = ecm.entity_store().children[&entity][index];
 
             if let Some(child_layout) = layouts.get(&child) {
-                let child_desired_size =
-                    child_layout.measure(render_context_2_d, child, ecm, layouts, theme);
+                let child_desired_size = measure_layout(
+                    child_layout.as_ref(),
+                    render_context_2_d,
+                    child,
+                    ecm,
+                    layouts,
+                    theme,
+                );
 
                 let mut child_margin = {
                     if child_desired_size.width() > 0.0 && child_desired_size.height() > 0.0 {
@@ -158,8 +166,15 @@ impl Layout for StackLayout {
 
             let mut child_desired_size = (0.0, 0.0);
             if let Some(child_layout) = layouts.get(&child) {
-                child_desired_size =
-                    child_layout.arrange(render_context_2_d, size, child, ecm, layouts, theme);
+                child_desired_size = arrange_layout(
+                    child_layout.as_ref(),
+                    render_context_2_d,
+                    size,
+                    child,
+                    ecm,
+                    layouts,
+                    theme,
+                );
             }
 
             let mut child_margin = {
@@ -7,7 +7,7 @@ use dces::prelude::Entity;
 
 use crate::{prelude::*, render::RenderContext2D, tree::Tree, utils::prelude::*};
 
-use super::{component, component_try_mut, try_component, Layout};
+use super::{arrange_layout, component, component_try_mut, measure_layout, try_component, Layout};
 
 /// The text selection layout is used to measure and arrange a text selection cursor.
 #[derive(Default)]
@@ -59,9 +59,15 @@ impl Layout for TextSelectionLayout {
             let child = ecm.entity_store().children[&entity][index];
 
             if let Some(child_layout) = layouts.get(&child) {
-                let dirty = child_layout
-                    .measure(render_context_2_d, child, ecm, layouts, theme)
-                    .dirty()
+                let dirty = measure_layout(
+                    child_layout.as_ref(),
+                    render_context_2_d,
+                    child,
+                    ecm,
+                    layouts,
+                    theme,
+                )
+                .dirty()
                     || self.desired_size.borrow().dirty();
                 self.desired_size.borrow_mut().set_dirty(dirty);
             }
@@ -81,9 +87,15 @@ impl Layout for TextSelectionLayout {
             let child = ecm.entity_store().children[&entity][index];
 
             if let Some(child_layout) = layouts.get(&child) {
-                let dirty = child_layout
-                    .measure(render_context_2_d, child, ecm, layouts, theme)
-                    .dirty()
+                let dirty = measure_layout(
+                    child_layout.as_ref(),
+                    render_context_2_d,
+                    child,
+                    ecm,
+                    layouts,
+                    theme,
+                )
+                .dirty()
                     || self.desired_size.borrow().dirty();
                 self.desired_size.borrow_mut().set_dirty(dirty);
             }
@@ -127,6 +139,16 @@ impl Layout for TextSelectionLayout {
             if let Some(text) = try_component::<String16>(ecm, text_block, "text") {
                 let font: String = component(ecm, text_block, "font");
                 let font_size: f64 = component(ecm, text_block, "font_size");
+                render_context_2_d.set_font_weight(
+                    try_component::<FontWeight>(ecm, text_block, "font_weight").unwrap_or_default(),
+                );
+                render_context_2_d.set_font_style(
+                    try_component::<FontStyle>(ecm, text_block, "font_style").unwrap_or_default(),
+                );
+                render_context_2_d.set_font_stretch(
+                    try_component::<FontStretch>(ecm, text_block, "font_stretch")
+                        .unwrap_or_default(),
+                );
                 text_len = text.len();
 
                 if let Some(selection) =
@@ -206,7 +228,15 @@ impl Layout for TextSelectionLayout {
             let child = ecm.entity_store().children[&entity][index];
 
             if let Some(child_layout) = layouts.get(&child) {
-                child_layout.arrange(render_context_2_d, size, child, ecm, layouts, theme);
+                arrange_layout(
+                    child_layout.as_ref(),
+                    render_context_2_d,
+                    size,
+                    child,
+                    ecm,
+                    layouts,
+                    theme,
+                );
             }
         }
 
@@ -15,6 +15,7 @@ pub use orbtk_theme::prelude as theme;
 pub use orbtk_tree::prelude as tree;
 pub use orbtk_utils::prelude as utils;
 
+pub mod animation;
 pub mod application;
 #[macro_use]
 pub mod event;
@@ -24,6 +25,8 @@ pub mod properties;
 pub mod render_object;
 pub mod services;
 pub mod systems;
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "pfinder")))]
+pub mod testing;
 pub mod widget;
 
 #[macro_use]
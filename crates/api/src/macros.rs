@@ -475,6 +475,20 @@ macro_rules! widget {
                     )*
                 )*
 
+                // register the property types, they are used to set properties by the theme
+                let mut property_types = PropertyTypes::new();
+                property_types.insert::<Alignment>("v_align");
+                property_types.insert::<Alignment>("h_align");
+                property_types.insert::<Visibility>("visibility");
+                property_types.insert::<Thickness>("margin");
+                property_types.insert::<f32>("opacity");
+                $(
+                    $(
+                        property_types.insert::<$property_type>(stringify!($property));
+                    )*
+                )*
+                ctx.register_property("property_types", entity, property_types);
+
                 ctx.update_theme_by_state(entity);
 
                 // register event handlers
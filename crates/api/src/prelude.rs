@@ -1,6 +1,7 @@
 pub use dces::prelude::{Entity, EntityComponentManager, StringComponentStore};
 
 pub use crate::{
+    animation::*,
     application::*,
     css_engine::{Selector, SelectorRelation, Theme as ThemeValue, ThemeBuilder},
     event::*,
@@ -88,7 +88,11 @@ into_property_source!(i64);
 
 // Implementation of PropertySource for utils types
 into_property_source!(utils::Alignment: &str);
+into_property_source!(utils::BoxShadow: (f64, f64, f64, utils::Color), (f64, f64, f64, f64, utils::Color));
 into_property_source!(utils::Brush: &str, utils::Color);
+into_property_source!(utils::FontStretch: &str);
+into_property_source!(utils::FontStyle: &str);
+into_property_source!(utils::FontWeight: &str, u16);
 into_property_source!(utils::Orientation: &str);
 into_property_source!(utils::Point: f64, i32, (i32, i32), (f64, f64));
 into_property_source!(utils::Rectangle: (i32, i32, i32, i32), (f64, f64, f64, f64));
@@ -100,8 +104,11 @@ into_property_source!(
     (i32, i32, i32, i32),
     (f64, f64, f64, f64)
 );
+into_property_source!(utils::Stretch: &str);
 into_property_source!(utils::String16: &str, String);
 into_property_source!(utils::SelectionMode: &str);
+into_property_source!(utils::TextAlignment: &str);
+into_property_source!(utils::TextWrap: &str, bool);
 into_property_source!(utils::Visibility: &str);
 into_property_source!(Vec<String>);
 
@@ -111,11 +118,14 @@ into_property_source!(css_engine::Theme);
 
 // Implementation of render property types
 into_property_source!(render::Image: &str, String, (u32, u32, Vec<u32>));
+into_property_source!(render::AnimatedImage: &str, String);
+into_property_source!(render::Svg: &str, String);
 
 // Implementation of custom property types
 into_property_source!(Columns: ColumnsBuilder);
 into_property_source!(Constraint: ConstraintBuilder);
 into_property_source!(RenderPipeline);
+into_property_source!(RenderPipeline2D);
 into_property_source!(Rows: RowsBuilder);
 into_property_source!(ScrollViewerMode: (&str, &str));
 into_property_source!(SelectedEntities: HashSet<Entity>);
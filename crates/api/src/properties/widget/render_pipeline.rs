@@ -22,6 +22,23 @@ impl render::RenderPipeline for EmptyRenderPipeline {
     fn draw(&self, _: &mut render::RenderTarget) {}
 }
 
+impl render::Pipeline2D for EmptyRenderPipeline {
+    fn box_eq(&self, other: &dyn Any) -> bool {
+        other.downcast_ref::<Self>().map_or(false, |a| self == a)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn render::Pipeline2D> {
+        Box::new(self.clone())
+    }
+}
+
+impl render::RenderPipeline2D for EmptyRenderPipeline {
+    fn draw(&self, _: &mut render::RenderContext2D, _: f64, _: f64) {}
+}
+
 /// RenderPipeline object.
 #[derive(Clone, Debug)]
 pub struct RenderPipeline(pub Box<dyn render::Pipeline>);
@@ -31,3 +48,13 @@ impl Default for RenderPipeline {
         RenderPipeline(Box::new(EmptyRenderPipeline))
     }
 }
+
+/// RenderPipeline2D object, its pipeline draws with the 2D api of the render context.
+#[derive(Clone, Debug)]
+pub struct RenderPipeline2D(pub Box<dyn render::Pipeline2D>);
+
+impl Default for RenderPipeline2D {
+    fn default() -> Self {
+        RenderPipeline2D(Box::new(EmptyRenderPipeline))
+    }
+}
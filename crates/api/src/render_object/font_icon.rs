@@ -37,7 +37,13 @@ impl RenderObject for FontIconRenderObject {
             ctx.render_context_2_d().begin_path();
             ctx.render_context_2_d().set_font_family(icon_font);
             ctx.render_context_2_d().set_font_size(icon_size);
-            ctx.render_context_2_d().set_fill_style(icon_brush);
+            ctx.render_context_2_d()
+                .set_fill_style(icon_brush.with_bounds(&Rectangle::new(
+                    global_position.x + bounds.x,
+                    global_position.y + bounds.y,
+                    bounds.width,
+                    bounds.height,
+                )));
 
             ctx.render_context_2_d().fill_text(
                 &icon,
@@ -1,6 +1,13 @@
-use crate::{prelude::*, render::Image, utils::*};
+use crate::{
+    prelude::*,
+    render::{AnimatedImage, Image, Svg},
+    utils::*,
+};
 
-/// Used to render an image.
+/// Used to render an image. The image is resized by the `stretch` property or sliced by the
+/// `nine_patch` property, while it is loading the `placeholder` brush is drawn. If the widget has
+/// a `svg` property that is not empty, the vector graphic is drawn into its bounds instead. If it
+/// has an `animated_image` property that is not empty, the frame at `frame_index` is drawn.
 pub struct ImageRenderObject;
 
 impl Into<Box<dyn RenderObject>> for ImageRenderObject {
@@ -11,17 +18,90 @@ impl Into<Box<dyn RenderObject>> for ImageRenderObject {
 
 impl RenderObject for ImageRenderObject {
     fn render_self(&self, ctx: &mut Context, global_position: &Point) {
-        let (_, mut image) = {
+        let (bounds, mut image, svg, icon_brush, stretch, insets, placeholder) = {
             let widget = ctx.widget();
             (
                 widget.clone::<Rectangle>("bounds"),
-                widget.try_clone::<Image>("image"),
+                // the current frame of an animated image is drawn instead of the image
+                widget
+                    .try_get::<AnimatedImage>("animated_image")
+                    .and_then(|animated_image| {
+                        animated_image.frame(widget.clone_or_default::<usize>("frame_index"))
+                    })
+                    .map(|frame| frame.image.clone())
+                    .or_else(|| widget.try_clone::<Image>("image")),
+                widget.try_clone::<Svg>("svg"),
+                widget.try_clone::<Brush>("icon_brush"),
+                widget.clone_or_default::<Stretch>("stretch"),
+                widget.clone_or_default::<Thickness>("nine_patch"),
+                widget.clone_or_default::<Brush>("placeholder"),
             )
         };
 
-        if let Some(image) = &mut image {
-            ctx.render_context_2_d()
-                .draw_image(image, global_position.x, global_position.y);
+        let area = Rectangle::new(
+            global_position.x + bounds.x(),
+            global_position.y + bounds.y(),
+            bounds.width(),
+            bounds.height(),
+        );
+
+        if let Some(svg) = svg.filter(|svg| !svg.is_empty()) {
+            // icons are recolored with their icon brush
+            let color = match icon_brush {
+                Some(Brush::SolidColor(color)) => Some(color),
+                _ => None,
+            };
+
+            svg.render(ctx.render_context_2_d(), area, color);
+            return;
+        }
+
+        let image = match &mut image {
+            Some(image) => image,
+            None => return,
+        };
+
+        if image.is_loading() {
+            if !placeholder.is_transparent() {
+                ctx.render_context_2_d().begin_path();
+                ctx.render_context_2_d()
+                    .rect(area.x, area.y, area.width, area.height);
+                ctx.render_context_2_d()
+                    .set_fill_style(placeholder.with_bounds(&area));
+                ctx.render_context_2_d().fill();
+            }
+            return;
         }
+
+        let image_size = (image.width(), image.height());
+
+        if insets != Thickness::default() {
+            for (clip, target) in nine_patch(image_size, insets, area) {
+                ctx.render_context_2_d().draw_image_with_clip_and_size(
+                    image,
+                    clip,
+                    target.x,
+                    target.y,
+                    target.width,
+                    target.height,
+                );
+            }
+            return;
+        }
+
+        if stretch == Stretch::None {
+            ctx.render_context_2_d().draw_image(image, area.x, area.y);
+            return;
+        }
+
+        let (clip, target) = stretch.rects(image_size, area);
+        ctx.render_context_2_d().draw_image_with_clip_and_size(
+            image,
+            clip,
+            target.x,
+            target.y,
+            target.width,
+            target.height,
+        );
     }
 }
@@ -3,7 +3,11 @@
 use std::{any::Any, collections::BTreeMap};
 
 use crate::{
-    application::ContextProvider, css_engine::*, prelude::*, render::RenderContext2D, utils::*,
+    application::{paint_area, ContextProvider},
+    css_engine::*,
+    prelude::*,
+    render::RenderContext2D,
+    utils::*,
 };
 
 pub use self::default::*;
@@ -72,10 +76,30 @@ pub trait RenderObject: Any {
             }
         }
 
-        self.render_self(
-            &mut Context::new((entity, ecm), &theme, context_provider, render_context),
-            &global_position,
-        );
+        // widgets outside of the repainted region keep their pixels of the last frame
+        let in_region = match (
+            context_provider.render_region.get(),
+            ecm.component_store().get::<Rectangle>("bounds", entity),
+        ) {
+            (Some(region), Ok(bounds)) => region.intersects(&paint_area(
+                ecm.component_store(),
+                entity,
+                Rectangle::new(
+                    global_position.x + bounds.x(),
+                    global_position.y + bounds.y(),
+                    bounds.width(),
+                    bounds.height(),
+                ),
+            )),
+            _ => true,
+        };
+
+        if in_region {
+            self.render_self(
+                &mut Context::new((entity, ecm), &theme, context_provider, render_context),
+                &global_position,
+            );
+        }
 
         let mut global_pos = (0.0, 0.0);
 
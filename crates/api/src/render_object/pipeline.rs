@@ -1,5 +1,8 @@
 use crate::{prelude::*, utils::*};
 
+/// Used to render the `render_pipeline` of a widget into a pixel buffer and to draw its
+/// `render_pipeline_2_d` with the 2D api of the render context, clipped to the bounds of the
+/// widget.
 pub struct PipelineRenderObject;
 
 impl Into<Box<dyn RenderObject>> for PipelineRenderObject {
@@ -9,20 +12,42 @@ impl Into<Box<dyn RenderObject>> for PipelineRenderObject {
 }
 
 impl RenderObject for PipelineRenderObject {
-    fn render_self(&self, ctx: &mut Context, _: &Point) {
-        let bounds = *ctx.widget().get::<Rectangle>("bounds");
-        let pipeline = ctx
-            .widget()
-            .get::<RenderPipeline>("render_pipeline")
-            .0
-            .clone();
+    fn render_self(&self, ctx: &mut Context, global_position: &Point) {
+        let (bounds, pipeline, pipeline_2_d) = {
+            let widget = ctx.widget();
+            (
+                widget.clone::<Rectangle>("bounds"),
+                widget
+                    .try_get::<RenderPipeline>("render_pipeline")
+                    .map(|pipeline| pipeline.0.clone()),
+                widget
+                    .try_get::<RenderPipeline2D>("render_pipeline_2_d")
+                    .map(|pipeline| pipeline.0.clone()),
+            )
+        };
 
-        ctx.render_context_2_d().draw_pipeline(
-            bounds.x,
-            bounds.y,
-            bounds.width,
-            bounds.height,
-            pipeline,
-        );
+        if let Some(pipeline) = pipeline {
+            ctx.render_context_2_d().draw_pipeline(
+                bounds.x,
+                bounds.y,
+                bounds.width,
+                bounds.height,
+                pipeline,
+            );
+        }
+
+        if let Some(pipeline) = pipeline_2_d {
+            let x = global_position.x + bounds.x;
+            let y = global_position.y + bounds.y;
+            let render_context = ctx.render_context_2_d();
+
+            render_context.save();
+            render_context.begin_path();
+            render_context.rect(x, y, bounds.width, bounds.height);
+            render_context.clip();
+            render_context.translate(x, y);
+            pipeline.draw_pipeline(render_context, bounds.width, bounds.height);
+            render_context.restore();
+        }
     }
 }
@@ -4,7 +4,7 @@ use crate::{
     prelude::*,
     render::RenderContext2D,
     utils,
-    utils::{Brush, Point, Rectangle, Thickness},
+    utils::{BoxShadow, Brush, Point, Rectangle, Thickness},
 };
 
 pub struct RectangleRenderObject;
@@ -127,6 +127,50 @@ impl RectangleRenderObject {
             render_context_2_d.stroke();
         }
     }
+
+    // Renders the shadow of the widget and blurs its backdrop. Both are drawn below the
+    // background.
+    fn render_effects(
+        &self,
+        render_context_2_d: &mut RenderContext2D,
+        area: Rectangle,
+        border_radius: f64,
+        box_shadow: BoxShadow,
+        backdrop_blur: f64,
+    ) {
+        if box_shadow.is_visible() {
+            let shape = box_shadow.shape(&area);
+            render_context_2_d.begin_path();
+
+            if area.width == area.height && border_radius >= area.width / 2.0 {
+                self.render_circle(
+                    render_context_2_d,
+                    shape.x,
+                    shape.y,
+                    shape.width,
+                    shape.height,
+                    shape.width / 2.0,
+                );
+            } else if border_radius > 0. {
+                self.render_rounded_rect_path(
+                    render_context_2_d,
+                    shape.x,
+                    shape.y,
+                    shape.width,
+                    shape.height,
+                    (border_radius + box_shadow.spread).max(0.0),
+                );
+            } else {
+                render_context_2_d.rect(shape.x, shape.y, shape.width, shape.height);
+            }
+
+            render_context_2_d.fill_shadow(box_shadow.color, box_shadow.blur);
+        }
+
+        if backdrop_blur > 0. {
+            render_context_2_d.blur_rect(area.x, area.y, area.width, area.height, backdrop_blur);
+        }
+    }
 }
 
 impl Into<Box<dyn RenderObject>> for RectangleRenderObject {
@@ -137,7 +181,15 @@ impl Into<Box<dyn RenderObject>> for RectangleRenderObject {
 
 impl RenderObject for RectangleRenderObject {
     fn render_self(&self, ctx: &mut Context, global_position: &Point) {
-        let (bounds, background, border_radius, border_thickness, border_brush) = {
+        let (
+            bounds,
+            background,
+            border_radius,
+            border_thickness,
+            border_brush,
+            box_shadow,
+            backdrop_blur,
+        ) = {
             let widget = ctx.widget();
             (
                 widget.clone::<Rectangle>("bounds"),
@@ -145,9 +197,29 @@ impl RenderObject for RectangleRenderObject {
                 widget.clone_or_default::<f64>("border_radius"),
                 widget.clone_or_default::<Thickness>("border_width"),
                 widget.clone_or_default::<Brush>("border_brush"),
+                widget.clone_or_default::<BoxShadow>("box_shadow"),
+                widget.clone_or_default::<f64>("backdrop_blur"),
             )
         };
 
+        // css gradients are relative to the bounds of the widget
+        let area = Rectangle::new(
+            global_position.x + bounds.x(),
+            global_position.y + bounds.y(),
+            bounds.width(),
+            bounds.height(),
+        );
+        let background = background.with_bounds(&area);
+        let border_brush = border_brush.with_bounds(&area);
+
+        self.render_effects(
+            ctx.render_context_2_d(),
+            area,
+            border_radius,
+            box_shadow,
+            backdrop_blur,
+        );
+
         if (bounds.width() == 0.0
             || bounds.height() == 0.0
             || (background.is_transparent() && border_brush.is_transparent()))
@@ -1,6 +1,10 @@
 use crate::{
     prelude::*,
-    utils::{Brush, Point, Rectangle, String16},
+    render::{layout_text, TextLayoutConfig},
+    utils::{
+        Brush, FontStretch, FontStyle, FontWeight, Point, Rectangle, String16, TextAlignment,
+        TextWrap,
+    },
 };
 
 /// Used to render a text.
@@ -14,7 +18,17 @@ impl Into<Box<dyn RenderObject>> for TextRenderObject {
 
 impl RenderObject for TextRenderObject {
     fn render_self(&self, ctx: &mut Context, global_position: &Point) {
-        let (bounds, text, foreground, font, font_size) = {
+        let (
+            bounds,
+            text,
+            foreground,
+            font,
+            font_size,
+            font_weight,
+            font_style,
+            font_stretch,
+            config,
+        ) = {
             let widget = ctx.widget();
             let text = widget.clone::<String16>("text");
 
@@ -31,6 +45,10 @@ impl RenderObject for TextRenderObject {
                 widget.get::<Brush>("foreground").clone(),
                 widget.get::<String>("font").clone(),
                 *widget.get::<f64>("font_size"),
+                widget.clone_or_default::<FontWeight>("font_weight"),
+                widget.clone_or_default::<FontStyle>("font_style"),
+                widget.clone_or_default::<FontStretch>("font_stretch"),
+                text_layout_config(&widget, widget.get::<Rectangle>("bounds").width),
             )
         };
 
@@ -45,16 +63,51 @@ impl RenderObject for TextRenderObject {
 
         if !text.is_empty() {
             ctx.render_context_2_d().begin_path();
-            ctx.render_context_2_d().set_font_family(font);
+            ctx.render_context_2_d().set_font_family(font.clone());
             ctx.render_context_2_d().set_font_size(font_size);
-            ctx.render_context_2_d().set_fill_style(foreground);
+            ctx.render_context_2_d().set_font_weight(font_weight);
+            ctx.render_context_2_d().set_font_style(font_style);
+            ctx.render_context_2_d().set_font_stretch(font_stretch);
+            ctx.render_context_2_d()
+                .set_fill_style(foreground.with_bounds(&Rectangle::new(
+                    global_position.x + bounds.x,
+                    global_position.y + bounds.y,
+                    bounds.width,
+                    bounds.height,
+                )));
 
-            ctx.render_context_2_d().fill_text(
-                &text,
-                global_position.x + bounds.x,
-                global_position.y + bounds.y,
-            );
+            let layout = layout_text(&text, &config, |line| {
+                ctx.render_context_2_d()
+                    .measure(line, font_size, font.as_str())
+                    .width
+            });
+
+            for line in &layout.lines {
+                ctx.render_context_2_d().fill_text(
+                    &line.text,
+                    global_position.x + bounds.x + line.x,
+                    global_position.y + bounds.y + line.y,
+                );
+            }
             ctx.render_context_2_d().close_path();
         }
     }
 }
+
+/// Reads the line height, text alignment, wrapping and maximum number of lines of the widget.
+/// Lines are as high as the font size if no line height is set.
+pub(crate) fn text_layout_config(widget: &WidgetContainer, max_width: f64) -> TextLayoutConfig {
+    let line_height = widget.clone_or_default::<f64>("line_height");
+
+    TextLayoutConfig {
+        max_width,
+        line_height: if line_height > 0.0 {
+            line_height
+        } else {
+            widget.clone_or_default::<f64>("font_size").ceil()
+        },
+        text_align: widget.clone_or_default::<TextAlignment>("text_align"),
+        text_wrap: widget.clone_or_default::<TextWrap>("text_wrap"),
+        max_lines: widget.clone_or_default::<usize>("max_lines"),
+    }
+}
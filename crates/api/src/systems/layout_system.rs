@@ -37,7 +37,26 @@ impl System<Tree, StringComponentStore, RenderContext2D> for LayoutSystem {
             .unwrap()
             .clone();
 
-        self.context_provider.layouts.borrow()[&root].measure(
+        if let Ok(cache) = ecm
+            .component_store_mut()
+            .get_mut::<LayoutCache>("layout_cache", root)
+        {
+            cache.begin_frame();
+        }
+
+        // popups are placed relative to their targets, which could move without invalidating the
+        // layout of the popup
+        if let Some(overlay) = ecm.entity_store().overlay {
+            let popups = ecm.entity_store().children[&overlay].clone();
+
+            for popup in popups {
+                invalidate_layout(ecm, popup);
+            }
+        }
+
+        // only widgets with an invalid layout and their ancestors are laid out again
+        measure_layout(
+            self.context_provider.layouts.borrow()[&root].as_ref(),
             render_context,
             root,
             ecm,
@@ -45,7 +64,8 @@ impl System<Tree, StringComponentStore, RenderContext2D> for LayoutSystem {
             &theme,
         );
 
-        self.context_provider.layouts.borrow()[&root].arrange(
+        arrange_layout(
+            self.context_provider.layouts.borrow()[&root].as_ref(),
             render_context,
             window_size,
             root,
@@ -54,6 +74,15 @@ impl System<Tree, StringComponentStore, RenderContext2D> for LayoutSystem {
             &theme,
         );
 
+        // removed widgets do not have a layout anymore
+        let layouts = self.context_provider.layouts.borrow();
+        if let Ok(cache) = ecm
+            .component_store_mut()
+            .get_mut::<LayoutCache>("layout_cache", root)
+        {
+            cache.end_frame(|entity| layouts.contains_key(&entity));
+        }
+
         // if self.debug_flag.get() {
         //     println!("\n------ End layout update   ------\n");
         // }
@@ -1,13 +1,92 @@
-use std::collections::BTreeMap;
+use std::{cell::RefCell, collections::BTreeMap};
 
 use dces::prelude::{EntityComponentManager, System};
 
-use crate::{css_engine::*, prelude::*, render::RenderContext2D, tree::Tree};
+use crate::{
+    application::paint_area,
+    css_engine::*,
+    prelude::*,
+    render::RenderContext2D,
+    tree::Tree,
+    utils::{Point, Rectangle, Visibility},
+};
 
 /// The `RenderSystem` iterates over all visual widgets and used its render objects to draw them on the screen.
-#[derive(Constructor)]
+/// Only the regions of the window that are covered by changed widgets are repainted.
 pub struct RenderSystem {
     context_provider: ContextProvider,
+
+    // global bounds of the visible widgets of the last rendered frame
+    rendered_bounds: RefCell<BTreeMap<Entity, Rectangle>>,
+}
+
+impl RenderSystem {
+    /// Creates a new render system.
+    pub fn new(context_provider: ContextProvider) -> Self {
+        RenderSystem {
+            context_provider,
+            rendered_bounds: RefCell::new(BTreeMap::new()),
+        }
+    }
+
+    // Collects the global bounds of all visible widgets that are drawn by a render object.
+    // Widgets that are marked as dirty or have a dirty ancestor are collected in `dirty`.
+    fn collect_bounds(
+        &self,
+        entity: Entity,
+        ecm: &EntityComponentManager<Tree, StringComponentStore>,
+        (offset, parent_dirty): (Point, bool),
+        bounds: &mut BTreeMap<Entity, Rectangle>,
+        dirty: &mut Vec<Entity>,
+    ) {
+        let (entity_store, store) = ecm.stores();
+
+        if store.get::<Visibility>("visibility", entity).ok() != Some(&Visibility::Visible) {
+            return;
+        }
+
+        let mut offset = offset;
+
+        if let Ok(b) = store.get::<Rectangle>("bounds", entity) {
+            offset = Point::new(offset.x + b.x(), offset.y + b.y());
+            bounds.insert(
+                entity,
+                paint_area(
+                    store,
+                    entity,
+                    Rectangle::new(offset.x, offset.y, b.width(), b.height()),
+                ),
+            );
+        }
+
+        let is_dirty = parent_dirty
+            || store
+                .get::<DirtyWidgets>("dirty_widgets", entity_store.root())
+                .map_or(true, |d| d.contains(entity));
+
+        if is_dirty {
+            dirty.push(entity);
+        }
+
+        for child in &entity_store.children[&entity] {
+            if self
+                .context_provider
+                .render_objects
+                .borrow()
+                .contains_key(child)
+            {
+                self.collect_bounds(*child, ecm, (offset, is_dirty), bounds, dirty);
+            }
+        }
+    }
+}
+
+// Checks if the damaged rectangle covers the whole window.
+fn covers(damaged: &Rectangle, window: &Rectangle) -> bool {
+    damaged.x() <= window.x()
+        && damaged.y() <= window.y()
+        && damaged.x() + damaged.width() >= window.x() + window.width()
+        && damaged.y() + damaged.height() >= window.y() + window.height()
 }
 
 impl System<Tree, StringComponentStore, RenderContext2D> for RenderSystem {
@@ -36,23 +115,101 @@ impl System<Tree, StringComponentStore, RenderContext2D> for RenderSystem {
             .unwrap()
             .clone();
 
-        let mut offsets = BTreeMap::new();
-        offsets.insert(root, (0.0, 0.0));
-
-        // CONSOLE.time("render");
-
-        render_context.start();
-        render_context.begin_path();
-        self.context_provider.render_objects.borrow()[&root].render(
-            render_context,
+        // compares the widgets with the last frame to find the damaged regions
+        let mut bounds = BTreeMap::new();
+        let mut dirty = vec![];
+        self.collect_bounds(
             root,
             ecm,
-            &self.context_provider,
-            &theme,
-            &mut offsets,
-            debug,
+            (Point::default(), false),
+            &mut bounds,
+            &mut dirty,
         );
+
+        let mut damage = Damage::default();
+        {
+            let rendered_bounds = self.rendered_bounds.borrow();
+
+            for entity in dirty {
+                for rect in bounds
+                    .get(&entity)
+                    .iter()
+                    .chain(rendered_bounds.get(&entity).iter())
+                {
+                    damage.add(**rect);
+                }
+            }
+
+            // moved or resized widgets are repainted at their old and their new place
+            for (entity, rect) in bounds.iter() {
+                let rendered = rendered_bounds.get(entity);
+
+                if rendered != Some(rect) {
+                    damage.add(*rect);
+
+                    if let Some(rendered) = rendered {
+                        damage.add(*rendered);
+                    }
+                }
+            }
+
+            for (entity, rect) in rendered_bounds.iter() {
+                if !bounds.contains_key(entity) {
+                    damage.add(*rect);
+                }
+            }
+        }
+
+        if let Ok(dirty_widgets) = ecm
+            .component_store_mut()
+            .get_mut::<DirtyWidgets>("dirty_widgets", root)
+        {
+            dirty_widgets.clear();
+        }
+
+        // nothing is changed, the last frame is kept
+        if damage.is_empty() {
+            return;
+        }
+
+        let window = ecm
+            .component_store()
+            .get::<Rectangle>("bounds", root)
+            .map(|b| Rectangle::new(0.0, 0.0, b.width(), b.height()))
+            .unwrap_or_default();
+
+        let regions: Vec<Option<Rectangle>> = match damage.bounds() {
+            Some(damaged) if covers(&damaged, &window) => vec![None],
+            _ => damage.rects().iter().map(|r| Some(*r)).collect(),
+        };
+
+        // CONSOLE.time("render");
+
+        for region in regions {
+            let mut offsets = BTreeMap::new();
+            offsets.insert(root, (0.0, 0.0));
+
+            match region {
+                Some(region) => render_context.start_region(region),
+                None => render_context.start(),
+            }
+
+            self.context_provider.render_region.set(region);
+            render_context.begin_path();
+            self.context_provider.render_objects.borrow()[&root].render(
+                render_context,
+                root,
+                ecm,
+                &self.context_provider,
+                &theme,
+                &mut offsets,
+                debug,
+            );
+        }
+
         render_context.finish();
+        self.context_provider.render_region.set(None);
+        *self.rendered_bounds.borrow_mut() = bounds;
 
         //  print_tree(root, 0, ecm);
     }
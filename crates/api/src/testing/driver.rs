@@ -0,0 +1,288 @@
+use std::{fmt::Debug, sync::mpsc};
+
+use dces::prelude::{Component, Entity};
+
+use crate::{
+    application::switch_theme,
+    css_engine::Selector,
+    prelude::*,
+    render::RenderContext2D,
+    shell::{self, ButtonState, Key, KeyEvent, MouseButton, ShellRequest, WindowRequest},
+    utils::{Point, Rectangle},
+};
+
+/// The `TestDriver` is used to automate a window in tests. It runs the widget tree of the window
+/// without a window shell, one iteration after another. Widgets could be looked up by their css
+/// id or element, events could be pushed to them and their properties could be checked.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut driver = TestDriver::new(|ctx| {
+///     Window::new()
+///         .child(TextBox::new().id("input").build(ctx))
+///         .build(ctx)
+/// });
+///
+/// let input = driver.find_by_id("input").unwrap();
+/// driver.focus(input);
+/// driver.type_text("OrbTk");
+/// driver.assert_property(input, "text", String16::from("OrbTk"));
+/// ```
+pub struct TestDriver {
+    adapter: WindowAdapter,
+    render_context: RenderContext2D,
+    theme: ThemeValue,
+    window_requests: mpsc::Receiver<WindowRequest>,
+    _shell_requests: mpsc::Receiver<ShellRequest<WindowAdapter>>,
+    title: String,
+    open: bool,
+}
+
+impl TestDriver {
+    /// Creates a new test driver for the window created by `create_fn` and runs the first iteration.
+    pub fn new<F: Fn(&mut BuildContext) -> Entity + 'static>(create_fn: F) -> Self {
+        let (shell_sender, shell_requests) = mpsc::channel();
+        let (mut adapter, settings, window_requests) = create_window("", shell_sender, create_fn);
+
+        let mut render_context = RenderContext2D::new(settings.size.0, settings.size.1);
+
+        for (family, font) in settings.fonts {
+            render_context.register_font(&family, font);
+        }
+
+        let root = adapter.entity_component_manager().entity_store().root();
+        let theme = adapter
+            .entity_component_manager()
+            .component_store()
+            .get::<ThemeValue>("theme", root)
+            .unwrap()
+            .clone();
+
+        let mut driver = TestDriver {
+            adapter,
+            render_context,
+            theme,
+            window_requests,
+            _shell_requests: shell_requests,
+            title: settings.title,
+            open: true,
+        };
+
+        driver.step();
+        driver
+    }
+
+    /// Runs one iteration of the widget tree: handles the pending events, updates the states,
+    /// layouts and renders the window.
+    pub fn step(&mut self) {
+        shell::WindowAdapter::run(&mut self.adapter, &mut self.render_context);
+
+        let root = self.root();
+        if let Ok(theme) = self
+            .adapter
+            .entity_component_manager()
+            .component_store()
+            .get::<ThemeValue>("theme", root)
+        {
+            self.theme = theme.clone();
+        }
+
+        for request in self.window_requests.try_iter() {
+            match request {
+                WindowRequest::ChangeTitle(title) => self.title = title,
+                WindowRequest::Close => self.open = false,
+                WindowRequest::Redraw => {}
+            }
+        }
+    }
+
+    /// Replaces the theme of the window like `Context::switch_theme` and runs one iteration.
+    pub fn switch_theme(&mut self, theme: ThemeValue) {
+        switch_theme(self.adapter.entity_component_manager(), theme);
+        self.step();
+    }
+
+    /// Runs the given number of iterations.
+    pub fn steps(&mut self, count: usize) {
+        for _ in 0..count {
+            self.step();
+        }
+    }
+
+    /// Returns `false` if the window has requested to close.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Gets the current title of the window.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Gets the entity of the window.
+    pub fn root(&mut self) -> Entity {
+        self.adapter
+            .entity_component_manager()
+            .entity_store()
+            .root()
+    }
+
+    /// Gets the render context of the window, e.g. to read the rendered frame.
+    pub fn render_context(&mut self) -> &mut RenderContext2D {
+        &mut self.render_context
+    }
+
+    /// Gets the number of widgets that are laid out by the last iteration.
+    pub fn laid_out_nodes(&mut self) -> usize {
+        let root = self.root();
+        self.adapter
+            .entity_component_manager()
+            .component_store()
+            .get::<LayoutCache>("layout_cache", root)
+            .map_or(0, |cache| cache.laid_out_nodes())
+    }
+
+    /// Looks up a widget by its css id.
+    pub fn find_by_id(&mut self, id: &str) -> Option<Entity> {
+        let root = self.root();
+        self.adapter
+            .entity_component_manager()
+            .component_store()
+            .get::<Global>("global", root)
+            .ok()
+            .and_then(|global| global.id_map.get(id).copied())
+    }
+
+    /// Looks up all widgets with the given css element in tree order.
+    pub fn find_by_element(&mut self, element: &str) -> Vec<Entity> {
+        let (entity_store, store) = self.adapter.entity_component_manager().stores();
+
+        entity_store
+            .into_iter()
+            .filter(|entity| {
+                store
+                    .get::<Selector>("selector", *entity)
+                    .map_or(false, |selector| {
+                        selector.element.as_ref().map_or(false, |e| e == element)
+                    })
+            })
+            .collect()
+    }
+
+    /// Gets a widget container of the given entity to read and write its properties.
+    pub fn widget(&mut self, entity: Entity) -> WidgetContainer<'_> {
+        WidgetContainer::new(entity, self.adapter.entity_component_manager(), &self.theme)
+    }
+
+    /// Gets a clone of the property with the given key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the widget does not contains the property.
+    pub fn get<P: Component + Clone>(&mut self, entity: Entity, key: &str) -> P {
+        self.widget(entity).clone::<P>(key)
+    }
+
+    /// Sets the value of the property with the given key.
+    pub fn set<P: Component + Clone>(&mut self, entity: Entity, key: &str, value: P) {
+        self.widget(entity).set::<P>(key, value);
+    }
+
+    /// Checks if the property with the given key is equal to `expected`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the property is not equal to `expected`.
+    pub fn assert_property<P: Component + Clone + PartialEq + Debug>(
+        &mut self,
+        entity: Entity,
+        key: &str,
+        expected: P,
+    ) {
+        let value = self.get::<P>(entity, key);
+        assert_eq!(
+            value, expected,
+            "Property {} of widget {} does not match.",
+            key, entity.0
+        );
+    }
+
+    /// Gets the center of the widget in window coordinates.
+    pub fn center_of(&mut self, entity: Entity) -> Point {
+        let widget = self.widget(entity);
+        let position = *widget.get::<Point>("position");
+        let bounds = *widget.get::<Rectangle>("bounds");
+
+        Point::new(
+            position.x + bounds.width() / 2.0,
+            position.y + bounds.height() / 2.0,
+        )
+    }
+
+    /// Pushes a `ClickEvent` at the center of the widget and runs one iteration.
+    pub fn click(&mut self, entity: Entity) {
+        let position = self.center_of(entity);
+        self.push_event(ClickEvent { position }, entity);
+        self.step();
+    }
+
+    /// Simulates a press and release of the left mouse button at the given position. Each of both
+    /// is handled by its own iteration.
+    pub fn mouse_click(&mut self, x: f64, y: f64) {
+        for state in &[ButtonState::Down, ButtonState::Up] {
+            shell::WindowAdapter::mouse(&mut self.adapter, x, y);
+            shell::WindowAdapter::mouse_event(
+                &mut self.adapter,
+                shell::MouseEvent {
+                    x,
+                    y,
+                    button: MouseButton::Left,
+                    state: *state,
+                },
+            );
+            self.step();
+        }
+    }
+
+    /// Requests the keyboard focus for the given widget and runs one iteration.
+    pub fn focus(&mut self, entity: Entity) {
+        let root = self.root();
+        self.push_event(FocusEvent::RequestFocus(entity), root);
+        self.step();
+    }
+
+    /// Simulates a press and release of the given key. The `KeyDownEvent` and the `KeyUpEvent` are
+    /// handled by the focused widget, each of both in its own iteration.
+    pub fn press_key(&mut self, key: Key, text: impl Into<String>) {
+        let text = text.into();
+
+        for state in &[ButtonState::Down, ButtonState::Up] {
+            shell::WindowAdapter::key_event(
+                &mut self.adapter,
+                KeyEvent {
+                    key,
+                    state: *state,
+                    text: text.clone(),
+                },
+            );
+            self.step();
+        }
+    }
+
+    /// Types the given text char by char on the focused widget.
+    pub fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.press_key(Key::from(c), c.to_string());
+        }
+    }
+
+    /// Pushes an event to the event queue of the window. It is handled on the next iteration.
+    pub fn push_event<E: Event>(&mut self, event: E, source: Entity) {
+        self.adapter
+            .context_provider()
+            .event_queue
+            .borrow_mut()
+            .register_event(event, source);
+    }
+}
@@ -0,0 +1,7 @@
+//! This module contains helpers to test OrbTk widgets and applications without a display.
+
+pub use self::driver::*;
+pub use self::snapshot::*;
+
+mod driver;
+mod snapshot;
@@ -0,0 +1,167 @@
+use std::{env, fs, path::PathBuf, sync::mpsc};
+
+use dces::prelude::Entity;
+
+use crate::{prelude::*, render::RenderTarget, shell::headless::Shell};
+
+/// If this environment variable is set, all golden images are overwritten by the rendered ones.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "ORBTK_UPDATE_SNAPSHOTS";
+
+// Number of iterations the widget tree runs before the frame is taken. States that react on
+// the init run (e.g. layout or theme changes) are settled after the second iteration.
+const SNAPSHOT_ITERATIONS: usize = 2;
+
+/// Renders the window created by `create_fn` with the given size on a headless window shell and
+/// returns the rendered frame. The frame has the physical size of the window, e.g. twice the given
+/// size if the window has a scale factor of 2.
+pub fn render_snapshot<F: Fn(&mut BuildContext) -> Entity + 'static>(
+    size: (f64, f64),
+    create_fn: F,
+) -> Result<RenderTarget, String> {
+    let (request_sender, request_receiver) = mpsc::channel();
+    let (adapter, mut settings, receiver) = create_window("", request_sender, create_fn);
+    settings.size = size;
+
+    let mut shell = Shell::new(request_receiver);
+    shell
+        .create_window_from_settings(settings, adapter)
+        .request_receiver(receiver)
+        .build();
+
+    shell.windows_mut()[0].resize(size.0, size.1);
+
+    for _ in 0..SNAPSHOT_ITERATIONS {
+        if let Some(window) = shell.windows_mut().get_mut(0) {
+            window.request_update();
+        }
+        shell.step();
+    }
+
+    let window = shell
+        .windows()
+        .get(0)
+        .ok_or("Snapshot window was closed before it is rendered.")?;
+
+    let width = (size.0 * window.scale_factor()).round() as u32;
+    let height = (size.1 * window.scale_factor()).round() as u32;
+
+    if window.frame().len() != (width * height) as usize {
+        return Err(format!(
+            "Snapshot frame has {} pixels, expected {} for {}x{} physical pixels.",
+            window.frame().len(),
+            width * height,
+            width,
+            height
+        ));
+    }
+
+    RenderTarget::from_data(width, height, window.frame().to_vec())
+}
+
+/// The `Snapshot` is used to compare the rendered output of a window with a golden png image.
+///
+/// If the golden image does not exists it is created from the rendered output. On a mismatch the
+/// rendered output (`<name>.actual.png`) and a diff image (`<name>.diff.png`) are written next to
+/// the golden image.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// Snapshot::new("button").size(120.0, 40.0).tolerance(2).assert(|ctx| {
+///     Window::new()
+///         .child(Button::new().text("Click me").build(ctx))
+///         .build(ctx)
+/// });
+/// ```
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    name: String,
+    directory: PathBuf,
+    size: (f64, f64),
+    tolerance: u8,
+}
+
+impl Snapshot {
+    /// Creates a new snapshot. The golden image is stored as `tests/snapshots/<name>.png` inside of
+    /// the crate directory.
+    pub fn new(name: impl Into<String>) -> Self {
+        let directory = env::var("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default()
+            .join("tests")
+            .join("snapshots");
+
+        Snapshot {
+            name: name.into(),
+            directory,
+            size: (100.0, 100.0),
+            tolerance: 0,
+        }
+    }
+
+    /// Sets the directory of the golden images.
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = directory.into();
+        self
+    }
+
+    /// Sets the size of the rendered window.
+    pub fn size(mut self, width: f64, height: f64) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    /// Sets the maximum difference of a color channel of two pixels that are still seen as equal.
+    pub fn tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Gets the path of the golden image.
+    pub fn golden_path(&self) -> PathBuf {
+        self.directory.join(format!("{}.png", self.name))
+    }
+
+    /// Renders the window created by `create_fn` and compares it with the golden image.
+    pub fn compare<F: Fn(&mut BuildContext) -> Entity + 'static>(
+        &self,
+        create_fn: F,
+    ) -> Result<(), String> {
+        let actual = render_snapshot(self.size, create_fn)?;
+        let golden_path = self.golden_path();
+
+        if !golden_path.exists() || env::var(UPDATE_SNAPSHOTS_VAR).is_ok() {
+            fs::create_dir_all(&self.directory).map_err(|e| e.to_string())?;
+            return actual.save_png(golden_path);
+        }
+
+        let expected = RenderTarget::from_png(&golden_path)?;
+
+        if let Some(diff) = expected.diff(&actual, self.tolerance) {
+            let actual_path = self.directory.join(format!("{}.actual.png", self.name));
+            let diff_path = self.directory.join(format!("{}.diff.png", self.name));
+
+            actual.save_png(&actual_path)?;
+            diff.image.save_png(&diff_path)?;
+
+            return Err(format!(
+                "Snapshot {} does not match the golden image {:?}: {} pixels differ (max difference {}, tolerance {}). See {:?}.",
+                self.name,
+                golden_path,
+                diff.mismatched_pixels,
+                diff.max_difference,
+                self.tolerance,
+                diff_path
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Renders the window created by `create_fn` and panics if it does not match the golden image.
+    pub fn assert<F: Fn(&mut BuildContext) -> Entity + 'static>(&self, create_fn: F) {
+        if let Err(message) = self.compare(create_fn) {
+            panic!("{}", message);
+        }
+    }
+}
@@ -47,6 +47,13 @@ impl<'a> BuildContext<'a> {
             .entity_store_mut()
             .append_child(parent, child)
             .unwrap();
+
+        // rules with descendant or child combinators could only match after the child is part of
+        // the tree. The descendants of the child are themed with the whole tree after the window
+        // is built.
+        if self.theme.has_selector_relations() {
+            self.get_widget(child).update_properties_by_theme();
+        }
     }
 
     /// Appends a child to overlay (on the top of the main tree). If the overlay does not exists an
@@ -95,6 +102,13 @@ impl<'a> BuildContext<'a> {
         self.ecm
             .component_store_mut()
             .register_shared_by_source_key::<P>(key, source_key, target, source);
+        register_sharing(
+            self.ecm.component_store_mut(),
+            key,
+            source_key,
+            target,
+            source,
+        );
     }
 
     /// Registers a shared component box. Uses the key as source key
@@ -115,9 +129,22 @@ impl<'a> BuildContext<'a> {
         widget: Entity,
         property: SharedComponentBox,
     ) {
+        let (type_id, source) = property.consume();
         self.ecm
             .component_store_mut()
-            .register_shared_box_by_source_key(key, source_key, widget, property);
+            .register_shared_box_by_source_key(
+                key,
+                source_key,
+                widget,
+                SharedComponentBox::new(type_id, source),
+            );
+        register_sharing(
+            self.ecm.component_store_mut(),
+            key,
+            source_key,
+            widget,
+            source,
+        );
     }
 
     /// Registers a state with a widget.
@@ -5,6 +5,7 @@ use dces::prelude::{Entity, EntityComponentManager};
 use crate::{
     application::{create_window, ContextProvider},
     css_engine::*,
+    layout::invalidate_layout,
     prelude::*,
     render::RenderContext2D,
     shell::{ShellRequest, WindowRequest},
@@ -202,6 +203,8 @@ impl<'a> Context<'a> {
 
     /// Appends a child widget to the given parent.
     pub fn append_child_to<W: Widget>(&mut self, child: W, parent: Entity) {
+        invalidate_layout(self.ecm, parent);
+
         let bctx = &mut self.build_context();
         let child = child.build(bctx);
         bctx.append_child(parent, child);
@@ -211,6 +214,8 @@ impl<'a> Context<'a> {
     /// exists an error will be returned.
     pub fn append_child_to_overlay<W: Widget>(&mut self, child: W) -> Result<(), String> {
         if let Some(overlay) = self.ecm.entity_store().overlay {
+            invalidate_layout(self.ecm, overlay);
+
             let bctx = &mut self.build_context();
             let child = child.build(bctx);
             bctx.append_child(overlay, child);
@@ -222,6 +227,7 @@ impl<'a> Context<'a> {
 
     /// Appends a child widget by entity to the given parent.
     pub fn append_child_entity_to(&mut self, child: Entity, parent: Entity) {
+        invalidate_layout(self.ecm, parent);
         self.build_context().append_child(parent, child)
     }
 
@@ -277,6 +283,8 @@ impl<'a> Context<'a> {
             if let Some(parent) = self.ecm.entity_store().children.get_mut(&parent) {
                 parent.remove(index);
             }
+
+            invalidate_layout(self.ecm, parent);
         }
     }
 
@@ -437,6 +445,39 @@ impl<'a> Context<'a> {
             .expect("Context.show_window: Could not send shell request.");
     }
 
+    /// Replaces the theme of the window. The theme is applied to all widgets at the beginning of
+    /// the next iteration, until then `theme` references the current one. A theme watched by
+    /// `watch_theme` is not reloaded anymore.
+    pub fn switch_theme(&mut self, theme: ThemeValue) {
+        // drops the sender of the watched theme, so its thread stops
+        *self.provider.theme_watcher.borrow_mut() = None;
+
+        self.provider
+            .theme_sender
+            .send(theme)
+            .expect("Context.switch_theme: Could not send theme.");
+        self.send_window_request(WindowRequest::Redraw);
+    }
+
+    /// Builds the theme from the given builder and applies it to the window. Each time one of the
+    /// css files of the builder (`create_from_path`, `extension_path`) changes, the theme is
+    /// rebuilt and applied again. Invalid rules and declarations are skipped. Only one theme is
+    /// watched at a time, the last watched theme is not reloaded anymore.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn watch_theme(&mut self, builder: ThemeBuilder) {
+        let (stop_sender, stop) = mpsc::channel();
+
+        // drops the sender of the last watched theme, so its thread stops
+        *self.provider.theme_watcher.borrow_mut() = Some(stop_sender);
+
+        crate::application::watch_theme(
+            builder,
+            self.provider.theme_sender.clone(),
+            self.provider.window_sender.clone(),
+            stop,
+        );
+    }
+
     /// Returns a mutable reference of the 2d render ctx.
     pub fn render_context_2_d(&mut self) -> &mut RenderContext2D {
         self.render_context
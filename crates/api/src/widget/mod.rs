@@ -6,7 +6,9 @@ use crate::{css_engine::*, event::EventHandler, properties::AttachedProperty};
 
 pub use self::build_context::*;
 pub use self::context::*;
+pub use self::property_types::*;
 pub use self::registry::*;
+pub use self::shared_properties::*;
 pub use self::state::*;
 pub use self::states_context::*;
 pub use self::template::*;
@@ -14,7 +16,9 @@ pub use self::widget_container::*;
 
 mod build_context;
 mod context;
+mod property_types;
 mod registry;
+mod shared_properties;
 mod state;
 mod states_context;
 mod template;
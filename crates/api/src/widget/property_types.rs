@@ -0,0 +1,26 @@
+use std::{any::TypeId, collections::BTreeMap};
+
+/// Describes the types of the properties a widget is defined with. It is registered as
+/// `property_types` on each widget and used to set the properties of a widget by the css
+/// properties of the same name.
+#[derive(Clone, Default, Debug)]
+pub struct PropertyTypes {
+    types: BTreeMap<String, TypeId>,
+}
+
+impl PropertyTypes {
+    /// Creates an empty list of property types.
+    pub fn new() -> Self {
+        PropertyTypes::default()
+    }
+
+    /// Stores that the property with the given key is of type `P`.
+    pub fn insert<P: 'static>(&mut self, key: impl Into<String>) {
+        self.types.insert(key.into(), TypeId::of::<P>());
+    }
+
+    /// Gets the type of the property with the given key.
+    pub fn get(&self, key: &str) -> Option<TypeId> {
+        self.types.get(key).cloned()
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use dces::prelude::{Entity, StringComponentStore};
+
+/// Describes which properties of a widget are shared with other widgets. It is registered as
+/// `shared_properties` on each widget that takes part in the sharing of a property.
+#[derive(Clone, Default, Debug)]
+pub struct SharedProperties {
+    // the widget and key the property of the given key is shared from
+    sources: BTreeMap<String, (Entity, String)>,
+
+    // the widgets and keys that share the property of the given key
+    targets: BTreeMap<String, Vec<(Entity, String)>>,
+}
+
+impl SharedProperties {
+    /// Gets the widget and the key of its property the property with the given key is shared from.
+    pub fn source(&self, key: &str) -> Option<&(Entity, String)> {
+        self.sources.get(key)
+    }
+
+    /// Gets the widgets and the keys of their properties that share the property with the given key.
+    pub fn targets(&self, key: &str) -> &[(Entity, String)] {
+        self.targets
+            .get(key)
+            .map_or(&[], |targets| targets.as_slice())
+    }
+}
+
+fn shared_properties_mut(
+    store: &mut StringComponentStore,
+    entity: Entity,
+) -> &mut SharedProperties {
+    if store
+        .get::<SharedProperties>("shared_properties", entity)
+        .is_err()
+    {
+        store.register("shared_properties", entity, SharedProperties::default());
+    }
+
+    store
+        .get_mut::<SharedProperties>("shared_properties", entity)
+        .unwrap()
+}
+
+/// Stores that the property `key` of `target` is shared from the property `source_key` of
+/// `source`.
+pub(crate) fn register_sharing(
+    store: &mut StringComponentStore,
+    key: &str,
+    source_key: &str,
+    target: Entity,
+    source: Entity,
+) {
+    let target_key = (target, key.to_string());
+
+    // a property is shared from one source only
+    if let Some((old_source, old_source_key)) = shared_properties_mut(store, target)
+        .sources
+        .insert(key.to_string(), (source, source_key.to_string()))
+    {
+        if let Some(targets) = shared_properties_mut(store, old_source)
+            .targets
+            .get_mut(&old_source_key)
+        {
+            targets.retain(|t| *t != target_key);
+        }
+    }
+
+    shared_properties_mut(store, source)
+        .targets
+        .entry(source_key.to_string())
+        .or_default()
+        .push(target_key);
+}
+
+/// Gets the given widget and all widgets that share the property with the given key with it, also
+/// if the property is shared over several widgets.
+pub(crate) fn sharing_widgets(
+    store: &StringComponentStore,
+    entity: Entity,
+    key: &str,
+) -> Vec<Entity> {
+    let shared_properties = |entity: Entity| {
+        store
+            .get::<SharedProperties>("shared_properties", entity)
+            .ok()
+    };
+
+    // walks up to the widget that owns the property
+    let mut owner = (entity, key.to_string());
+    let mut visited = BTreeSet::new();
+
+    while visited.insert(owner.clone()) {
+        match shared_properties(owner.0).and_then(|s| s.source(&owner.1)) {
+            Some(source) => owner = source.clone(),
+            None => break,
+        }
+    }
+
+    let mut widgets = vec![];
+    let mut visited = BTreeSet::new();
+    let mut stack = vec![owner];
+
+    while let Some((entity, key)) = stack.pop() {
+        if !visited.insert((entity, key.clone())) {
+            continue;
+        }
+
+        if !widgets.contains(&entity) {
+            widgets.push(entity);
+        }
+
+        if let Some(shared_properties) = shared_properties(entity) {
+            stack.extend(shared_properties.targets(&key).iter().cloned());
+        }
+    }
+
+    widgets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sharing_widgets() {
+        let mut store = StringComponentStore::default();
+        let (view, text_box, text_block, label) = (Entity(0), Entity(1), Entity(2), Entity(3));
+
+        register_sharing(&mut store, "text", "text", text_box, view);
+        register_sharing(&mut store, "text", "text", text_block, text_box);
+        register_sharing(&mut store, "title", "text", label, view);
+
+        let mut widgets = sharing_widgets(&store, text_block, "text");
+        widgets.sort();
+        assert_eq!(widgets, vec![view, text_box, text_block, label]);
+
+        assert_eq!(sharing_widgets(&store, text_box, "font"), vec![text_box]);
+
+        // the text box shares its text from the label now
+        register_sharing(&mut store, "text", "title", text_box, label);
+        assert_eq!(
+            sharing_widgets(&store, view, "text"),
+            vec![view, label, text_box, text_block]
+        );
+    }
+}
@@ -1,13 +1,108 @@
 use std::any::TypeId;
 
 use crate::{
+    animation::{animation_value, set_animation_value},
+    application::mark_dirty,
     css_engine::*,
+    layout::invalidate_property_layout,
     prelude::*,
-    utils::{Brush, String16, Thickness},
+    utils::{
+        Alignment, BoxShadow, Brush, FontStretch, FontStyle, FontWeight, Orientation, Stretch,
+        String16, TextAlignment, TextWrap, Thickness, Visibility,
+    },
 };
 
 use dces::prelude::{Component, Entity, EntityComponentManager};
 
+// Type of a widget property that is set by the css property of the same name.
+#[derive(Clone, Copy)]
+enum ThemeProperty {
+    Length,
+    Float,
+    Uint,
+    Brush,
+    Thickness,
+    String,
+    String16,
+    Alignment,
+    Orientation,
+    TextAlignment,
+    TextWrap,
+    Visibility,
+    FontWeight,
+    FontStyle,
+    FontStretch,
+    Stretch,
+    BoxShadow,
+}
+
+impl ThemeProperty {
+    // Gets the theme property of the given type of a widget property, `None` if properties of the
+    // type could not be set by a theme.
+    fn from_type(type_id: TypeId) -> Option<Self> {
+        let types = [
+            (TypeId::of::<f64>(), ThemeProperty::Length),
+            (TypeId::of::<f32>(), ThemeProperty::Float),
+            (TypeId::of::<usize>(), ThemeProperty::Uint),
+            (TypeId::of::<Brush>(), ThemeProperty::Brush),
+            (TypeId::of::<Thickness>(), ThemeProperty::Thickness),
+            (TypeId::of::<String>(), ThemeProperty::String),
+            (TypeId::of::<String16>(), ThemeProperty::String16),
+            (TypeId::of::<Alignment>(), ThemeProperty::Alignment),
+            (TypeId::of::<Orientation>(), ThemeProperty::Orientation),
+            (TypeId::of::<TextAlignment>(), ThemeProperty::TextAlignment),
+            (TypeId::of::<TextWrap>(), ThemeProperty::TextWrap),
+            (TypeId::of::<Visibility>(), ThemeProperty::Visibility),
+            (TypeId::of::<FontWeight>(), ThemeProperty::FontWeight),
+            (TypeId::of::<FontStyle>(), ThemeProperty::FontStyle),
+            (TypeId::of::<FontStretch>(), ThemeProperty::FontStretch),
+            (TypeId::of::<Stretch>(), ThemeProperty::Stretch),
+            (TypeId::of::<BoxShadow>(), ThemeProperty::BoxShadow),
+        ];
+
+        types
+            .iter()
+            .find(|(t, _)| *t == type_id)
+            .map(|(_, property)| *property)
+    }
+}
+
+// Css properties that are set by `update_properties_by_theme` with a different property name or
+// are combined with other css properties.
+static MAPPED_CSS_PROPERTIES: &[&str] = &[
+    "color",
+    "background",
+    "border-color",
+    "border-radius",
+    "border-width",
+    "opacity",
+    "padding",
+    "padding-left",
+    "padding-top",
+    "padding-right",
+    "padding-bottom",
+    "margin",
+    "margin-left",
+    "margin-top",
+    "margin-right",
+    "margin-bottom",
+    "width",
+    "height",
+    "min-width",
+    "min-height",
+    "max-width",
+    "max-height",
+    "spacing",
+    "font-size",
+    "font-family",
+    "icon-color",
+    "icon-size",
+    "icon-family",
+];
+
+// Widget properties that are not visual, a theme never changes them.
+static NON_THEME_PROPERTIES: &[&str] = &["text", "name", "id"];
+
 /// The `WidgetContainer` wraps the entity of a widget and provides access to its properties, its children properties and its parent properties.
 pub struct WidgetContainer<'a> {
     ecm: &'a mut EntityComponentManager<Tree, StringComponentStore>,
@@ -63,7 +158,8 @@ impl<'a> WidgetContainer<'a> {
     );
     }
 
-    /// Gets a mutable reference of the property of type `P`.
+    /// Gets a mutable reference of the property of type `P`. Changes through the reference are not
+    /// repainted or laid out, use `set` to change a property that is drawn or affects the layout.
     ///
     /// # Panics
     ///
@@ -158,6 +254,7 @@ impl<'a> WidgetContainer<'a> {
             .get_mut::<P>(key, self.current_node)
         {
             *property = value;
+            self.mark_changed(key);
             return;
         }
 
@@ -198,7 +295,8 @@ impl<'a> WidgetContainer<'a> {
     }
 
     /// Returns a mutable reference of a property of type `P` from the given widget entity. If the entity does
-    /// not exists or it doesn't have a component of type `P` `None` will be returned.
+    /// not exists or it doesn't have a component of type `P` `None` will be returned. Like for `get_mut`
+    /// changes through the reference are not repainted or laid out.
     pub fn try_get_mut<P: Component>(&mut self, key: &str) -> Option<&mut P> {
         self.ecm
             .component_store_mut()
@@ -215,16 +313,12 @@ impl<'a> WidgetContainer<'a> {
         false
     }
 
-    fn update_internal_theme_by_state(&mut self, force: bool, entity: &Entity) {
-        for child in &(self.ecm.entity_store().children.clone())[&entity] {
-            self.update_internal_theme_by_state(force, child);
-        }
-
+    fn update_internal_theme_by_state(&mut self, force: bool, animate: bool, entity: &Entity) {
         self.current_node = *entity;
 
-        if let Some(selector) = self.try_clone::<Selector>("selector") {
-            let mut update = false;
+        let mut update = false;
 
+        if let Some(selector) = self.try_clone::<Selector>("selector") {
             if let Some(focus) = self.try_clone::<bool>("focused") {
                 if focus && !selector.pseudo_classes.contains("focus") {
                     add_selector_to_widget("focus", self);
@@ -286,136 +380,479 @@ impl<'a> WidgetContainer<'a> {
             }
 
             if update || force {
-                self.update_properties_by_theme();
+                self.update_properties(animate);
             }
         }
+
+        // if the selector of the widget is changed, rules with relations could match different
+        // on its children
+        let force = force || (update && self.theme.has_selector_relations());
+
+        for child in &(self.ecm.entity_store().children.clone())[entity] {
+            self.update_internal_theme_by_state(force, animate, child);
+        }
+
+        self.current_node = *entity;
     }
 
-    /// Updates the theme by the inner state e.g. `selected` or `pressed`.
+    /// Updates the theme by the inner state e.g. `selected` or `pressed`. If the update is not
+    /// forced, property changes are animated by the css transitions of the widget.
     pub fn update_theme_by_state(&mut self, force: bool) {
-        self.update_internal_theme_by_state(force, &(self.current_node.clone()));
+        self.update_internal_theme_by_state(force, !force, &(self.current_node.clone()));
     }
 
     /// Update all properties for the theme.
     pub fn update_properties_by_theme(&mut self) {
+        self.update_properties(false);
+    }
+
+    fn update_properties(&mut self, animate: bool) {
         if !self.has::<Selector>("selector") {
             return;
         }
 
-        let selector = self.clone::<Selector>("selector");
+        let mut selector = self.clone::<Selector>("selector");
 
         if !selector.dirty() {
             return;
         }
 
+        if self.theme.has_selector_relations() {
+            selector = self.selector_with_ancestors(self.current_node);
+        }
+
         if self.has::<Brush>("foreground") {
             if let Some(color) = self.theme.brush("color", &selector) {
-                self.set::<Brush>("foreground", color);
+                self.set_by_theme(
+                    "foreground",
+                    "color",
+                    AnimationValue::Brush(color),
+                    &selector,
+                    animate,
+                );
             }
         }
 
         if self.has::<Brush>("background") {
             if let Some(background) = self.theme.brush("background", &selector) {
-                self.set::<Brush>("background", background);
+                self.set_by_theme(
+                    "background",
+                    "background",
+                    AnimationValue::Brush(background),
+                    &selector,
+                    animate,
+                );
             }
         }
 
         if self.has::<Brush>("border_brush") {
             if let Some(border_brush) = self.theme.brush("border-color", &selector) {
-                self.set::<Brush>("border_brush", border_brush);
+                self.set_by_theme(
+                    "border_brush",
+                    "border-color",
+                    AnimationValue::Brush(border_brush),
+                    &selector,
+                    animate,
+                );
             }
         }
 
         if self.has::<f64>("border_radius") {
-            if let Some(radius) = self.theme.uint("border-radius", &selector) {
-                self.set::<f64>("border_radius", f64::from(radius));
+            if let Some(radius) = self.theme.length("border-radius", &selector) {
+                self.set_by_theme(
+                    "border_radius",
+                    "border-radius",
+                    AnimationValue::Float(radius),
+                    &selector,
+                    animate,
+                );
             }
         }
 
         if self.has::<f32>("opacity") {
             if let Some(opacity) = self.theme.float("opacity", &selector) {
-                self.set::<f32>("opacity", opacity);
+                self.set_by_theme(
+                    "opacity",
+                    "opacity",
+                    AnimationValue::Float32(opacity),
+                    &selector,
+                    animate,
+                );
             }
         }
 
         if self.has::<Thickness>("border_width") {
-            if let Some(border_width) = self.theme.uint("border-width", &selector) {
-                self.set::<Thickness>("border_width", Thickness::from(border_width as f64));
+            if let Some(border_width) = self.theme.thickness("border-width", &selector) {
+                self.set_by_theme(
+                    "border_width",
+                    "border-width",
+                    AnimationValue::Thickness(border_width),
+                    &selector,
+                    animate,
+                );
             }
         }
 
         self.update_font_properties_by_theme(&selector);
 
         if let Some(mut padding) = self.try_clone::<Thickness>("padding") {
-            if let Some(pad) = self.theme.uint("padding", &selector) {
-                padding.set_thickness(pad as f64);
+            if let Some(pad) = self.theme.thickness("padding", &selector) {
+                padding = pad;
             }
 
-            if let Some(left) = self.theme.uint("padding-left", &selector) {
-                padding.set_left(left as f64);
+            if let Some(left) = self.theme.length("padding-left", &selector) {
+                padding.set_left(left);
             }
 
-            if let Some(top) = self.theme.uint("padding-top", &selector) {
-                padding.set_top(top as f64);
+            if let Some(top) = self.theme.length("padding-top", &selector) {
+                padding.set_top(top);
             }
 
-            if let Some(right) = self.theme.uint("padding-right", &selector) {
-                padding.set_right(right as f64);
+            if let Some(right) = self.theme.length("padding-right", &selector) {
+                padding.set_right(right);
             }
 
-            if let Some(bottom) = self.theme.uint("padding-bottom", &selector) {
-                padding.set_bottom(bottom as f64);
+            if let Some(bottom) = self.theme.length("padding-bottom", &selector) {
+                padding.set_bottom(bottom);
             }
-            self.set::<Thickness>("padding", padding);
+            self.set_by_theme(
+                "padding",
+                "padding",
+                AnimationValue::Thickness(padding),
+                &selector,
+                animate,
+            );
+        }
+
+        if let Some(mut margin) = self.try_clone::<Thickness>("margin") {
+            if let Some(m) = self.theme.thickness("margin", &selector) {
+                margin = m;
+            }
+
+            if let Some(left) = self.theme.length("margin-left", &selector) {
+                margin.set_left(left);
+            }
+
+            if let Some(top) = self.theme.length("margin-top", &selector) {
+                margin.set_top(top);
+            }
+
+            if let Some(right) = self.theme.length("margin-right", &selector) {
+                margin.set_right(right);
+            }
+
+            if let Some(bottom) = self.theme.length("margin-bottom", &selector) {
+                margin.set_bottom(bottom);
+            }
+            self.set::<Thickness>("margin", margin);
         }
 
         if let Some(mut constraint) = self.try_clone::<Constraint>("constraint") {
-            if let Some(width) = self.theme.uint("width", &selector) {
-                constraint.set_width(width as f64);
+            if let Some(width) = self.theme.length("width", &selector) {
+                constraint.set_width(width);
             }
 
-            if let Some(height) = self.theme.uint("height", &selector) {
-                constraint.set_height(height as f64);
+            if let Some(height) = self.theme.length("height", &selector) {
+                constraint.set_height(height);
             }
 
-            if let Some(min_width) = self.theme.uint("min-width", &selector) {
-                constraint.set_min_width(min_width as f64);
+            if let Some(min_width) = self.theme.length("min-width", &selector) {
+                constraint.set_min_width(min_width);
             }
 
-            if let Some(min_height) = self.theme.uint("min-height", &selector) {
-                constraint.set_min_height(min_height as f64);
+            if let Some(min_height) = self.theme.length("min-height", &selector) {
+                constraint.set_min_height(min_height);
             }
 
-            if let Some(max_width) = self.theme.uint("max-width", &selector) {
-                constraint.set_max_width(max_width as f64);
+            if let Some(max_width) = self.theme.length("max-width", &selector) {
+                constraint.set_max_width(max_width);
             }
 
-            if let Some(max_height) = self.theme.uint("max-height", &selector) {
-                constraint.set_max_height(max_height as f64);
+            if let Some(max_height) = self.theme.length("max-height", &selector) {
+                constraint.set_max_height(max_height);
             }
 
             self.set::<Constraint>("constraint", constraint);
         }
 
         if self.has::<f64>("spacing") {
-            if let Some(spacing) = self.theme.uint("spacing", &selector) {
-                self.set::<f64>("spacing", spacing.into());
+            if let Some(spacing) = self.theme.length("spacing", &selector) {
+                self.set::<f64>("spacing", spacing);
             }
         }
 
+        self.update_generic_properties_by_theme(&selector, animate);
+
         self.get_mut::<Selector>("selector").set_dirty(true);
     }
 
+    // Sets each property of the widget by the css property of the same name, e.g. `text-align`
+    // sets the `text_align` property, if the value of the css property could be read as the type
+    // of the property. It works also for the properties of widgets defined outside of this crate.
+    fn update_generic_properties_by_theme(&mut self, selector: &Selector, animate: bool) {
+        let theme = self.theme;
+
+        let property_types = match self.try_clone::<PropertyTypes>("property_types") {
+            Some(property_types) => property_types,
+            None => return,
+        };
+
+        for property in theme.properties(selector) {
+            if MAPPED_CSS_PROPERTIES.contains(&property.as_str()) {
+                continue;
+            }
+
+            let key = property.replace('-', "_");
+
+            if NON_THEME_PROPERTIES.contains(&key.as_str()) {
+                continue;
+            }
+
+            if let Some(property_type) = property_types.get(&key).and_then(ThemeProperty::from_type)
+            {
+                if let Some(value) = theme.get(&property, selector) {
+                    self.set_theme_value(&key, &property, property_type, &value, selector, animate);
+                }
+            }
+        }
+    }
+
+    // Sets the property of the given key if the widget has a property of the given type and the
+    // value could be read as this type.
+    fn set_theme_value(
+        &mut self,
+        key: &str,
+        property: &str,
+        property_type: ThemeProperty,
+        value: &Value,
+        selector: &Selector,
+        animate: bool,
+    ) {
+        match property_type {
+            ThemeProperty::Length => {
+                if self.has::<f64>(key) {
+                    if let Some(length) = value.length() {
+                        self.set_by_theme(
+                            key,
+                            property,
+                            AnimationValue::Float(length),
+                            selector,
+                            animate,
+                        );
+                    }
+                }
+            }
+            ThemeProperty::Float => {
+                if self.has::<f32>(key) {
+                    if let Some(float) = value.float() {
+                        self.set_by_theme(
+                            key,
+                            property,
+                            AnimationValue::Float32(float),
+                            selector,
+                            animate,
+                        );
+                    }
+                }
+            }
+            ThemeProperty::Uint => {
+                if self.has::<usize>(key) {
+                    if let Some(uint) = value.uint() {
+                        self.set::<usize>(key, uint as usize);
+                    }
+                }
+            }
+            ThemeProperty::Brush => {
+                if self.has::<Brush>(key) {
+                    if let Some(brush) = value.brush() {
+                        self.set_by_theme(
+                            key,
+                            property,
+                            AnimationValue::Brush(brush),
+                            selector,
+                            animate,
+                        );
+                    }
+                }
+            }
+            ThemeProperty::Thickness => {
+                if self.has::<Thickness>(key) {
+                    if let Some(thickness) = value.thickness() {
+                        self.set_by_theme(
+                            key,
+                            property,
+                            AnimationValue::Thickness(thickness),
+                            selector,
+                            animate,
+                        );
+                    }
+                }
+            }
+            ThemeProperty::String => {
+                if self.has::<String>(key) {
+                    if let Some(string) = value.string() {
+                        self.set::<String>(key, string);
+                    }
+                }
+            }
+            ThemeProperty::String16 => {
+                if self.has::<String16>(key) {
+                    if let Some(string) = value.string() {
+                        self.set::<String16>(key, String16::from(string));
+                    }
+                }
+            }
+            ThemeProperty::Alignment => {
+                if self.has::<Alignment>(key) {
+                    if let Some(keyword) = value.keyword() {
+                        self.set::<Alignment>(key, Alignment::from(keyword.as_str()));
+                    }
+                }
+            }
+            ThemeProperty::Orientation => {
+                if self.has::<Orientation>(key) {
+                    if let Some(keyword) = value.keyword() {
+                        self.set::<Orientation>(key, Orientation::from(keyword.as_str()));
+                    }
+                }
+            }
+            ThemeProperty::TextAlignment => {
+                if self.has::<TextAlignment>(key) {
+                    if let Some(keyword) = value.keyword() {
+                        self.set::<TextAlignment>(key, TextAlignment::from(keyword.as_str()));
+                    }
+                }
+            }
+            ThemeProperty::TextWrap => {
+                if self.has::<TextWrap>(key) {
+                    if let Some(keyword) = value.keyword() {
+                        self.set::<TextWrap>(key, TextWrap::from(keyword.as_str()));
+                    }
+                }
+            }
+            ThemeProperty::Visibility => {
+                if self.has::<Visibility>(key) {
+                    if let Some(keyword) = value.keyword() {
+                        self.set::<Visibility>(key, Visibility::from(keyword.as_str()));
+                    }
+                }
+            }
+            ThemeProperty::FontWeight => {
+                if !self.has::<FontWeight>(key) {
+                    return;
+                }
+
+                if let Some(keyword) = value.keyword() {
+                    self.set::<FontWeight>(key, FontWeight::from(keyword.as_str()));
+                } else if let Some(weight) = value.float() {
+                    self.set::<FontWeight>(key, FontWeight::from(weight as u16));
+                }
+            }
+            ThemeProperty::FontStyle => {
+                if self.has::<FontStyle>(key) {
+                    if let Some(keyword) = value.keyword() {
+                        self.set::<FontStyle>(key, FontStyle::from(keyword.as_str()));
+                    }
+                }
+            }
+            ThemeProperty::FontStretch => {
+                if self.has::<FontStretch>(key) {
+                    if let Some(keyword) = value.keyword() {
+                        self.set::<FontStretch>(key, FontStretch::from(keyword.as_str()));
+                    }
+                }
+            }
+            ThemeProperty::Stretch => {
+                if self.has::<Stretch>(key) {
+                    if let Some(keyword) = value.keyword() {
+                        self.set::<Stretch>(key, Stretch::from(keyword.as_str()));
+                    }
+                }
+            }
+            ThemeProperty::BoxShadow => {
+                if self.has::<BoxShadow>(key) {
+                    if let Some(box_shadow) = value.box_shadow() {
+                        self.set::<BoxShadow>(key, box_shadow);
+                    }
+                }
+            }
+        }
+    }
+
+    // Marks the widget to be repainted and, if the property affects the layout, to be laid out
+    // again.
+    fn mark_changed(&mut self, key: &str) {
+        mark_dirty(self.ecm, self.current_node, key);
+        invalidate_property_layout(self.ecm, self.current_node, key);
+    }
+
+    // Sets the property with the given key to a value of the theme. If `animate` is set and the
+    // theme declares a transition for the css property, the property is animated from its current
+    // value.
+    fn set_by_theme(
+        &mut self,
+        key: &str,
+        property: &str,
+        value: AnimationValue,
+        selector: &Selector,
+        animate: bool,
+    ) {
+        let root = self.ecm.entity_store().root();
+        let transition = if animate {
+            self.theme
+                .transition(property, selector)
+                .filter(|t| t.duration > 0.0)
+        } else {
+            None
+        };
+        let from = animation_value(self.ecm, self.current_node, key, &value);
+
+        if let Ok(driver) = self
+            .ecm
+            .component_store_mut()
+            .get_mut::<AnimationDriver>("animations", root)
+        {
+            match (transition, from) {
+                (Some(transition), Some(from)) if from != value => {
+                    driver.start(self.current_node, key, from, value, transition);
+                    return;
+                }
+                // a running animation would overwrite the value
+                _ => driver.stop(self.current_node, key),
+            }
+        }
+
+        set_animation_value(self.ecm, self.current_node, key, value);
+    }
+
+    // Gets the selector of the given widget with the selectors of its ancestors as parent
+    // relations. It is used to match rules with descendant and child combinators.
+    fn selector_with_ancestors(&self, entity: Entity) -> Selector {
+        let (entity_store, component_store) = self.ecm.stores();
+        let mut selector = component_store
+            .get::<Selector>("selector", entity)
+            .cloned()
+            .unwrap_or_default();
+
+        if let Some(Some(parent)) = entity_store.parent.get(&entity) {
+            selector.relation = Some(Box::new(SelectorRelation::Parent(
+                self.selector_with_ancestors(*parent),
+            )));
+        }
+
+        selector
+    }
+
     pub fn update_font_properties_by_theme(&mut self, selector: &Selector) {
         if self.has::<f64>("font_size") {
-            if let Some(size) = self.theme.uint("font-size", selector) {
-                self.set::<f64>("font_size", f64::from(size));
+            if let Some(size) = self.theme.length("font-size", selector) {
+                self.set::<f64>("font_size", size);
             }
         }
 
-        if self.has::<String>("font_family") {
+        if self.has::<String>("font") {
             if let Some(font_family) = self.theme.string("font-family", selector) {
-                self.set::<String>("font_family", font_family);
+                self.set::<String>("font", font_family);
             }
         }
 
@@ -426,8 +863,8 @@ impl<'a> WidgetContainer<'a> {
         }
 
         if self.has::<f64>("icon_size") {
-            if let Some(size) = self.theme.uint("icon-size", selector) {
-                self.set::<f64>("icon_size", f64::from(size));
+            if let Some(size) = self.theme.length("icon-size", selector) {
+                self.set::<f64>("icon_size", size);
             }
         }
 
@@ -22,7 +22,9 @@ let background = theme.brush("background", &selector);
 
 pub use selector::*;
 pub use theme::*;
+pub use transition::*;
 
 pub mod prelude;
 mod selector;
 mod theme;
+mod transition;
@@ -85,7 +85,39 @@ impl Selector {
         s
     }
 
+    /// Checks if the selector matches the `other` selector.
+    ///
+    /// The relation of `other` is used as the ancestry of the queried widget: each
+    /// `SelectorRelation::Parent` (or `Ancestor`) holds the selector of the parent widget.
+    /// Relations of `self` are only matched if `other` provides its ancestry.
     pub fn matches(&self, other: &Selector) -> bool {
+        if !self.matches_compound(other) {
+            return false;
+        }
+
+        match self.relation.as_deref() {
+            Some(SelectorRelation::Parent(parent)) => {
+                other.parent().map_or(false, |p| parent.matches(p))
+            }
+            Some(SelectorRelation::Ancestor(ancestor)) => {
+                let mut current = other.parent();
+
+                while let Some(selector) = current {
+                    if ancestor.matches(selector) {
+                        return true;
+                    }
+
+                    current = selector.parent();
+                }
+
+                false
+            }
+            None => true,
+        }
+    }
+
+    // Matches id, element, classes and pseudo classes without the relation.
+    fn matches_compound(&self, other: &Selector) -> bool {
         if self.id.is_some() && self.id != other.id {
             return false;
         }
@@ -105,6 +137,16 @@ impl Selector {
         true
     }
 
+    /// Gets the selector of the parent element if the selector contains the ancestry of a widget.
+    pub fn parent(&self) -> Option<&Selector> {
+        match self.relation.as_deref() {
+            Some(SelectorRelation::Parent(parent)) | Some(SelectorRelation::Ancestor(parent)) => {
+                Some(parent)
+            }
+            None => None,
+        }
+    }
+
     pub fn with<S: Into<String>>(mut self, element: S) -> Self {
         self.element = Some(element.into());
         self
@@ -189,3 +231,53 @@ impl From<&Selector> for Selector {
         selector
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn child_of(selector: Selector, parent: Selector) -> Selector {
+        let mut selector = selector;
+        selector.relation = Some(Box::new(SelectorRelation::Parent(parent)));
+        selector
+    }
+
+    #[test]
+    fn test_matches_parent() {
+        let rule = child_of(
+            Selector::from("list-view-item").pseudo_class("selected"),
+            Selector::from("list-view"),
+        );
+
+        let query = child_of(
+            Selector::from("list-view-item").pseudo_class("selected"),
+            Selector::from("list-view"),
+        );
+        assert!(rule.matches(&query));
+
+        let query = child_of(
+            Selector::from("list-view-item").pseudo_class("selected"),
+            Selector::from("stack"),
+        );
+        assert!(!rule.matches(&query));
+
+        assert!(!rule.matches(&Selector::from("list-view-item").pseudo_class("selected")));
+    }
+
+    #[test]
+    fn test_matches_ancestor() {
+        let mut rule = Selector::from("button");
+        rule.relation = Some(Box::new(SelectorRelation::Ancestor(
+            Selector::new().class("toolbar"),
+        )));
+
+        let query = child_of(
+            Selector::from("button"),
+            child_of(Selector::from("stack"), Selector::from("container").class("toolbar")),
+        );
+        assert!(rule.matches(&query));
+
+        let query = child_of(Selector::from("button"), Selector::from("stack"));
+        assert!(!rule.matches(&query));
+    }
+}
@@ -1,17 +1,21 @@
 //! This module contains all css theming related resources.
 
-use std::{fs::File, io::BufReader, io::Read, mem, path::Path, sync::Arc};
+use std::{fmt, fs::File, io::BufReader, io::Read, mem, ops::Range, path::Path, sync::Arc};
 
 use cssparser::{
     self, BasicParseError, CompactCowStr, DeclarationListParser, ParseError, Parser, ParserInput,
-    Token,
+    SourcePosition, Token,
 };
 
 use orbtk_utils::prelude::*;
 
 use crate::prelude::*;
 
+// Maximum depth of nested `var()` references, protects against cyclic references.
+const MAX_VAR_DEPTH: usize = 16;
+
 /// Used to build a theme, specifying additional details.
+#[derive(Clone, Debug)]
 pub struct ThemeBuilder {
     theme_css: Option<String>,
     theme_path: Option<String>,
@@ -43,47 +47,96 @@ impl ThemeBuilder {
         self
     }
 
-    /// Builds the theme.
+    /// Gets the paths of all css files the theme is read from.
+    pub fn paths(&self) -> Vec<String> {
+        self.theme_path
+            .iter()
+            .chain(self.theme_extension_paths.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// Builds the theme. Invalid rules and declarations are skipped.
     pub fn build(self) -> Theme {
-        let mut theme = String::new();
+        Theme::from_rules(
+            self.sources()
+                .iter()
+                .flat_map(|(_, css)| parse(css))
+                .collect(),
+        )
+    }
 
-        for css_extension in self.theme_extensions.iter().rev() {
-            theme.push_str(&css_extension);
+    /// Builds the theme and returns all parse errors if the css contains invalid rules or
+    /// declarations. The errors of a css file hold its path as origin.
+    pub fn try_build(self) -> Result<Theme, Vec<ThemeParseError>> {
+        let mut rules = vec![];
+        let mut errors = vec![];
+
+        // each source is parsed on its own, so the lines of the errors start in its first line
+        for (origin, css) in self.sources() {
+            let (mut source_rules, source_errors) = parse_rules(&css);
+            rules.append(&mut source_rules);
+
+            errors.extend(source_errors.into_iter().map(|mut error| {
+                error.origin = origin.clone();
+                error
+            }));
         }
 
-        for extension_path in self.theme_extension_paths.iter().rev() {
-            if let Ok(file) = File::open(extension_path) {
-                let mut reader = BufReader::new(file);
-                let mut css = String::new();
-                let _ = reader.read_to_string(&mut css).unwrap();
+        if errors.is_empty() {
+            Ok(Theme::from_rules(rules))
+        } else {
+            Err(errors)
+        }
+    }
+
+    // Reads the css of all extensions and the base theme in the order of their rules. The css of
+    // a file is returned with its path.
+    fn sources(self) -> Vec<(Option<String>, String)> {
+        let mut sources = vec![];
 
-                theme.push_str(&css);
+        for css_extension in self.theme_extensions.into_iter().rev() {
+            sources.push((None, css_extension));
+        }
+
+        for extension_path in self.theme_extension_paths.into_iter().rev() {
+            if let Some(css) = read_css(&extension_path) {
+                sources.push((Some(extension_path), css));
             }
         }
 
         if let Some(css) = self.theme_css {
-            theme.push_str(&css);
+            sources.push((None, css));
         };
 
         if let Some(path) = self.theme_path {
-            if let Ok(file) = File::open(path) {
-                let mut reader = BufReader::new(file);
-                let mut css = String::new();
-                let _ = reader.read_to_string(&mut css).unwrap();
-
-                theme.push_str(&css);
+            if let Some(css) = read_css(&path) {
+                sources.push((Some(path), css));
             }
         };
 
-        Theme::parse(&theme)
+        sources
     }
 }
 
+// Reads the css file with the given path. Returns `None` if the file could not be opened.
+fn read_css(path: &str) -> Option<String> {
+    let file = File::open(path).ok()?;
+    let mut reader = BufReader::new(file);
+    let mut css = String::new();
+    let _ = reader.read_to_string(&mut css).unwrap();
+
+    Some(css)
+}
+
 /// `Theme` is the representation of a css styling.
 #[derive(Clone, PartialEq, Default, Debug)]
 pub struct Theme {
     parent: Option<Arc<Theme>>,
     rules: Vec<Rule>,
+
+    // if one of the rules uses a descendant or child combinator
+    selector_relations: bool,
 }
 
 impl Theme {
@@ -112,9 +165,18 @@ impl Theme {
     }
 
     fn parse(s: &str) -> Self {
+        Theme::from_rules(parse(s))
+    }
+
+    fn from_rules(rules: Vec<Rule>) -> Self {
+        let selector_relations = rules
+            .iter()
+            .any(|rule| rule.selectors.iter().any(|s| s.relation.is_some()));
+
         Theme {
             parent: None,
-            rules: parse(s),
+            rules,
+            selector_relations,
         }
     }
 
@@ -143,7 +205,15 @@ impl Theme {
         }
     }
 
+    /// Gets the value of the given property for the given selector. References to custom properties
+    /// (`var(--name, fallback)`) are resolved.
     pub fn get(&self, property: &str, query: &Selector) -> Option<Value> {
+        self.declared_value(property, query)
+            .and_then(|value| self.resolve_value(value, query, 0))
+    }
+
+    // Gets the declared value of the property with the highest priority.
+    fn declared_value(&self, property: &str, query: &Selector) -> Option<Value> {
         let mut matches: Vec<(bool, Specificity, Value)> = Vec::new();
 
         for rule in self.all_rules().iter().rev() {
@@ -173,6 +243,50 @@ impl Theme {
         matches.last().map(|x| x.2.clone())
     }
 
+    // Resolves `var()` references. If the custom property is not declared the fallback is used.
+    fn resolve_value(&self, value: Value, query: &Selector, depth: usize) -> Option<Value> {
+        match value {
+            Value::Var(name, fallback) => {
+                let resolved = if depth < MAX_VAR_DEPTH {
+                    self.custom_property(&name, query)
+                        .and_then(|value| self.resolve_value(value, query, depth + 1))
+                } else {
+                    None
+                };
+
+                resolved.or_else(|| {
+                    fallback.and_then(|fallback| self.resolve_value(*fallback, query, depth + 1))
+                })
+            }
+            value => Some(value),
+        }
+    }
+
+    // Custom properties are inherited, if the widget does not declare it, its ancestors are asked.
+    fn custom_property(&self, name: &str, query: &Selector) -> Option<Value> {
+        let mut current = Some(query);
+
+        while let Some(selector) = current {
+            if let Some(value) = self.declared_value(name, selector) {
+                return Some(value);
+            }
+
+            current = selector.parent();
+        }
+
+        None
+    }
+
+    /// Returns `true` if one of the rules uses a descendant or child combinator. In this case the
+    /// properties of a widget depend on its ancestors.
+    pub fn has_selector_relations(&self) -> bool {
+        self.selector_relations
+            || self
+                .parent
+                .as_ref()
+                .map_or(false, |parent| parent.selector_relations)
+    }
+
     pub fn brush(&self, property: &str, query: &Selector) -> Option<Brush> {
         self.get(property, query).and_then(|v| v.brush())
     }
@@ -188,6 +302,56 @@ impl Theme {
     pub fn string(&self, property: &str, query: &Selector) -> Option<String> {
         self.get(property, query).and_then(|v| v.string())
     }
+
+    pub fn length(&self, property: &str, query: &Selector) -> Option<f64> {
+        self.get(property, query).and_then(|v| v.length())
+    }
+
+    pub fn thickness(&self, property: &str, query: &Selector) -> Option<Thickness> {
+        self.get(property, query).and_then(|v| v.thickness())
+    }
+
+    pub fn percent(&self, property: &str, query: &Selector) -> Option<f32> {
+        self.get(property, query).and_then(|v| v.percent())
+    }
+
+    pub fn keyword(&self, property: &str, query: &Selector) -> Option<String> {
+        self.get(property, query).and_then(|v| v.keyword())
+    }
+
+    pub fn box_shadow(&self, property: &str, query: &Selector) -> Option<BoxShadow> {
+        self.get(property, query).and_then(|v| v.box_shadow())
+    }
+
+    /// Gets the transition of the given css property for the given selector. If more than one
+    /// transition matches the property, the last one is used.
+    pub fn transition(&self, property: &str, query: &Selector) -> Option<Transition> {
+        self.get("transition", query)
+            .and_then(|v| v.transitions())
+            .and_then(|transitions| transitions.into_iter().rev().find(|t| t.matches(property)))
+    }
+
+    /// Gets the names of all properties that are declared by rules matching the given selector.
+    /// Custom properties are skipped.
+    pub fn properties(&self, query: &Selector) -> Vec<String> {
+        let mut properties: Vec<String> = vec![];
+
+        for rule in self.all_rules() {
+            if !rule.selectors.iter().any(|s| s.matches(query)) {
+                continue;
+            }
+
+            for declaration in rule.declarations {
+                if !declaration.property.starts_with("--")
+                    && !properties.contains(&declaration.property)
+                {
+                    properties.push(declaration.property);
+                }
+            }
+        }
+
+        properties
+    }
 }
 
 #[derive(Clone, Default, PartialEq, Debug)]
@@ -209,6 +373,20 @@ pub enum Value {
     Float(f32),
     Brush(Brush),
     Str(String),
+    /// Pixel length, e.g. `12.5px`.
+    Length(f64),
+    /// Thickness shorthand with two to four values, e.g. `padding: 4 8`.
+    Thickness(Thickness),
+    /// Percentage from 0 to 100, e.g. `50%`.
+    Percent(f32),
+    /// Keyword that is not a color name, e.g. `text-align: center`.
+    Keyword(String),
+    /// List of transitions, e.g. `transition: background 150ms ease-out, opacity 1s`.
+    Transitions(Vec<Transition>),
+    /// Shadow of a widget, e.g. `box-shadow: 0 2px 4px rgba(0, 0, 0, 0.3)`.
+    BoxShadow(BoxShadow),
+    /// Reference to a custom property with an optional fallback value, e.g. `var(--accent, #efd035)`.
+    Var(String, Option<Box<Value>>),
 }
 
 impl Default for Value {
@@ -225,9 +403,44 @@ impl Value {
         }
     }
 
+    /// Gets the value as float. Percentages are returned as fraction, e.g. `50%` as `0.5`.
     pub fn float(&self) -> Option<f32> {
         match *self {
             Value::Float(x) => Some(x),
+            Value::UInt(x) => Some(x as f32),
+            Value::Percent(x) => Some(x / 100.0),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as pixel length. Numbers without unit are handled as pixels.
+    pub fn length(&self) -> Option<f64> {
+        match *self {
+            Value::Length(x) => Some(x),
+            Value::UInt(x) => Some(x as f64),
+            Value::Float(x) => Some(x as f64),
+            _ => None,
+        }
+    }
+
+    /// Gets the value as thickness. A single length is used for all sides.
+    pub fn thickness(&self) -> Option<Thickness> {
+        match *self {
+            Value::Thickness(x) => Some(x),
+            _ => self.length().map(Thickness::from),
+        }
+    }
+
+    pub fn percent(&self) -> Option<f32> {
+        match *self {
+            Value::Percent(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn keyword(&self) -> Option<String> {
+        match self {
+            Value::Keyword(x) => Some(x.clone()),
             _ => None,
         }
     }
@@ -241,17 +454,106 @@ impl Value {
 
     pub fn string(&self) -> Option<String> {
         match self {
-            Value::Str(x) => Some(x.clone()),
+            Value::Str(x) | Value::Keyword(x) => Some(x.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn transitions(&self) -> Option<Vec<Transition>> {
+        match self {
+            Value::Transitions(x) => Some(x.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn box_shadow(&self) -> Option<BoxShadow> {
+        match *self {
+            Value::BoxShadow(x) => Some(x),
             _ => None,
         }
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub enum CustomParseError {
     InvalidColorName(String),
     InvalidColorHex(String),
     InvalidStringName(String),
+    InvalidVariableName(String),
+    InvalidGradient(String),
+    InvalidTransition(String),
+    InvalidBoxShadow(String),
+    /// Syntax error reported by the css parser, e.g. an unexpected token.
+    InvalidSyntax(String),
+}
+
+impl<'i> From<ParseError<'i, CustomParseError>> for CustomParseError {
+    fn from(e: ParseError<'i, CustomParseError>) -> Self {
+        match e {
+            ParseError::Basic(e) => CustomParseError::InvalidSyntax(format!("{:?}", e)),
+            ParseError::Custom(e) => e,
+        }
+    }
+}
+
+/// Describes an invalid rule or declaration of a css theme.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ThemeParseError {
+    /// Path of the css file that contains the error, `None` if the css is not read from a file.
+    pub origin: Option<String>,
+
+    /// Line of the invalid css, starting at 1.
+    pub line: usize,
+
+    /// Column of the invalid css, starting at 1.
+    pub column: usize,
+
+    /// The invalid rule or declaration.
+    pub snippet: String,
+
+    /// Describes what is wrong.
+    pub kind: CustomParseError,
+}
+
+impl ThemeParseError {
+    // Creates the error for the given span. `start` is the position of the first character of
+    // the css.
+    fn new<'i>(
+        input: &Parser<'i, '_>,
+        start: SourcePosition,
+        span: Range<SourcePosition>,
+        error: ParseError<'i, CustomParseError>,
+    ) -> Self {
+        let prefix = input.slice(start..span.start);
+        let line = prefix.matches('\n').count() + 1;
+        let column = prefix
+            .rsplit('\n')
+            .next()
+            .map_or(0, |last_line| last_line.chars().count())
+            + 1;
+
+        ThemeParseError {
+            origin: None,
+            line,
+            column,
+            snippet: input.slice(span).trim().to_string(),
+            kind: CustomParseError::from(error),
+        }
+    }
+}
+
+impl fmt::Display for ThemeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(origin) = &self.origin {
+            write!(f, "{}:", origin)?;
+        }
+
+        write!(
+            f,
+            "{}:{}: {:?} in `{}`",
+            self.line, self.column, self.kind, self.snippet
+        )
+    }
 }
 
 impl<'t> From<CustomParseError> for ParseError<'t, CustomParseError> {
@@ -260,15 +562,18 @@ impl<'t> From<CustomParseError> for ParseError<'t, CustomParseError> {
     }
 }
 
-struct RuleParser;
+struct RuleParser<'a> {
+    start: SourcePosition,
+    errors: &'a mut Vec<ThemeParseError>,
+}
 
-impl RuleParser {
-    fn new() -> Self {
-        RuleParser {}
+impl<'a> RuleParser<'a> {
+    fn new(start: SourcePosition, errors: &'a mut Vec<ThemeParseError>) -> Self {
+        RuleParser { start, errors }
     }
 }
 
-impl<'i> cssparser::QualifiedRuleParser<'i> for RuleParser {
+impl<'i, 'a> cssparser::QualifiedRuleParser<'i> for RuleParser<'a> {
     type Prelude = Vec<Selector>;
     type QualifiedRule = Rule;
     type Error = CustomParseError;
@@ -290,29 +595,25 @@ impl<'i> cssparser::QualifiedRuleParser<'i> for RuleParser {
 
         let decls = DeclarationListParser::new(input, decl_parser).collect::<Vec<_>>();
 
-        for decl in &decls {
-            match *decl {
-                Ok(_) => {}
-                Err(ref e) => {
-                    match e.error {
-                        ParseError::Basic(ref e) => eprintln!("{:?}", e),
-                        ParseError::Custom(ref e) => eprintln!("{:?}", e),
-                    }
-                    println!("Error occurred in `{}`", input.slice(e.span.clone()));
-                }
+        let mut declarations = vec![];
+
+        for decl in decls {
+            match decl {
+                Ok(decl) => declarations.push(decl),
+                Err(e) => self
+                    .errors
+                    .push(ThemeParseError::new(input, self.start, e.span, e.error)),
             }
         }
 
-        let decls = decls.into_iter().filter_map(|decl| decl.ok()).collect();
-
         Ok(Rule {
             selectors,
-            declarations: decls,
+            declarations,
         })
     }
 }
 
-impl<'i> cssparser::AtRuleParser<'i> for RuleParser {
+impl<'i, 'a> cssparser::AtRuleParser<'i> for RuleParser<'a> {
     type Prelude = ();
     type AtRule = Rule;
     type Error = CustomParseError;
@@ -325,24 +626,51 @@ fn parse_selectors<'i, 't>(
 
     let mut selector = Selector::default();
 
+    // relation to the previous compound selector, e.g. `>` or whitespace
+    let mut relation: Option<fn(Selector) -> SelectorRelation> = None;
+
     let mut first_token_in_selector = true;
-    while let Ok(t) = input.next() {
+    while let Ok(t) = input.next_including_whitespace() {
         match t {
-            // Element
-            Token::Ident(ref element_name) => {
-                if first_token_in_selector {
-                    selector.element = Some(element_name.to_string())
-                } else {
-                    let mut old_selector = Selector::new().with(element_name.to_string());
-                    mem::swap(&mut old_selector, &mut selector);
-                    selector.relation = Some(Box::new(SelectorRelation::Ancestor(old_selector)));
+            // Descendant combinator
+            Token::WhiteSpace(_) => {
+                if !first_token_in_selector && relation.is_none() {
+                    relation = Some(SelectorRelation::Ancestor);
                 }
+                continue;
             }
 
+            // Child combinator
             Token::Delim('>') => {
-                let mut old_selector = Selector::new().with(input.expect_ident()?.to_string());
-                mem::swap(&mut old_selector, &mut selector);
-                selector.relation = Some(Box::new(SelectorRelation::Parent(old_selector)));
+                if first_token_in_selector {
+                    return Err(BasicParseError::UnexpectedToken(t).into());
+                }
+                relation = Some(SelectorRelation::Parent);
+                continue;
+            }
+
+            // This selector is done, on to the next one
+            Token::Comma => {
+                selectors.push(selector);
+                selector = Selector::default();
+                relation = None;
+                first_token_in_selector = true;
+                continue; // need to continue to avoid `first_token_in_selector` being set to false
+            }
+
+            _ => {}
+        }
+
+        // a new compound selector starts, the previous one becomes its relation
+        if let Some(relation) = relation.take() {
+            let old_selector = mem::replace(&mut selector, Selector::default());
+            selector.relation = Some(Box::new(relation(old_selector)));
+        }
+
+        match t {
+            // Element
+            Token::Ident(ref element_name) => {
+                selector.element = Some(element_name.to_string());
             }
 
             // Id
@@ -365,14 +693,6 @@ fn parse_selectors<'i, 't>(
                     .insert(input.expect_ident()?.into_owned());
             }
 
-            // This selector is done, on to the next one
-            Token::Comma => {
-                selectors.push(selector);
-                selector = Selector::default();
-                first_token_in_selector = true;
-                continue; // need to continue to avoid `first_token_in_selector` being set to false
-            }
-
             t => {
                 let basic_error = BasicParseError::UnexpectedToken(t);
                 return Err(basic_error.into());
@@ -384,10 +704,6 @@ fn parse_selectors<'i, 't>(
 
     selectors.push(selector);
 
-    if selectors.iter().any(|sel| sel.relation.is_some()) {
-        eprintln!("WARNING: Complex selector relations not implemented");
-    }
-
     Ok(selectors)
 }
 
@@ -402,31 +718,10 @@ impl<'i> cssparser::DeclarationParser<'i> for DeclarationParser {
         name: CompactCowStr<'i>,
         input: &mut Parser<'i, 't>,
     ) -> Result<Self::Declaration, ParseError<'i, Self::Error>> {
-        let value = match &*name {
-            "color" | "border-color" | "icon-color" => Value::Brush(parse_basic_color(input)?),
-
-            "background" | "foreground" => Value::Brush(parse_basic_color(input)?),
-
-            "font-family" | "icon-family" => Value::Str(parse_string(input)?),
-
-            "border-radius" | "border-width" | "font-size" | "icon-size" | "icon-margin"
-            | "padding" | "padding-left" | "padding-top" | "padding-right" | "padding-bottom"
-            | "width" | "height" | "min-width" | "min-height" | "max-width" | "max-height"
-            | "spacing" => match input.next()? {
-                Token::Number {
-                    int_value: Some(x),
-                    has_sign,
-                    ..
-                } if !has_sign && x >= 0 => Value::UInt(x as u32),
-                t => return Err(BasicParseError::UnexpectedToken(t).into()),
-            },
-
-            "opacity" => match input.next()? {
-                Token::Number { value: x, .. } => Value::Float(x as f32),
-                t => return Err(BasicParseError::UnexpectedToken(t).into()),
-            },
-
-            _ => return Err(BasicParseError::UnexpectedToken(input.next()?).into()),
+        let value = if let Ok(value) = input.r#try(|input| parse_var(&name, input)) {
+            value
+        } else {
+            parse_property_value(&name, input)?
         };
 
         Ok(Declaration {
@@ -443,26 +738,265 @@ impl<'i> cssparser::AtRuleParser<'i> for DeclarationParser {
     type Error = CustomParseError;
 }
 
-fn css_color(name: &str) -> Option<Brush> {
-    Some(match name {
-        "transparent" => Brush::from(name),
-
-        "black" => Brush::from("#000000"),
-        "silver" => Brush::from("#C0C0C0"),
-        "gray" | "grey" => Brush::from("#808080"),
-        "white" => Brush::from("#FFFFFF"),
-        "maroon" => Brush::from("#800000"),
-        "red" => Brush::from("#FF0000"),
-        "purple" => Brush::from("#800080"),
-        "fuchsia" => Brush::from("#FF00FF"),
-        "green" => Brush::from("#008000"),
-        "lime" => Brush::from("#00FF00"),
-        "olive" => Brush::from("#808000"),
-        "yellow" => Brush::from("#FFFF00"),
-        "navy" => Brush::from("#000080"),
-        "blue" => Brush::from("#0000FF"),
-        "teal" => Brush::from("#008080"),
-        "aqua" => Brush::from("#00FFFF"),
+fn parse_property_value<'i, 't>(
+    name: &str,
+    input: &mut Parser<'i, 't>,
+) -> Result<Value, ParseError<'i, CustomParseError>> {
+    Ok(match name {
+        "color" | "border-color" | "icon-color" => Value::Brush(parse_basic_color(input)?),
+
+        "background" | "foreground" => Value::Brush(parse_basic_color(input)?),
+
+        "font-family" | "icon-family" => Value::Str(parse_string(input)?),
+
+        "transition" => Value::Transitions(parse_transitions(input)?),
+
+        "box-shadow" => Value::BoxShadow(parse_box_shadow(input)?),
+
+        _ => parse_generic_value(input)?,
+    })
+}
+
+// Parses the value of a property without a fixed type, e.g. a custom property (`--name: value`).
+// The type of the value is taken from its syntax.
+fn parse_generic_value<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Value, ParseError<'i, CustomParseError>> {
+    if let Ok(value) = input.r#try(parse_number) {
+        let mut lengths = vec![value];
+
+        while lengths.len() < 4 {
+            match input.r#try(parse_number) {
+                Ok(length) => lengths.push(length),
+                Err(_) => break,
+            }
+        }
+
+        if lengths.len() == 1 {
+            return Ok(lengths.remove(0));
+        }
+
+        let l: Vec<f64> = lengths.iter().filter_map(|l| l.length()).collect();
+
+        // same order as css: top, right, bottom, left
+        return Ok(Value::Thickness(match l.len() {
+            2 => Thickness::new(l[1], l[0], l[1], l[0]),
+            3 => Thickness::new(l[1], l[0], l[1], l[2]),
+            _ => Thickness::new(l[3], l[0], l[1], l[2]),
+        }));
+    }
+
+    if let Ok(brush) = input.r#try(parse_basic_color) {
+        return Ok(Value::Brush(brush));
+    }
+
+    Ok(match input.next()? {
+        Token::Percentage { unit_value, .. } => Value::Percent(unit_value * 100.0),
+
+        Token::QuotedString(s) => Value::Str(s.into_owned()),
+
+        Token::Ident(s) => Value::Keyword(s.into_owned()),
+
+        t => return Err(BasicParseError::UnexpectedToken(t).into()),
+    })
+}
+
+// Parses a number or a pixel length.
+fn parse_number<'i, 't>(input: &mut Parser<'i, 't>) -> Result<Value, BasicParseError<'i>> {
+    match input.next()? {
+        Token::Number {
+            int_value: Some(x),
+            has_sign,
+            ..
+        } if !has_sign && x >= 0 => Ok(Value::UInt(x as u32)),
+
+        Token::Number { value: x, .. } => Ok(Value::Float(x)),
+
+        Token::Dimension {
+            value: x, ref unit, ..
+        } if unit.eq_ignore_ascii_case("px") => Ok(Value::Length(x as f64)),
+
+        t => Err(BasicParseError::UnexpectedToken(t)),
+    }
+}
+
+// Parses `var(--name)` or `var(--name, fallback)`. The fallback is parsed as value of `property`.
+fn parse_var<'i, 't>(
+    property: &str,
+    input: &mut Parser<'i, 't>,
+) -> Result<Value, ParseError<'i, CustomParseError>> {
+    input.expect_function_matching("var")?;
+
+    input.parse_nested_block(|input| {
+        let name = input.expect_ident()?.into_owned();
+
+        if !name.starts_with("--") {
+            return Err(CustomParseError::InvalidVariableName(name).into());
+        }
+
+        let fallback = if input.r#try(|input| input.expect_comma()).is_ok() {
+            Some(Box::new(parse_property_value(property, input)?))
+        } else {
+            None
+        };
+
+        Ok(Value::Var(name, fallback))
+    })
+}
+
+// Gets the named css colors.
+fn css_color(name: &str) -> Option<Color> {
+    Some(match name.to_lowercase().as_str() {
+        "transparent" => Color::rgba(0, 0, 0, 0),
+
+        "aliceblue" => Color::from("#F0F8FF"),
+        "antiquewhite" => Color::from("#FAEBD7"),
+        "aqua" => Color::from("#00FFFF"),
+        "aquamarine" => Color::from("#7FFFD4"),
+        "azure" => Color::from("#F0FFFF"),
+        "beige" => Color::from("#F5F5DC"),
+        "bisque" => Color::from("#FFE4C4"),
+        "black" => Color::from("#000000"),
+        "blanchedalmond" => Color::from("#FFEBCD"),
+        "blue" => Color::from("#0000FF"),
+        "blueviolet" => Color::from("#8A2BE2"),
+        "brown" => Color::from("#A52A2A"),
+        "burlywood" => Color::from("#DEB887"),
+        "cadetblue" => Color::from("#5F9EA0"),
+        "chartreuse" => Color::from("#7FFF00"),
+        "chocolate" => Color::from("#D2691E"),
+        "coral" => Color::from("#FF7F50"),
+        "cornflowerblue" => Color::from("#6495ED"),
+        "cornsilk" => Color::from("#FFF8DC"),
+        "crimson" => Color::from("#DC143C"),
+        "cyan" => Color::from("#00FFFF"),
+        "darkblue" => Color::from("#00008B"),
+        "darkcyan" => Color::from("#008B8B"),
+        "darkgoldenrod" => Color::from("#B8860B"),
+        "darkgray" => Color::from("#A9A9A9"),
+        "darkgreen" => Color::from("#006400"),
+        "darkgrey" => Color::from("#A9A9A9"),
+        "darkkhaki" => Color::from("#BDB76B"),
+        "darkmagenta" => Color::from("#8B008B"),
+        "darkolivegreen" => Color::from("#556B2F"),
+        "darkorange" => Color::from("#FF8C00"),
+        "darkorchid" => Color::from("#9932CC"),
+        "darkred" => Color::from("#8B0000"),
+        "darksalmon" => Color::from("#E9967A"),
+        "darkseagreen" => Color::from("#8FBC8F"),
+        "darkslateblue" => Color::from("#483D8B"),
+        "darkslategray" => Color::from("#2F4F4F"),
+        "darkslategrey" => Color::from("#2F4F4F"),
+        "darkturquoise" => Color::from("#00CED1"),
+        "darkviolet" => Color::from("#9400D3"),
+        "deeppink" => Color::from("#FF1493"),
+        "deepskyblue" => Color::from("#00BFFF"),
+        "dimgray" => Color::from("#696969"),
+        "dimgrey" => Color::from("#696969"),
+        "dodgerblue" => Color::from("#1E90FF"),
+        "firebrick" => Color::from("#B22222"),
+        "floralwhite" => Color::from("#FFFAF0"),
+        "forestgreen" => Color::from("#228B22"),
+        "fuchsia" => Color::from("#FF00FF"),
+        "gainsboro" => Color::from("#DCDCDC"),
+        "ghostwhite" => Color::from("#F8F8FF"),
+        "gold" => Color::from("#FFD700"),
+        "goldenrod" => Color::from("#DAA520"),
+        "gray" => Color::from("#808080"),
+        "green" => Color::from("#008000"),
+        "greenyellow" => Color::from("#ADFF2F"),
+        "grey" => Color::from("#808080"),
+        "honeydew" => Color::from("#F0FFF0"),
+        "hotpink" => Color::from("#FF69B4"),
+        "indianred" => Color::from("#CD5C5C"),
+        "indigo" => Color::from("#4B0082"),
+        "ivory" => Color::from("#FFFFF0"),
+        "khaki" => Color::from("#F0E68C"),
+        "lavender" => Color::from("#E6E6FA"),
+        "lavenderblush" => Color::from("#FFF0F5"),
+        "lawngreen" => Color::from("#7CFC00"),
+        "lemonchiffon" => Color::from("#FFFACD"),
+        "lightblue" => Color::from("#ADD8E6"),
+        "lightcoral" => Color::from("#F08080"),
+        "lightcyan" => Color::from("#E0FFFF"),
+        "lightgoldenrodyellow" => Color::from("#FAFAD2"),
+        "lightgray" => Color::from("#D3D3D3"),
+        "lightgreen" => Color::from("#90EE90"),
+        "lightgrey" => Color::from("#D3D3D3"),
+        "lightpink" => Color::from("#FFB6C1"),
+        "lightsalmon" => Color::from("#FFA07A"),
+        "lightseagreen" => Color::from("#20B2AA"),
+        "lightskyblue" => Color::from("#87CEFA"),
+        "lightslategray" => Color::from("#778899"),
+        "lightslategrey" => Color::from("#778899"),
+        "lightsteelblue" => Color::from("#B0C4DE"),
+        "lightyellow" => Color::from("#FFFFE0"),
+        "lime" => Color::from("#00FF00"),
+        "limegreen" => Color::from("#32CD32"),
+        "linen" => Color::from("#FAF0E6"),
+        "magenta" => Color::from("#FF00FF"),
+        "maroon" => Color::from("#800000"),
+        "mediumaquamarine" => Color::from("#66CDAA"),
+        "mediumblue" => Color::from("#0000CD"),
+        "mediumorchid" => Color::from("#BA55D3"),
+        "mediumpurple" => Color::from("#9370DB"),
+        "mediumseagreen" => Color::from("#3CB371"),
+        "mediumslateblue" => Color::from("#7B68EE"),
+        "mediumspringgreen" => Color::from("#00FA9A"),
+        "mediumturquoise" => Color::from("#48D1CC"),
+        "mediumvioletred" => Color::from("#C71585"),
+        "midnightblue" => Color::from("#191970"),
+        "mintcream" => Color::from("#F5FFFA"),
+        "mistyrose" => Color::from("#FFE4E1"),
+        "moccasin" => Color::from("#FFE4B5"),
+        "navajowhite" => Color::from("#FFDEAD"),
+        "navy" => Color::from("#000080"),
+        "oldlace" => Color::from("#FDF5E6"),
+        "olive" => Color::from("#808000"),
+        "olivedrab" => Color::from("#6B8E23"),
+        "orange" => Color::from("#FFA500"),
+        "orangered" => Color::from("#FF4500"),
+        "orchid" => Color::from("#DA70D6"),
+        "palegoldenrod" => Color::from("#EEE8AA"),
+        "palegreen" => Color::from("#98FB98"),
+        "paleturquoise" => Color::from("#AFEEEE"),
+        "palevioletred" => Color::from("#DB7093"),
+        "papayawhip" => Color::from("#FFEFD5"),
+        "peachpuff" => Color::from("#FFDAB9"),
+        "peru" => Color::from("#CD853F"),
+        "pink" => Color::from("#FFC0CB"),
+        "plum" => Color::from("#DDA0DD"),
+        "powderblue" => Color::from("#B0E0E6"),
+        "purple" => Color::from("#800080"),
+        "rebeccapurple" => Color::from("#663399"),
+        "red" => Color::from("#FF0000"),
+        "rosybrown" => Color::from("#BC8F8F"),
+        "royalblue" => Color::from("#4169E1"),
+        "saddlebrown" => Color::from("#8B4513"),
+        "salmon" => Color::from("#FA8072"),
+        "sandybrown" => Color::from("#F4A460"),
+        "seagreen" => Color::from("#2E8B57"),
+        "seashell" => Color::from("#FFF5EE"),
+        "sienna" => Color::from("#A0522D"),
+        "silver" => Color::from("#C0C0C0"),
+        "skyblue" => Color::from("#87CEEB"),
+        "slateblue" => Color::from("#6A5ACD"),
+        "slategray" => Color::from("#708090"),
+        "slategrey" => Color::from("#708090"),
+        "snow" => Color::from("#FFFAFA"),
+        "springgreen" => Color::from("#00FF7F"),
+        "steelblue" => Color::from("#4682B4"),
+        "tan" => Color::from("#D2B48C"),
+        "teal" => Color::from("#008080"),
+        "thistle" => Color::from("#D8BFD8"),
+        "tomato" => Color::from("#FF6347"),
+        "turquoise" => Color::from("#40E0D0"),
+        "violet" => Color::from("#EE82EE"),
+        "wheat" => Color::from("#F5DEB3"),
+        "white" => Color::from("#FFFFFF"),
+        "whitesmoke" => Color::from("#F5F5F5"),
+        "yellow" => Color::from("#FFFF00"),
+        "yellowgreen" => Color::from("#9ACD32"),
         _ => return None,
     })
 }
@@ -487,16 +1021,196 @@ fn parse_string<'i, 't>(
     })
 }
 
+// Parses `none` or a shadow with two to four lengths (offset x, offset y, blur and spread) and a
+// color before or after them, e.g. `0 2px 4px rgba(0, 0, 0, 0.3)`. Without a color the shadow is
+// black.
+fn parse_box_shadow<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<BoxShadow, ParseError<'i, CustomParseError>> {
+    if input
+        .r#try(|input| input.expect_ident_matching("none"))
+        .is_ok()
+    {
+        return Ok(BoxShadow::default());
+    }
+
+    let color = input.r#try(parse_color).ok();
+    let mut lengths = vec![];
+
+    while lengths.len() < 4 {
+        match input.r#try(parse_number) {
+            Ok(length) => lengths.push(length.length().unwrap_or_default()),
+            Err(_) => break,
+        }
+    }
+
+    if lengths.len() < 2 {
+        return Err(CustomParseError::InvalidBoxShadow(
+            "expected at least two lengths".to_string(),
+        )
+        .into());
+    }
+
+    let color = match color {
+        Some(color) => color,
+        None => input
+            .r#try(parse_color)
+            .unwrap_or_else(|_| Color::rgba(0, 0, 0, 255)),
+    };
+    lengths.resize(4, 0.0);
+
+    Ok(BoxShadow::new(
+        lengths[0], lengths[1], lengths[2], lengths[3], color,
+    ))
+}
+
+// Parses a comma separated list of transitions or `none`.
+fn parse_transitions<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Vec<Transition>, ParseError<'i, CustomParseError>> {
+    if input
+        .r#try(|input| input.expect_ident_matching("none"))
+        .is_ok()
+    {
+        return Ok(vec![]);
+    }
+
+    input.parse_comma_separated(parse_transition)
+}
+
+// Parses a single transition e.g. `background 150ms ease-out 50ms`. The first time is the
+// duration, the second one the delay.
+fn parse_transition<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Transition, ParseError<'i, CustomParseError>> {
+    let mut transition = Transition::default();
+    let mut has_duration = false;
+
+    while !input.is_exhausted() {
+        if input
+            .r#try(|input| input.expect_function_matching("cubic-bezier"))
+            .is_ok()
+        {
+            transition.easing = input.parse_nested_block(parse_cubic_bezier)?;
+            continue;
+        }
+
+        match input.next()? {
+            Token::Dimension {
+                value, ref unit, ..
+            } => {
+                let time = match unit.to_lowercase().as_str() {
+                    // converted after scaling to keep whole milliseconds, e.g. 0.2s is 200ms
+                    "s" => (value * 1000.0) as f64,
+                    "ms" => value as f64,
+                    unit => {
+                        return Err(CustomParseError::InvalidTransition(format!(
+                            "unknown time unit {}",
+                            unit
+                        ))
+                        .into())
+                    }
+                };
+
+                if has_duration {
+                    transition.delay = time;
+                } else {
+                    transition.duration = time;
+                    has_duration = true;
+                }
+            }
+
+            Token::Ident(ident) => match &*ident {
+                "linear" | "ease" | "ease-in" | "ease-out" | "ease-in-out" => {
+                    transition.easing = Easing::from(&*ident)
+                }
+                _ => transition.property = String::from(&*ident),
+            },
+
+            t => return Err(BasicParseError::UnexpectedToken(t).into()),
+        }
+    }
+
+    Ok(transition)
+}
+
+// Parses the control points of `cubic-bezier(x1, y1, x2, y2)`.
+fn parse_cubic_bezier<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Easing, ParseError<'i, CustomParseError>> {
+    let x1 = input.expect_number()? as f64;
+    input.expect_comma()?;
+    let y1 = input.expect_number()? as f64;
+    input.expect_comma()?;
+    let x2 = input.expect_number()? as f64;
+    input.expect_comma()?;
+    let y2 = input.expect_number()? as f64;
+
+    Ok(Easing::CubicBezier(x1, y1, x2, y2))
+}
+
 fn parse_basic_color<'i, 't>(
     input: &mut Parser<'i, 't>,
 ) -> Result<Brush, ParseError<'i, CustomParseError>> {
+    if input
+        .r#try(|input| input.expect_function_matching("linear-gradient"))
+        .is_ok()
+    {
+        return input.parse_nested_block(parse_linear_gradient);
+    }
+
+    if input
+        .r#try(|input| input.expect_function_matching("radial-gradient"))
+        .is_ok()
+    {
+        return input.parse_nested_block(parse_radial_gradient);
+    }
+
+    if let Ok(source) = input.r#try(|input| input.expect_url().map(|s| s.into_owned())) {
+        return Ok(Brush::Relative(Box::new(Brush::ImagePattern {
+            source,
+            origin: Point::new(0.0, 0.0),
+        })));
+    }
+
+    Ok(Brush::from(parse_color(input)?))
+}
+
+// Parses a named color, a hex color or one of the color functions `rgb()`, `rgba()`, `hsl()` and
+// `hsla()`.
+fn parse_color<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Color, ParseError<'i, CustomParseError>> {
     Ok(match input.next()? {
         Token::Ident(s) => match css_color(&s) {
             Some(color) => color,
             None => return Err(CustomParseError::InvalidColorName(s.into_owned()).into()),
         },
 
-        Token::IDHash(hash) | Token::Hash(hash) => Brush::from(hash.into_owned()),
+        Token::IDHash(hash) | Token::Hash(hash) => {
+            if !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(CustomParseError::InvalidColorHex(hash.into_owned()).into());
+            }
+
+            match hash.len() {
+                6 | 8 => Color::from(hash.as_ref()),
+                // the short forms double each digit, e.g. `#fffa` is `#ffffffaa`
+                3 | 4 => Color::from(hash.chars().flat_map(|c| vec![c, c]).collect::<String>()),
+                _ => return Err(CustomParseError::InvalidColorHex(hash.into_owned()).into()),
+            }
+        }
+
+        Token::Function(ref name)
+            if name.eq_ignore_ascii_case("rgb") || name.eq_ignore_ascii_case("rgba") =>
+        {
+            input.parse_nested_block(parse_rgb)?
+        }
+
+        Token::Function(ref name)
+            if name.eq_ignore_ascii_case("hsl") || name.eq_ignore_ascii_case("hsla") =>
+        {
+            input.parse_nested_block(parse_hsl)?
+        }
 
         t => {
             let basic_error = BasicParseError::UnexpectedToken(t);
@@ -505,29 +1219,757 @@ fn parse_basic_color<'i, 't>(
     })
 }
 
+// Parses the arguments of `rgb()` and `rgba()`.
+fn parse_rgb<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Color, ParseError<'i, CustomParseError>> {
+    let r = parse_color_channel(input)?;
+    skip_comma(input);
+    let g = parse_color_channel(input)?;
+    skip_comma(input);
+    let b = parse_color_channel(input)?;
+    let a = parse_alpha(input)?;
+
+    Ok(Color::rgba(r, g, b, a))
+}
+
+// Parses the arguments of `hsl()` and `hsla()`.
+fn parse_hsl<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Color, ParseError<'i, CustomParseError>> {
+    let hue = parse_angle(input)?;
+    skip_comma(input);
+    let saturation = input.expect_percentage()? as f64 * 100.0;
+    skip_comma(input);
+    let lightness = input.expect_percentage()? as f64 * 100.0;
+    let alpha = parse_alpha(input)?;
+
+    Ok(Color::hsla(
+        hue,
+        saturation,
+        lightness,
+        alpha as f64 / 255.0,
+    ))
+}
+
+// Color functions could separate their arguments by commas or spaces.
+fn skip_comma(input: &mut Parser) {
+    let _ = input.r#try(|input| input.expect_comma());
+}
+
+// Parses a color channel from 0 to 255 or a percentage.
+fn parse_color_channel<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<u8, ParseError<'i, CustomParseError>> {
+    Ok(match input.next()? {
+        Token::Number { value, .. } => value.max(0.0).min(255.0).round() as u8,
+        Token::Percentage { unit_value, .. } => {
+            (unit_value.max(0.0).min(1.0) * 255.0).round() as u8
+        }
+        t => return Err(BasicParseError::UnexpectedToken(t).into()),
+    })
+}
+
+// Parses the optional alpha value of a color function as number from 0.0 to 1.0 or as percentage.
+fn parse_alpha<'i, 't>(input: &mut Parser<'i, 't>) -> Result<u8, ParseError<'i, CustomParseError>> {
+    if input.is_exhausted() {
+        return Ok(255);
+    }
+
+    if input.r#try(|input| input.expect_delim('/')).is_err() {
+        input.expect_comma()?;
+    }
+
+    Ok(match input.next()? {
+        Token::Number { value, .. }
+        | Token::Percentage {
+            unit_value: value, ..
+        } => (value.max(0.0).min(1.0) * 255.0).round() as u8,
+        t => return Err(BasicParseError::UnexpectedToken(t).into()),
+    })
+}
+
+// Parses an angle and returns it in degrees. Numbers without unit are handled as degrees.
+fn parse_angle<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<f64, ParseError<'i, CustomParseError>> {
+    Ok(match input.next()? {
+        Token::Number { value, .. } => value as f64,
+        Token::Dimension {
+            value, ref unit, ..
+        } => {
+            let value = value as f64;
+            let unit = unit.to_lowercase();
+
+            match unit.as_str() {
+                "deg" => value,
+                "rad" => value.to_degrees(),
+                "grad" => value * 0.9,
+                "turn" => value * 360.0,
+                _ => {
+                    return Err(CustomParseError::InvalidGradient(format!(
+                        "unknown angle unit {}",
+                        unit
+                    ))
+                    .into())
+                }
+            }
+        }
+        t => return Err(BasicParseError::UnexpectedToken(t).into()),
+    })
+}
+
+// Parses the direction of a gradient e.g. `to top right` and returns it as angle in degrees.
+fn parse_side<'i, 't>(input: &mut Parser<'i, 't>) -> Result<f64, ParseError<'i, CustomParseError>> {
+    input.expect_ident_matching("to")?;
+
+    let (mut x, mut y) = (0.0_f64, 0.0_f64);
+
+    for i in 0..2 {
+        let side = if i == 0 {
+            input.expect_ident()?.into_owned()
+        } else if let Ok(side) = input.r#try(|input| input.expect_ident().map(|s| s.into_owned())) {
+            side
+        } else {
+            break;
+        };
+
+        match side.as_str() {
+            "left" => x = -1.0,
+            "right" => x = 1.0,
+            "top" => y = -1.0,
+            "bottom" => y = 1.0,
+            _ => {
+                return Err(
+                    CustomParseError::InvalidGradient(format!("unknown side {}", side)).into(),
+                )
+            }
+        }
+    }
+
+    Ok(x.atan2(-y).to_degrees())
+}
+
+// Parses the arguments of `linear-gradient()`. The start and end point of the resulting brush are
+// relative to the bounds of the filled area (from 0.0 to 1.0), so it is wrapped in `Brush::Relative`.
+fn parse_linear_gradient<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Brush, ParseError<'i, CustomParseError>> {
+    let angle = if let Ok(angle) = input.r#try(parse_angle) {
+        input.expect_comma()?;
+        angle
+    } else if let Ok(angle) = input.r#try(parse_side) {
+        input.expect_comma()?;
+        angle
+    } else {
+        // to bottom
+        180.0
+    };
+
+    let (start, end) = gradient_line(angle);
+
+    Ok(Brush::Relative(Box::new(Brush::LinearGradient {
+        start,
+        end,
+        stops: parse_color_stops(input)?,
+    })))
+}
+
+// Parses the arguments of `radial-gradient()`, e.g. `radial-gradient(circle 40% at 25% 25%, white,
+// black)`. Only circles are supported. The center is relative to the bounds of the filled area and
+// the radius to its larger side, it defaults to 50%.
+fn parse_radial_gradient<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Brush, ParseError<'i, CustomParseError>> {
+    let mut center = Point::new(0.5, 0.5);
+    let mut radius = 0.5;
+
+    let has_shape = input
+        .r#try(|input| input.expect_ident_matching("circle"))
+        .is_ok();
+
+    let has_radius = if let Ok(r) = input.r#try(|input| input.expect_percentage()) {
+        radius = percentage(r);
+        true
+    } else {
+        false
+    };
+
+    let has_center = if input
+        .r#try(|input| input.expect_ident_matching("at"))
+        .is_ok()
+    {
+        center = Point::new(
+            percentage(input.expect_percentage()?),
+            percentage(input.expect_percentage()?),
+        );
+        true
+    } else {
+        false
+    };
+
+    if has_shape || has_radius || has_center {
+        input.expect_comma()?;
+    }
+
+    Ok(Brush::Relative(Box::new(Brush::RadialGradient {
+        center,
+        radius,
+        stops: parse_color_stops(input)?,
+    })))
+}
+
+// Parses the comma separated color stops of a gradient.
+fn parse_color_stops<'i, 't>(
+    input: &mut Parser<'i, 't>,
+) -> Result<Vec<LinearGradientStop>, ParseError<'i, CustomParseError>> {
+    let mut stops: Vec<(Color, Option<f64>)> = vec![];
+
+    loop {
+        let color = parse_color(input)?;
+        let position = input
+            .r#try(|input| input.expect_percentage())
+            .ok()
+            .map(percentage);
+        stops.push((color, position));
+
+        if input.r#try(|input| input.expect_comma()).is_err() {
+            break;
+        }
+    }
+
+    if stops.len() < 2 {
+        return Err(CustomParseError::InvalidGradient(
+            "a gradient needs at least two color stops".to_string(),
+        )
+        .into());
+    }
+
+    Ok(gradient_stops(stops))
+}
+
+// Converts a css percentage that is parsed as `f32` from 0.0 to 1.0. Going over the percent value
+// keeps e.g. `40%` at exactly 0.4.
+fn percentage(unit_value: f32) -> f64 {
+    (unit_value * 100.0) as f64 / 100.0
+}
+
+// Calculates the start and end of a gradient line with the given angle (0deg points up) inside of
+// a unit square.
+fn gradient_line(angle: f64) -> (Point, Point) {
+    let angle = angle.to_radians();
+    let (dx, dy) = (angle.sin(), -angle.cos());
+    let half_length = (dx.abs() + dy.abs()) / 2.0;
+
+    (
+        Point::new(0.5 - dx * half_length, 0.5 - dy * half_length),
+        Point::new(0.5 + dx * half_length, 0.5 + dy * half_length),
+    )
+}
+
+// Fills missing stop positions. The first stop defaults to 0.0, the last to 1.0 and stops between
+// are distributed evenly between their neighbors.
+fn gradient_stops(stops: Vec<(Color, Option<f64>)>) -> Vec<LinearGradientStop> {
+    let len = stops.len();
+    let mut positions: Vec<Option<f64>> = stops.iter().map(|s| s.1).collect();
+
+    if positions[0].is_none() {
+        positions[0] = Some(0.0);
+    }
+
+    if positions[len - 1].is_none() {
+        positions[len - 1] = Some(1.0);
+    }
+
+    let mut i = 0;
+    while i < len {
+        if positions[i].is_some() {
+            i += 1;
+            continue;
+        }
+
+        let start = i - 1;
+        let mut end = i;
+        while positions[end].is_none() {
+            end += 1;
+        }
+
+        let from = positions[start].unwrap();
+        let to = positions[end].unwrap();
+
+        for (offset, position) in positions[i..end].iter_mut().enumerate() {
+            *position =
+                Some(from + (to - from) * (i + offset - start) as f64 / (end - start) as f64);
+        }
+
+        i = end;
+    }
+
+    let mut last = 0.0_f64;
+
+    stops
+        .iter()
+        .zip(positions)
+        .map(|(stop, position)| {
+            // positions could not decrease
+            last = last.max(position.unwrap());
+
+            LinearGradientStop {
+                position: last,
+                color: stop.0,
+            }
+        })
+        .collect()
+}
+
+/// Parses the given css. Invalid rules and declarations are skipped.
 pub fn parse(s: &str) -> Vec<Rule> {
+    parse_rules(s).0
+}
+
+/// Parses the given css and returns all parse errors if it contains invalid rules or declarations.
+pub fn try_parse(s: &str) -> Result<Vec<Rule>, Vec<ThemeParseError>> {
+    let (rules, errors) = parse_rules(s);
+
+    if errors.is_empty() {
+        Ok(rules)
+    } else {
+        Err(errors)
+    }
+}
+
+// Parses the valid rules of the given css and collects the errors of the invalid ones.
+fn parse_rules(s: &str) -> (Vec<Rule>, Vec<ThemeParseError>) {
     let mut input = ParserInput::new(s);
     let mut parser = Parser::new(&mut input);
-    let rule_parser = RuleParser::new();
+    let start = parser.position();
+    let mut errors = vec![];
 
-    let rules = {
+    let results = {
+        let rule_parser = RuleParser::new(start, &mut errors);
         let rule_list_parser =
             cssparser::RuleListParser::new_for_stylesheet(&mut parser, rule_parser);
         rule_list_parser.collect::<Vec<_>>()
     };
 
-    for rule in &rules {
-        match *rule {
-            Ok(_) => {}
-            Err(ref e) => {
-                match e.error {
-                    ParseError::Basic(ref e) => eprintln!("{:?}", e),
-                    ParseError::Custom(ref e) => eprintln!("{:?}", e),
-                }
-                println!("Error occurred in `{}`", parser.slice(e.span.clone()));
+    let mut rules = vec![];
+
+    for result in results {
+        match result {
+            Ok(rule) => rules.push(rule),
+            Err(e) => errors.push(ThemeParseError::new(&parser, start, e.span, e.error)),
+        }
+    }
+
+    // report the errors in the order of the css
+    errors.sort_by_key(|e| (e.line, e.column));
+
+    (rules, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_var() {
+        let theme = Theme::parse(
+            "* { --accent: #efd035; --size: 14; } \
+             button { background: var(--accent); font-size: var(--size); color: var(--missing, #ffffff); }",
+        );
+        let selector = Selector::from("button");
+
+        assert_eq!(
+            theme.brush("background", &selector),
+            Some(Brush::from("#efd035"))
+        );
+        assert_eq!(theme.uint("font-size", &selector), Some(14));
+        assert_eq!(
+            theme.brush("color", &selector),
+            Some(Brush::from("#ffffff"))
+        );
+    }
+
+    #[test]
+    fn test_selector_relations() {
+        let theme = Theme::parse(
+            "a > b:selected { background: #ff0000; } \
+             .toolbar button { background: #0000ff; }",
+        );
+        assert!(theme.has_selector_relations());
+        assert!(!Theme::parse("button { background: #0000ff; }").has_selector_relations());
+
+        // the queried selector holds the selectors of its ancestors as parent relations
+        let child_of = |selector: Selector, parent: Selector| {
+            let mut selector = selector;
+            selector.relation = Some(Box::new(SelectorRelation::Parent(parent)));
+            selector
+        };
+        let background = |selector: &Selector| theme.brush("background", selector);
+
+        let selected = Selector::from("b").pseudo_class("selected");
+        assert_eq!(
+            background(&child_of(selected.clone(), Selector::from("a"))),
+            Some(Brush::from("#ff0000"))
+        );
+        assert_eq!(background(&child_of(selected, Selector::from("c"))), None);
+        assert_eq!(
+            background(&child_of(Selector::from("b"), Selector::from("a"))),
+            None
+        );
+
+        let toolbar = Selector::from("container").class("toolbar");
+        assert_eq!(
+            background(&child_of(
+                Selector::from("button"),
+                child_of(Selector::from("stack"), toolbar)
+            )),
+            Some(Brush::from("#0000ff"))
+        );
+        assert_eq!(
+            background(&child_of(Selector::from("button"), Selector::from("stack"))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_builder_paths() {
+        let builder = Theme::create_from_path("theme.css")
+            .extension_css("a { color: red; }")
+            .extension_path("extension.css");
+
+        assert_eq!(
+            builder.paths(),
+            vec!["theme.css".to_string(), "extension.css".to_string()]
+        );
+        assert!(Theme::create().paths().is_empty());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let css =
+            "a { color: #ff0000; }\nb {\n  color: redd;\n  background: blue;\n}\n% { color: red; }";
+
+        let errors = try_parse(css).unwrap_err();
+        assert_eq!(errors.len(), 2);
+
+        assert_eq!((errors[0].line, errors[0].column), (3, 3));
+        assert!(errors[0].snippet.starts_with("color: redd"));
+        assert_eq!(
+            errors[0].kind,
+            CustomParseError::InvalidColorName("redd".to_string())
+        );
+
+        assert_eq!(errors[1].line, 6);
+        assert!(errors[1].snippet.starts_with('%'));
+        match errors[1].kind {
+            CustomParseError::InvalidSyntax(_) => {}
+            ref kind => panic!("unexpected error kind {:?}", kind),
+        }
+
+        assert!(Theme::create_from_css(css).try_build().is_err());
+        assert!(try_parse("a { color: #ff0000; }").is_ok());
+
+        // lenient mode skips the invalid rules and declarations
+        let theme = Theme::create_from_css(css).build();
+        assert_eq!(
+            theme.brush("background", &Selector::from("b")),
+            Some(Brush::from("#0000ff"))
+        );
+        assert_eq!(theme.brush("color", &Selector::from("b")), None);
+        assert_eq!(parse(css).len(), 2);
+    }
+
+    #[test]
+    fn test_parse_errors_origin() {
+        let path = std::env::temp_dir().join("orbtk_test_parse_errors_origin.css");
+        std::fs::write(&path, "a { color: #ff0000; }\nb { color: redd; }").unwrap();
+        let path = path.to_string_lossy().to_string();
+
+        let errors = Theme::create_from_css("c {\n  color: bluee;\n}")
+            .extension_path(path.clone())
+            .try_build()
+            .unwrap_err();
+        let _ = std::fs::remove_file(&path);
+
+        // the lines are counted in each source
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].origin, Some(path.clone()));
+        assert_eq!((errors[0].line, errors[0].column), (2, 5));
+        assert!(errors[0].to_string().starts_with(&format!("{}:2:5:", path)));
+        assert_eq!(errors[1].origin, None);
+        assert_eq!((errors[1].line, errors[1].column), (2, 3));
+        assert!(errors[1].to_string().starts_with("2:3:"));
+    }
+
+    #[test]
+    fn test_transitions() {
+        let theme = Theme::parse(
+            "a { transition: background 150ms ease-out, opacity 1s linear 50ms; } \
+             a:hover { transition: all 0.2s cubic-bezier(0.1, 0.2, 0.3, 0.4); } \
+             b { transition: none; }",
+        );
+        let a = Selector::from("a");
+
+        assert_eq!(
+            theme.transition("background", &a),
+            Some(Transition {
+                property: "background".to_string(),
+                duration: 150.0,
+                delay: 0.0,
+                easing: Easing::EaseOut,
+            })
+        );
+        assert_eq!(
+            theme.transition("opacity", &a),
+            Some(Transition {
+                property: "opacity".to_string(),
+                duration: 1000.0,
+                delay: 50.0,
+                easing: Easing::Linear,
+            })
+        );
+        assert_eq!(theme.transition("color", &a), None);
+
+        let mut hover = Selector::from("a");
+        hover.pseudo_classes.insert("hover".to_string());
+        let transition = theme.transition("color", &hover).unwrap();
+        assert_eq!(transition.duration, 200.0);
+        // the control points are parsed as f32
+        assert_eq!(
+            transition.easing,
+            Easing::CubicBezier(
+                0.1_f32 as f64,
+                0.2_f32 as f64,
+                0.3_f32 as f64,
+                0.4_f32 as f64
+            )
+        );
+
+        assert_eq!(theme.transition("background", &Selector::from("b")), None);
+    }
+
+    #[test]
+    fn test_colors() {
+        let theme = Theme::parse(
+            "a { color: rebeccapurple; background: rgb(255, 0, 128); border-color: rgba(0, 0, 0, 50%); } \
+             b { color: hsl(120, 100%, 25%); background: #01020304; border-color: DarkSlateGray; }",
+        );
+        let a = Selector::from("a");
+        let b = Selector::from("b");
+
+        assert_eq!(theme.brush("color", &a), Some(Brush::from("#663399")));
+        assert_eq!(theme.brush("background", &a), Some(Brush::from("#ff0080")));
+        assert_eq!(
+            Color::from(theme.brush("border-color", &a).unwrap()).a(),
+            128
+        );
+        assert_eq!(theme.brush("color", &b), Some(Brush::from("#008000")));
+        assert_eq!(
+            Color::from(theme.brush("background", &b).unwrap()).a(),
+            0x01
+        );
+        assert_eq!(
+            theme.brush("border-color", &b),
+            Some(Brush::from("#2f4f4f"))
+        );
+    }
+
+    #[test]
+    fn test_short_hex_colors() {
+        let theme = Theme::parse("a { color: #fff; background: #fffa; border-color: #1234; }");
+        let a = Selector::from("a");
+
+        let color = |property: &str| Color::from(theme.brush(property, &a).unwrap());
+
+        assert_eq!(color("color"), Color::rgb(0xff, 0xff, 0xff));
+        assert_eq!(color("color").a(), 0xff);
+        assert_eq!(color("background"), Color::rgb(0xff, 0xff, 0xaa));
+        assert_eq!(color("background").a(), 0xff);
+        assert_eq!(color("border-color"), Color::rgb(0x22, 0x33, 0x44));
+        assert_eq!(color("border-color").a(), 0x11);
+
+        assert!(Theme::create_from_css("a { color: #ffff0; }")
+            .try_build()
+            .is_err());
+    }
+
+    // Gets the background of the given element if it is relative to the bounds of the filled area.
+    fn relative_background(theme: &Theme, element: &str) -> Option<Brush> {
+        match theme.brush("background", &Selector::from(element)) {
+            Some(Brush::Relative(brush)) => Some(*brush),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_linear_gradient() {
+        let theme = Theme::parse(
+            "a { background: linear-gradient(to right, red, #0000ff 40%, white); } \
+             b { background: linear-gradient(red, lime, blue, white); } \
+             c { background: linear-gradient(45deg, red, blue); }",
+        );
+
+        let positions = |element: &str| match relative_background(&theme, element) {
+            Some(Brush::LinearGradient { start, end, stops }) => (
+                (start.x, start.y, end.x, end.y),
+                stops.iter().map(|s| s.position).collect::<Vec<f64>>(),
+            ),
+            _ => panic!("{} has no linear gradient", element),
+        };
+
+        let round = |(a, b, c, d): (f64, f64, f64, f64)| {
+            let r = |v: f64| (v * 1000.0).round() / 1000.0;
+            (r(a), r(b), r(c), r(d))
+        };
+
+        let (line, stops) = positions("a");
+        assert_eq!(round(line), (0.0, 0.5, 1.0, 0.5));
+        assert_eq!(stops, vec![0.0, 0.4, 1.0]);
+
+        let (line, stops) = positions("b");
+        assert_eq!(round(line), (0.5, 0.0, 0.5, 1.0));
+        assert_eq!(stops.len(), 4);
+        assert!((stops[1] - 1.0 / 3.0).abs() < 0.001);
+        assert!((stops[2] - 2.0 / 3.0).abs() < 0.001);
+
+        let (line, _) = positions("c");
+        assert_eq!(round(line), (0.0, 1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_radial_gradient_and_pattern() {
+        let theme = Theme::parse(
+            "a { background: radial-gradient(white, black); } \
+             b { background: radial-gradient(circle 25% at 10% 20%, red, blue 50%, lime); } \
+             c { background: url(\"assets/pattern.png\"); }",
+        );
+
+        assert_eq!(
+            relative_background(&theme, "a"),
+            Some(Brush::RadialGradient {
+                center: Point::new(0.5, 0.5),
+                radius: 0.5,
+                stops: vec![
+                    LinearGradientStop {
+                        position: 0.0,
+                        color: Color::rgb(255, 255, 255),
+                    },
+                    LinearGradientStop {
+                        position: 1.0,
+                        color: Color::rgb(0, 0, 0),
+                    },
+                ],
+            })
+        );
+
+        match relative_background(&theme, "b") {
+            Some(Brush::RadialGradient {
+                center,
+                radius,
+                stops,
+            }) => {
+                assert!((center.x - 0.1).abs() < 0.001);
+                assert!((center.y - 0.2).abs() < 0.001);
+                assert!((radius - 0.25).abs() < 0.001);
+                assert_eq!(
+                    stops.iter().map(|s| s.position).collect::<Vec<f64>>(),
+                    vec![0.0, 0.5, 1.0]
+                );
             }
+            _ => panic!("b has no radial gradient"),
         }
+
+        assert_eq!(
+            relative_background(&theme, "c"),
+            Some(Brush::ImagePattern {
+                source: "assets/pattern.png".to_string(),
+                origin: Point::new(0.0, 0.0),
+            })
+        );
+    }
+
+    #[test]
+    fn test_typed_values() {
+        let theme = Theme::parse(
+            "my-widget { padding: 4 8; margin: 1 2 3 4; width: 12.5px; opacity: 50%; text-align: center; border-width: 2; }",
+        );
+        let selector = Selector::from("my-widget");
+
+        assert_eq!(
+            theme.thickness("padding", &selector),
+            Some(Thickness::new(8.0, 4.0, 8.0, 4.0))
+        );
+        assert_eq!(
+            theme.thickness("margin", &selector),
+            Some(Thickness::new(4.0, 1.0, 2.0, 3.0))
+        );
+        assert_eq!(theme.length("width", &selector), Some(12.5));
+        assert_eq!(theme.float("opacity", &selector), Some(0.5));
+        assert_eq!(
+            theme.keyword("text-align", &selector),
+            Some("center".to_string())
+        );
+        assert_eq!(
+            theme.thickness("border-width", &selector),
+            Some(Thickness::new(2.0, 2.0, 2.0, 2.0))
+        );
+        assert_eq!(theme.properties(&selector).len(), 6);
     }
 
-    rules.into_iter().filter_map(|rule| rule.ok()).collect()
+    #[test]
+    fn test_font_values() {
+        let theme = Theme::parse(
+            "a { font-weight: bold; font-style: italic; } b { font-weight: 600; font-stretch: condensed; }",
+        );
+
+        assert_eq!(
+            theme.keyword("font-weight", &Selector::from("a")),
+            Some("bold".to_string())
+        );
+        assert_eq!(
+            theme.keyword("font-style", &Selector::from("a")),
+            Some("italic".to_string())
+        );
+        assert_eq!(theme.uint("font-weight", &Selector::from("b")), Some(600));
+        assert_eq!(
+            theme.keyword("font-stretch", &Selector::from("b")),
+            Some("condensed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_box_shadow() {
+        let theme = Theme::parse(
+            "a { box-shadow: 0 2px 4px rgba(0, 0, 0, 0.5); } \
+             b { box-shadow: #ff0000 -1px 1px; } \
+             c { box-shadow: none; }",
+        );
+
+        assert_eq!(
+            theme.box_shadow("box-shadow", &Selector::from("a")),
+            Some(BoxShadow::new(
+                0.0,
+                2.0,
+                4.0,
+                0.0,
+                Color::rgba(0, 0, 0, 128)
+            ))
+        );
+        assert_eq!(
+            theme.box_shadow("box-shadow", &Selector::from("b")),
+            Some(BoxShadow::new(-1.0, 1.0, 0.0, 0.0, Color::rgb(255, 0, 0)))
+        );
+        assert_eq!(
+            theme.box_shadow("box-shadow", &Selector::from("c")),
+            Some(BoxShadow::default())
+        );
+    }
+
+    #[test]
+    fn test_var_cycle() {
+        let theme = Theme::parse("* { --a: var(--b); --b: var(--a); opacity: var(--a, 0.5); }");
+
+        assert_eq!(theme.float("opacity", &Selector::default()), Some(0.5));
+    }
 }
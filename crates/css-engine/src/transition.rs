@@ -0,0 +1,164 @@
+// Iterations of the newton method used to solve the x coordinate of a cubic bezier curve.
+const BEZIER_ITERATIONS: usize = 8;
+
+/// Describes how the progress of a transition is accelerated, like the css `transition-timing-function`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Easing {
+    Linear,
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// Cubic bezier curve with the control points `(x1, y1)` and `(x2, y2)`.
+    CubicBezier(f64, f64, f64, f64),
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::Ease
+    }
+}
+
+impl Easing {
+    /// Gets the eased progress for the given linear progress from 0.0 to 1.0.
+    pub fn ease(self, progress: f64) -> f64 {
+        let progress = progress.max(0.0).min(1.0);
+
+        let (x1, y1, x2, y2) = match self {
+            Easing::Linear => return progress,
+            Easing::Ease => (0.25, 0.1, 0.25, 1.0),
+            Easing::EaseIn => (0.42, 0.0, 1.0, 1.0),
+            Easing::EaseOut => (0.0, 0.0, 0.58, 1.0),
+            Easing::EaseInOut => (0.42, 0.0, 0.58, 1.0),
+            Easing::CubicBezier(x1, y1, x2, y2) => (x1, y1, x2, y2),
+        };
+
+        if progress == 0.0 || progress == 1.0 {
+            return progress;
+        }
+
+        // find the curve parameter for the progress as x coordinate and return its y coordinate
+        let mut t = progress;
+        for _ in 0..BEZIER_ITERATIONS {
+            let x = bezier(x1, x2, t) - progress;
+            let slope = bezier_slope(x1, x2, t);
+
+            if x.abs() < 1e-6 || slope.abs() < 1e-6 {
+                break;
+            }
+
+            t = (t - x / slope).max(0.0).min(1.0);
+        }
+
+        bezier(y1, y2, t)
+    }
+}
+
+impl From<&str> for Easing {
+    fn from(s: &str) -> Easing {
+        match s {
+            "linear" => Easing::Linear,
+            "ease-in" => Easing::EaseIn,
+            "ease-out" => Easing::EaseOut,
+            "ease-in-out" => Easing::EaseInOut,
+            _ => Easing::Ease,
+        }
+    }
+}
+
+// One coordinate of a cubic bezier curve from (0, 0) to (1, 1).
+fn bezier(p1: f64, p2: f64, t: f64) -> f64 {
+    let u = 1.0 - t;
+    3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t
+}
+
+fn bezier_slope(p1: f64, p2: f64, t: f64) -> f64 {
+    let u = 1.0 - t;
+    3.0 * u * u * p1 + 6.0 * u * t * (p2 - p1) + 3.0 * t * t * (1.0 - p2)
+}
+
+/// Describes the transition of a property, e.g. `transition: background 150ms ease-out`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Transition {
+    /// Name of the css property or `all`.
+    pub property: String,
+
+    /// Duration in milliseconds.
+    pub duration: f64,
+
+    /// Delay in milliseconds before the transition starts.
+    pub delay: f64,
+
+    pub easing: Easing,
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Transition {
+            property: "all".to_string(),
+            duration: 0.0,
+            delay: 0.0,
+            easing: Easing::default(),
+        }
+    }
+}
+
+impl Transition {
+    /// Checks if the transition is used for the given css property.
+    pub fn matches(&self, property: &str) -> bool {
+        self.property == "all" || self.property == property
+    }
+
+    /// Gets the eased progress from 0.0 to 1.0 after the given elapsed milliseconds.
+    pub fn progress(&self, elapsed: f64) -> f64 {
+        let elapsed = elapsed - self.delay;
+
+        if elapsed <= 0.0 {
+            return 0.0;
+        }
+
+        if elapsed >= self.duration {
+            return 1.0;
+        }
+
+        self.easing.ease(elapsed / self.duration)
+    }
+
+    /// Checks if the transition is finished after the given elapsed milliseconds.
+    pub fn is_finished(&self, elapsed: f64) -> bool {
+        elapsed >= self.delay + self.duration
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ease() {
+        assert_eq!(Easing::Linear.ease(0.25), 0.25);
+        assert_eq!(Easing::Ease.ease(0.0), 0.0);
+        assert_eq!(Easing::Ease.ease(1.0), 1.0);
+        assert!((Easing::EaseInOut.ease(0.5) - 0.5).abs() < 0.001);
+        assert!(Easing::EaseIn.ease(0.25) < 0.25);
+        assert!(Easing::EaseOut.ease(0.25) > 0.25);
+    }
+
+    #[test]
+    fn test_progress() {
+        let transition = Transition {
+            property: "background".to_string(),
+            duration: 100.0,
+            delay: 50.0,
+            easing: Easing::Linear,
+        };
+
+        assert!(transition.matches("background"));
+        assert!(!transition.matches("opacity"));
+        assert_eq!(transition.progress(25.0), 0.0);
+        assert_eq!(transition.progress(100.0), 0.5);
+        assert_eq!(transition.progress(200.0), 1.0);
+        assert!(!transition.is_finished(149.0));
+        assert!(transition.is_finished(150.0));
+    }
+}
@@ -0,0 +1,220 @@
+use std::path::Path;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::{fs::File, io::BufReader};
+
+use crate::platform::Image;
+
+// Browsers show frames with a shorter delay for 100 ms, most animated images rely on it.
+const MIN_FRAME_DELAY: f64 = 10.0;
+const DEFAULT_FRAME_DELAY: f64 = 100.0;
+
+/// A frame of an animated image.
+#[derive(Clone, Default, Debug)]
+pub struct Frame {
+    pub image: Image,
+
+    /// Time in milliseconds the frame is shown.
+    pub delay: f64,
+}
+
+/// An image with multiple frames that are shown one after another, e.g. from a GIF or APNG file.
+#[derive(Clone, Default, Debug)]
+pub struct AnimatedImage {
+    frames: Vec<Frame>,
+}
+
+impl AnimatedImage {
+    /// Creates an animated image from the given frames.
+    pub fn from_frames(frames: Vec<Frame>) -> Self {
+        AnimatedImage {
+            frames: frames
+                .into_iter()
+                .map(|frame| Frame {
+                    delay: if frame.delay <= MIN_FRAME_DELAY {
+                        DEFAULT_FRAME_DELAY
+                    } else {
+                        frame.delay
+                    },
+                    ..frame
+                })
+                .collect(),
+        }
+    }
+
+    /// Loads the frames of a GIF or APNG file. Other images are loaded as a single frame.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        use image::{AnimationDecoder, ImageFormat};
+
+        let path = path.as_ref();
+        let error = || "Could not load animated image.".to_string();
+        let reader = BufReader::new(File::open(path).map_err(|_| error())?);
+
+        let frames = match ImageFormat::from_path(path) {
+            Ok(ImageFormat::Gif) => image::gif::GifDecoder::new(reader)
+                .map_err(|_| error())?
+                .into_frames()
+                .collect_frames()
+                .map_err(|_| error())?,
+            Ok(ImageFormat::Png) => image::png::PngDecoder::new(reader)
+                .map_err(|_| error())?
+                .apng()
+                .into_frames()
+                .collect_frames()
+                .unwrap_or_default(),
+            _ => vec![],
+        };
+
+        // not animated
+        if frames.is_empty() {
+            let image = image::open(path).map_err(|_| error())?.to_rgba();
+
+            return Ok(AnimatedImage::from_frames(vec![Frame {
+                image: frame_image(&image),
+                delay: 0.0,
+            }]));
+        }
+
+        Ok(AnimatedImage::from_frames(
+            frames
+                .into_iter()
+                .map(|frame| {
+                    let (numerator, denominator) = frame.delay().numer_denom_ms();
+
+                    Frame {
+                        image: frame_image(frame.buffer()),
+                        delay: numerator as f64 / denominator.max(1) as f64,
+                    }
+                })
+                .collect(),
+        ))
+    }
+
+    /// Animated images are not supported on the web yet.
+    #[cfg(target_arch = "wasm32")]
+    pub fn from_path<P: AsRef<Path>>(_: P) -> Result<Self, String> {
+        Err("Animated images are not supported on the web.".to_string())
+    }
+
+    /// Gets the number of frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Checks if the image has no frames.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Gets the frame with the given index.
+    pub fn frame(&self, index: usize) -> Option<&Frame> {
+        self.frames.get(index)
+    }
+
+    /// Gets the width of the first frame.
+    pub fn width(&self) -> f64 {
+        self.frames.first().map_or(0.0, |frame| frame.image.width())
+    }
+
+    /// Gets the height of the first frame.
+    pub fn height(&self) -> f64 {
+        self.frames
+            .first()
+            .map_or(0.0, |frame| frame.image.height())
+    }
+
+    /// Gets the time in milliseconds one pass through all frames takes.
+    pub fn duration(&self) -> f64 {
+        self.frames.iter().map(|frame| frame.delay).sum()
+    }
+
+    /// Gets the index of the frame that is shown the given time in milliseconds after the start
+    /// and if the animation has finished. An animation that loops never finishes.
+    pub fn frame_at(&self, time: f64, looping: bool) -> (usize, bool) {
+        let duration = self.duration();
+
+        if self.frames.len() < 2 || duration <= 0.0 {
+            return (0, true);
+        }
+
+        if !looping && time >= duration {
+            return (self.frames.len() - 1, true);
+        }
+
+        let mut time = time.max(0.0) % duration;
+
+        for (index, frame) in self.frames.iter().enumerate() {
+            if time < frame.delay {
+                return (index, false);
+            }
+
+            time -= frame.delay;
+        }
+
+        (self.frames.len() - 1, false)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn frame_image(buffer: &image::RgbaImage) -> Image {
+    let data: Vec<u32> = buffer
+        .pixels()
+        .map(|p| {
+            ((p[3] as u32) << 24) | ((p[0] as u32) << 16) | ((p[1] as u32) << 8) | (p[2] as u32)
+        })
+        .collect();
+
+    Image::from((buffer.width(), buffer.height(), data))
+}
+
+// --- Conversions ---
+
+impl From<&str> for AnimatedImage {
+    /// Loads the frames from file path. The animated image is empty if the file could not be
+    /// loaded.
+    fn from(s: &str) -> AnimatedImage {
+        AnimatedImage::from_path(s).unwrap_or_default()
+    }
+}
+
+impl From<String> for AnimatedImage {
+    fn from(s: String) -> AnimatedImage {
+        AnimatedImage::from(s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(delays: &[f64]) -> AnimatedImage {
+        AnimatedImage::from_frames(
+            delays
+                .iter()
+                .map(|delay| Frame {
+                    image: Image::default(),
+                    delay: *delay,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_frame_at() {
+        let image = frames(&[100.0, 50.0, 0.0]);
+
+        assert_eq!(image.duration(), 250.0);
+        assert_eq!(image.frame_at(0.0, false), (0, false));
+        assert_eq!(image.frame_at(120.0, false), (1, false));
+        assert_eq!(image.frame_at(200.0, false), (2, false));
+        assert_eq!(image.frame_at(260.0, false), (2, true));
+        assert_eq!(image.frame_at(260.0, true), (0, false));
+    }
+
+    #[test]
+    fn test_single_frame() {
+        assert_eq!(frames(&[0.0]).frame_at(500.0, true), (0, true));
+        assert_eq!(AnimatedImage::default().frame_at(0.0, true), (0, true));
+    }
+}
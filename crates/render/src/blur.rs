@@ -0,0 +1,127 @@
+/// Gets the weights of a gaussian kernel that blurs `radius` pixels to each side. The standard
+/// deviation is half of the radius like the blur radius of css shadows.
+fn kernel(radius: f64) -> Vec<f32> {
+    let half = radius.ceil() as i32;
+    let sigma = (radius / 2.0).max(0.5);
+
+    let weights: Vec<f32> = (-half..=half)
+        .map(|i| (-(i * i) as f64 / (2.0 * sigma * sigma)).exp() as f32)
+        .collect();
+    let sum: f32 = weights.iter().sum();
+
+    weights.iter().map(|w| w / sum).collect()
+}
+
+// Blurs a plane of values in two passes, first each row and then each column. Values outside of
+// the plane are taken from its nearest edge.
+fn blur_plane(plane: &mut [f32], width: usize, height: usize, kernel: &[f32]) {
+    let half = (kernel.len() / 2) as isize;
+    let mut line = vec![];
+
+    for y in 0..height {
+        line.clear();
+        line.extend_from_slice(&plane[y * width..(y + 1) * width]);
+
+        for x in 0..width {
+            plane[y * width + x] = kernel
+                .iter()
+                .enumerate()
+                .map(|(k, w)| {
+                    let i = (x as isize + k as isize - half)
+                        .max(0)
+                        .min(width as isize - 1);
+                    line[i as usize] * w
+                })
+                .sum();
+        }
+    }
+
+    for x in 0..width {
+        line.clear();
+        line.extend((0..height).map(|y| plane[y * width + x]));
+
+        for y in 0..height {
+            plane[y * width + x] = kernel
+                .iter()
+                .enumerate()
+                .map(|(k, w)| {
+                    let i = (y as isize + k as isize - half)
+                        .max(0)
+                        .min(height as isize - 1);
+                    line[i as usize] * w
+                })
+                .sum();
+        }
+    }
+}
+
+/// Blurs a coverage mask with one byte per pixel by a separable gaussian blur.
+pub fn blur_mask(mask: &mut [u8], width: usize, height: usize, radius: f64) {
+    if radius <= 0.0 || width == 0 || height == 0 {
+        return;
+    }
+
+    let mut plane: Vec<f32> = mask.iter().map(|m| *m as f32).collect();
+    blur_plane(&mut plane, width, height, &kernel(radius));
+
+    for (m, p) in mask.iter_mut().zip(plane) {
+        *m = p.round().max(0.0).min(255.0) as u8;
+    }
+}
+
+/// Blurs premultiplied ARGB pixels by a separable gaussian blur.
+pub fn blur_pixels(pixels: &mut [u32], width: usize, height: usize, radius: f64) {
+    if radius <= 0.0 || width == 0 || height == 0 {
+        return;
+    }
+
+    let kernel = kernel(radius);
+
+    for shift in &[0, 8, 16, 24] {
+        let mut plane: Vec<f32> = pixels
+            .iter()
+            .map(|p| ((p >> shift) & 0xFF) as f32)
+            .collect();
+        blur_plane(&mut plane, width, height, &kernel);
+
+        for (pixel, p) in pixels.iter_mut().zip(plane) {
+            let channel = p.round().max(0.0).min(255.0) as u32;
+            *pixel = (*pixel & !(0xFF << shift)) | (channel << shift);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kernel() {
+        let kernel = kernel(4.0);
+
+        assert_eq!(kernel.len(), 9);
+        assert!((kernel.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+        assert!(kernel[4] > kernel[3] && kernel[3] > kernel[2]);
+    }
+
+    #[test]
+    fn test_blur_mask() {
+        // a single opaque pixel in the center
+        let mut mask = vec![0; 81];
+        mask[40] = 255;
+        blur_mask(&mut mask, 9, 9, 2.0);
+
+        assert!(mask[40] < 255);
+        assert!(mask[41] > 0 && mask[41] < mask[40]);
+        assert_eq!(mask[41], mask[39]);
+        assert_eq!(mask[0], 0);
+    }
+
+    #[test]
+    fn test_blur_pixels_keeps_solid_color() {
+        let mut pixels = vec![0xFF33_6699; 16];
+        blur_pixels(&mut pixels, 4, 4, 3.0);
+
+        assert!(pixels.iter().all(|p| *p == 0xFF33_6699));
+    }
+}
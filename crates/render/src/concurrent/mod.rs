@@ -3,7 +3,7 @@ use std::{
     thread,
 };
 
-use crate::{platform, utils::*, Pipeline, RenderTarget, TextMetrics};
+use crate::{platform, utils::*, FillRule, LineCap, LineJoin, Pipeline, RenderTarget, TextMetrics};
 use platform::Image;
 
 #[derive(Clone)]
@@ -20,15 +20,23 @@ impl PartialEq for PipelineWrapper {
 enum RenderTask {
     // Single tasks
     Start(),
+    StartRegion(Rectangle),
     SetBackground(Color),
     Resize {
         width: f64,
         height: f64,
     },
+    SetScaleFactor(f64),
     RegisterFont {
         family: String,
         font_file: &'static [u8],
     },
+    RegisterFontFile {
+        path: String,
+    },
+    SetFontFallbacks {
+        families: Vec<String>,
+    },
 
     // Multi tasks
     FillRect {
@@ -50,6 +58,17 @@ enum RenderTask {
     },
     Fill(),
     Stroke(),
+    FillShadow {
+        color: Color,
+        blur: f64,
+    },
+    BlurRect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        radius: f64,
+    },
     BeginPath(),
     ClosePath(),
     Rectangle {
@@ -103,6 +122,14 @@ enum RenderTask {
         x: f64,
         y: f64,
     },
+    DrawImageWithClipAndSize {
+        image: Image,
+        clip: Rectangle,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
     DrawPipeline {
         x: f64,
         y: f64,
@@ -114,6 +141,24 @@ enum RenderTask {
     SetLineWidth {
         line_width: f64,
     },
+    SetLineDash {
+        segments: Vec<f64>,
+    },
+    SetLineDashOffset {
+        offset: f64,
+    },
+    SetLineCap {
+        line_cap: LineCap,
+    },
+    SetLineJoin {
+        line_join: LineJoin,
+    },
+    SetFillRule {
+        fill_rule: FillRule,
+    },
+    SetMiterLimit {
+        miter_limit: f64,
+    },
     SetAlpha {
         alpha: f32,
     },
@@ -123,6 +168,15 @@ enum RenderTask {
     SetFontSize {
         size: f64,
     },
+    SetFontWeight {
+        weight: FontWeight,
+    },
+    SetFontStyle {
+        style: FontStyle,
+    },
+    SetFontStretch {
+        stretch: FontStretch,
+    },
     SetFillStyle {
         fill_style: Brush,
     },
@@ -142,13 +196,45 @@ enum RenderTask {
         h_moving: f64,
         v_moving: f64,
     },
+    Translate {
+        x: f64,
+        y: f64,
+    },
+    Scale {
+        x: f64,
+        y: f64,
+    },
+    Rotate {
+        angle: f64,
+    },
     Finish(),
     Terminate(),
 }
 
 // Used to send results to the main thread.
 enum RenderResult {
-    Finish { data: Vec<u32> },
+    // `damage` are the repainted regions of the frame in physical pixels, `None` if the whole
+    // frame is repainted
+    Finish {
+        data: Vec<u32>,
+        width: usize,
+        damage: Option<Vec<Rectangle>>,
+    },
+}
+
+// Extends the given region to whole physical pixels.
+fn physical_region(region: Rectangle, scale_factor: f64) -> Rectangle {
+    let (left, top) = (
+        (region.x * scale_factor).floor(),
+        (region.y * scale_factor).floor(),
+    );
+
+    Rectangle::new(
+        left,
+        top,
+        ((region.x + region.width) * scale_factor).ceil() - left,
+        ((region.y + region.height) * scale_factor).ceil() - top,
+    )
 }
 
 // Wrapper for the render thread.
@@ -159,14 +245,18 @@ struct RenderWorker {
 fn is_single_tasks(task: &RenderTask) -> bool {
     match task {
         RenderTask::Start() => true,
+        RenderTask::StartRegion(_) => true,
         RenderTask::SetBackground(_) => true,
         RenderTask::Resize { .. } => true,
+        RenderTask::SetScaleFactor(_) => true,
         RenderTask::RegisterFont { .. } => true,
+        RenderTask::RegisterFontFile { .. } => true,
+        RenderTask::SetFontFallbacks { .. } => true,
         RenderTask::DrawRenderTarget { .. } => true,
         RenderTask::DrawImage { .. } => true,
         RenderTask::DrawImageWithClip { .. } => true,
+        RenderTask::DrawImageWithClipAndSize { .. } => true,
         RenderTask::DrawPipeline { .. } => true,
-        RenderTask::SetTransform { .. } => true,
         RenderTask::Terminate { .. } => true,
         _ => false,
     }
@@ -185,6 +275,11 @@ impl RenderWorker {
 
             let mut render_context_2_d = platform::RenderContext2D::new(width, height);
 
+            // logical size and scale factor of the pixel buffer and the repainted regions of the
+            // current frame
+            let (mut width, mut scale_factor) = (width, 1.0);
+            let mut damage: Option<Vec<Rectangle>> = Some(vec![]);
+
             loop {
                 let mut tasks = receiver.lock().unwrap().recv().unwrap();
 
@@ -194,20 +289,48 @@ impl RenderWorker {
                         RenderTask::Start() => {
                             tasks_collection.clear();
                             render_context_2_d.start();
+                            damage = None;
+                            continue;
+                        }
+                        RenderTask::StartRegion(region) => {
+                            tasks_collection.clear();
+                            render_context_2_d.start_region(region);
+
+                            if let Some(damage) = &mut damage {
+                                damage.push(physical_region(region, scale_factor));
+                            }
                             continue;
                         }
                         RenderTask::SetBackground(background) => {
                             render_context_2_d.set_background(background);
                             continue;
                         }
-                        RenderTask::Resize { width, height } => {
+                        RenderTask::Resize {
+                            width: new_width,
+                            height,
+                        } => {
+                            width = new_width;
                             render_context_2_d.resize(width, height);
                             continue;
                         }
+                        RenderTask::SetScaleFactor(new_scale_factor) => {
+                            scale_factor = new_scale_factor;
+                            render_context_2_d.set_scale_factor(scale_factor);
+                            continue;
+                        }
                         RenderTask::RegisterFont { family, font_file } => {
                             render_context_2_d.register_font(family.as_str(), font_file);
                             continue;
                         }
+                        RenderTask::RegisterFontFile { path } => {
+                            // errors are already reported by the measure context
+                            let _ = render_context_2_d.register_font_file(path.as_str());
+                            continue;
+                        }
+                        RenderTask::SetFontFallbacks { families } => {
+                            render_context_2_d.set_font_fallbacks(families);
+                            continue;
+                        }
                         RenderTask::DrawRenderTarget {
                             render_target,
                             x,
@@ -221,6 +344,17 @@ impl RenderWorker {
                         RenderTask::DrawImageWithClip { image, clip, x, y } => {
                             render_context_2_d.draw_image_with_clip(&image, clip, x, y);
                         }
+                        RenderTask::DrawImageWithClipAndSize {
+                            image,
+                            clip,
+                            x,
+                            y,
+                            width,
+                            height,
+                        } => {
+                            render_context_2_d
+                                .draw_image_with_clip_and_size(&image, clip, x, y, width, height);
+                        }
                         RenderTask::DrawPipeline {
                             x,
                             y,
@@ -230,18 +364,6 @@ impl RenderWorker {
                         } => {
                             render_context_2_d.draw_pipeline(x, y, width, height, pipeline.0);
                         }
-                        RenderTask::SetTransform {
-                            h_scaling,
-                            h_skewing,
-                            v_skewing,
-                            v_scaling,
-                            h_moving,
-                            v_moving,
-                        } => {
-                            render_context_2_d.set_transform(
-                                h_scaling, h_skewing, v_skewing, v_scaling, h_moving, v_moving,
-                            );
-                        }
                         RenderTask::Terminate() => {
                             return;
                         }
@@ -279,6 +401,18 @@ impl RenderWorker {
                             RenderTask::Stroke() => {
                                 render_context_2_d.stroke();
                             }
+                            RenderTask::FillShadow { color, blur } => {
+                                render_context_2_d.fill_shadow(color, blur);
+                            }
+                            RenderTask::BlurRect {
+                                x,
+                                y,
+                                width,
+                                height,
+                                radius,
+                            } => {
+                                render_context_2_d.blur_rect(x, y, width, height, radius);
+                            }
                             RenderTask::BeginPath() => {
                                 render_context_2_d.begin_path();
                             }
@@ -324,6 +458,24 @@ impl RenderWorker {
                             RenderTask::SetLineWidth { line_width } => {
                                 render_context_2_d.set_line_width(line_width);
                             }
+                            RenderTask::SetLineDash { segments } => {
+                                render_context_2_d.set_line_dash(&segments);
+                            }
+                            RenderTask::SetLineDashOffset { offset } => {
+                                render_context_2_d.set_line_dash_offset(offset);
+                            }
+                            RenderTask::SetLineCap { line_cap } => {
+                                render_context_2_d.set_line_cap(line_cap);
+                            }
+                            RenderTask::SetLineJoin { line_join } => {
+                                render_context_2_d.set_line_join(line_join);
+                            }
+                            RenderTask::SetFillRule { fill_rule } => {
+                                render_context_2_d.set_fill_rule(fill_rule);
+                            }
+                            RenderTask::SetMiterLimit { miter_limit } => {
+                                render_context_2_d.set_miter_limit(miter_limit);
+                            }
                             RenderTask::SetAlpha { alpha } => {
                                 render_context_2_d.set_alpha(alpha);
                             }
@@ -336,12 +488,42 @@ impl RenderWorker {
                             RenderTask::SetFontSize { size } => {
                                 render_context_2_d.set_font_size(size);
                             }
+                            RenderTask::SetFontWeight { weight } => {
+                                render_context_2_d.set_font_weight(weight);
+                            }
+                            RenderTask::SetFontStyle { style } => {
+                                render_context_2_d.set_font_style(style);
+                            }
+                            RenderTask::SetFontStretch { stretch } => {
+                                render_context_2_d.set_font_stretch(stretch);
+                            }
                             RenderTask::SetFillStyle { fill_style } => {
                                 render_context_2_d.set_fill_style(fill_style);
                             }
                             RenderTask::SetStrokeStyle { stroke_style } => {
                                 render_context_2_d.set_stroke_style(stroke_style);
                             }
+                            RenderTask::SetTransform {
+                                h_scaling,
+                                h_skewing,
+                                v_skewing,
+                                v_scaling,
+                                h_moving,
+                                v_moving,
+                            } => {
+                                render_context_2_d.set_transform(
+                                    h_scaling, h_skewing, v_skewing, v_scaling, h_moving, v_moving,
+                                );
+                            }
+                            RenderTask::Translate { x, y } => {
+                                render_context_2_d.translate(x, y);
+                            }
+                            RenderTask::Scale { x, y } => {
+                                render_context_2_d.scale(x, y);
+                            }
+                            RenderTask::Rotate { angle } => {
+                                render_context_2_d.rotate(angle);
+                            }
                             RenderTask::Save() => {
                                 render_context_2_d.save();
                             }
@@ -357,6 +539,11 @@ impl RenderWorker {
                                     .unwrap()
                                     .send(RenderResult::Finish {
                                         data: render_context_2_d.data().iter().copied().collect(),
+                                        width: (width * scale_factor).round() as usize,
+                                        // a frame without start is handled as whole frame
+                                        damage: damage
+                                            .replace(vec![])
+                                            .filter(|damage| !damage.is_empty()),
                                     })
                                     .expect("Could not send render result to main thread.");
                                 finish_sender
@@ -385,6 +572,7 @@ pub struct RenderContext2D {
     finish_receiver: mpsc::Receiver<bool>,
     tasks: Vec<RenderTask>,
     measure_context: platform::RenderContext2D,
+    scale_factor: f64,
 }
 
 impl Drop for RenderContext2D {
@@ -419,6 +607,7 @@ impl RenderContext2D {
             finish_receiver,
             tasks: vec![],
             measure_context: platform::RenderContext2D::new(width, height),
+            scale_factor: 1.0,
         }
     }
 
@@ -449,6 +638,16 @@ impl RenderContext2D {
             .expect("Could not send start to render thread.");
     }
 
+    /// Starts a new render pipeline that only repaints the given region. The pixels outside of
+    /// the region are kept from the last frame.
+    pub fn start_region(&mut self, region: Rectangle) {
+        // the tasks of a previous region of the same frame are drawn first
+        self.send_tasks();
+        self.sender
+            .send(vec![RenderTask::StartRegion(region)])
+            .expect("Could not send start region to render thread.");
+    }
+
     /// Finishes the current render pipeline.
     pub fn finish(&mut self) {
         self.tasks.push(RenderTask::Finish());
@@ -462,6 +661,20 @@ impl RenderContext2D {
             .expect("Could not send resize to render thread.");
     }
 
+    /// Sets the number of physical pixels per logical pixel. Drawing is given in logical pixels,
+    /// the pixel buffer has the physical size. Text is measured in logical pixels.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+        self.sender
+            .send(vec![RenderTask::SetScaleFactor(scale_factor)])
+            .expect("Could not send set scale factor to render thread.");
+    }
+
+    /// Gets the number of physical pixels per logical pixel.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
     /// Registers a new font file.
     pub fn register_font(&mut self, family: &str, font_file: &'static [u8]) {
         self.measure_context.register_font(family, font_file);
@@ -473,6 +686,27 @@ impl RenderContext2D {
             .expect("Could not send register font to render thread.");
     }
 
+    /// Loads and registers a font file from disk. The font could be selected by the family name
+    /// stored in the file.
+    pub fn register_font_file(&mut self, path: &str) -> Result<(), String> {
+        self.measure_context.register_font_file(path)?;
+        self.sender
+            .send(vec![RenderTask::RegisterFontFile {
+                path: path.to_string(),
+            }])
+            .expect("Could not send register font file to render thread.");
+        Ok(())
+    }
+
+    /// Sets the font families that are used in the given order for glyphs that are missing in
+    /// the selected font, e.g. for CJK characters or emojis.
+    pub fn set_font_fallbacks(&mut self, families: Vec<String>) {
+        self.measure_context.set_font_fallbacks(families.clone());
+        self.sender
+            .send(vec![RenderTask::SetFontFallbacks { families }])
+            .expect("Could not send font fallbacks to render thread.");
+    }
+
     // Rectangles
 
     /// Draws a filled rectangle whose starting point is at the coordinates {x, y} with the
@@ -507,6 +741,8 @@ impl RenderContext2D {
         });
     }
 
+    /// Returns the metrics of the text with the given font. It is measured with the font weight,
+    /// style and stretch that were set last, so they have to be set before each measure.
     pub fn measure(
         &mut self,
         text: &str,
@@ -523,6 +759,20 @@ impl RenderContext2D {
         self.measure_context.measure_text(text)
     }
 
+    /// Returns the advance of each character of the text with the given font. The sum of the
+    /// advances is the width of the text. Like `measure` it uses the font weight, style and stretch
+    /// that were set last.
+    pub fn measure_advances(
+        &mut self,
+        text: &str,
+        font_size: f64,
+        family: impl Into<String>,
+    ) -> Vec<f64> {
+        self.measure_context.set_font_family(family);
+        self.measure_context.set_font_size(font_size);
+        self.measure_context.measure_advances(text)
+    }
+
     /// Fills the current or given path with the current file style.
     pub fn fill(&mut self) {
         self.tasks.push(RenderTask::Fill());
@@ -533,6 +783,24 @@ impl RenderContext2D {
         self.tasks.push(RenderTask::Stroke());
     }
 
+    /// Fills the current path with the given color blurred by the blur radius, e.g. to draw the
+    /// shadow of a shape. The fill style is not changed.
+    pub fn fill_shadow(&mut self, color: Color, blur: f64) {
+        self.tasks.push(RenderTask::FillShadow { color, blur });
+    }
+
+    /// Blurs the pixels of the given rectangle that are already drawn, e.g. for a frosted glass
+    /// effect behind a widget. Only the pixels inside of the current clip are changed.
+    pub fn blur_rect(&mut self, x: f64, y: f64, width: f64, height: f64, radius: f64) {
+        self.tasks.push(RenderTask::BlurRect {
+            x,
+            y,
+            width,
+            height,
+            radius,
+        });
+    }
+
     /// Starts a new path by emptying the list of sub-paths. Call this when you want to create a new path.
     pub fn begin_path(&mut self) {
         self.send_tasks();
@@ -635,6 +903,28 @@ impl RenderContext2D {
             .expect("Could not send clipped image to render thread.");
     }
 
+    /// Draws the given part of the image scaled to the given size.
+    pub fn draw_image_with_clip_and_size(
+        &mut self,
+        image: &mut Image,
+        clip: Rectangle,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) {
+        self.sender
+            .send(vec![RenderTask::DrawImageWithClipAndSize {
+                image: image.clone(),
+                clip,
+                x,
+                y,
+                width,
+                height,
+            }])
+            .expect("Could not send scaled image to render thread.");
+    }
+
     pub fn draw_pipeline(
         &mut self,
         x: f64,
@@ -667,6 +957,40 @@ impl RenderContext2D {
         self.tasks.push(RenderTask::SetLineWidth { line_width });
     }
 
+    /// Sets the dash pattern of lines as list of alternating line and gap lengths. An empty list
+    /// draws solid lines.
+    pub fn set_line_dash(&mut self, segments: &[f64]) {
+        self.tasks.push(RenderTask::SetLineDash {
+            segments: segments.to_vec(),
+        });
+    }
+
+    /// Sets the offset at which the dash pattern of lines starts.
+    pub fn set_line_dash_offset(&mut self, offset: f64) {
+        self.tasks.push(RenderTask::SetLineDashOffset { offset });
+    }
+
+    /// Sets how the end points of lines are drawn.
+    pub fn set_line_cap(&mut self, line_cap: LineCap) {
+        self.tasks.push(RenderTask::SetLineCap { line_cap });
+    }
+
+    /// Sets how two connected segments of a line are joined.
+    pub fn set_line_join(&mut self, line_join: LineJoin) {
+        self.tasks.push(RenderTask::SetLineJoin { line_join });
+    }
+
+    /// Sets which areas inside of a path are filled by `fill`.
+    pub fn set_fill_rule(&mut self, fill_rule: FillRule) {
+        self.tasks.push(RenderTask::SetFillRule { fill_rule });
+    }
+
+    /// Sets the limit of the ratio between the miter length and the line width. Longer miters are
+    /// drawn beveled.
+    pub fn set_miter_limit(&mut self, miter_limit: f64) {
+        self.tasks.push(RenderTask::SetMiterLimit { miter_limit });
+    }
+
     /// Sets the alpha value,
     pub fn set_alpha(&mut self, alpha: f32) {
         self.tasks.push(RenderTask::SetAlpha { alpha });
@@ -683,6 +1007,24 @@ impl RenderContext2D {
         self.tasks.push(RenderTask::SetFontSize { size });
     }
 
+    /// Specifies the font weight.
+    pub fn set_font_weight(&mut self, weight: FontWeight) {
+        self.measure_context.set_font_weight(weight);
+        self.tasks.push(RenderTask::SetFontWeight { weight });
+    }
+
+    /// Specifies the font style.
+    pub fn set_font_style(&mut self, style: FontStyle) {
+        self.measure_context.set_font_style(style);
+        self.tasks.push(RenderTask::SetFontStyle { style });
+    }
+
+    /// Specifies the font stretch.
+    pub fn set_font_stretch(&mut self, stretch: FontStretch) {
+        self.measure_context.set_font_stretch(stretch);
+        self.tasks.push(RenderTask::SetFontStretch { stretch });
+    }
+
     // Fill and stroke style
 
     /// Specifies the fill color to use inside shapes.
@@ -717,6 +1059,21 @@ impl RenderContext2D {
         });
     }
 
+    /// Adds a translation to the current transformation.
+    pub fn translate(&mut self, x: f64, y: f64) {
+        self.tasks.push(RenderTask::Translate { x, y });
+    }
+
+    /// Adds a scaling to the current transformation.
+    pub fn scale(&mut self, x: f64, y: f64) {
+        self.tasks.push(RenderTask::Scale { x, y });
+    }
+
+    /// Adds a clockwise rotation by the given angle in radians to the current transformation.
+    pub fn rotate(&mut self, angle: f64) {
+        self.tasks.push(RenderTask::Rotate { angle });
+    }
+
     // Canvas states
 
     /// Saves the entire state of the canvas by pushing the current state onto a stack.
@@ -736,7 +1093,7 @@ impl RenderContext2D {
     }
 
     pub fn data(&mut self) -> Option<&[u32]> {
-        if let Ok(RenderResult::Finish { data }) = self.result_receiver.try_recv() {
+        if let Ok(RenderResult::Finish { data, .. }) = self.result_receiver.try_recv() {
             self.output = data;
             Some(&self.output)
         } else {
@@ -744,6 +1101,49 @@ impl RenderContext2D {
         }
     }
 
+    /// Copies the regions that are repainted by the finished frames into the given frame buffer,
+    /// the other pixels of the buffer are kept. The whole frame is copied if it is repainted or
+    /// the size of the buffer is changed. Returns `true` if a frame is finished since the last
+    /// call.
+    pub fn present(&mut self, frame: &mut Vec<u32>) -> bool {
+        let mut presented = false;
+
+        while let Ok(RenderResult::Finish {
+            data,
+            width,
+            damage,
+        }) = self.result_receiver.try_recv()
+        {
+            match damage {
+                Some(damage) if width > 0 && frame.len() == data.len() => {
+                    let height = data.len() / width;
+
+                    for region in damage {
+                        let left = (region.x.max(0.0) as usize).min(width);
+                        let right = ((region.x + region.width).max(0.0) as usize).min(width);
+                        let top = (region.y.max(0.0) as usize).min(height);
+                        let bottom = ((region.y + region.height).max(0.0) as usize).min(height);
+
+                        for y in top..bottom {
+                            let row = y * width;
+                            frame[row + left..row + right]
+                                .copy_from_slice(&data[row + left..row + right]);
+                        }
+                    }
+                }
+                _ => {
+                    frame.clear();
+                    frame.extend_from_slice(&data);
+                }
+            }
+
+            self.output = data;
+            presented = true;
+        }
+
+        presented
+    }
+
     pub fn data_mut(&mut self) -> &mut [u32] {
         &mut self.output
     }
@@ -756,3 +1156,49 @@ impl RenderContext2D {
         unsafe { std::slice::from_raw_parts_mut(p as *mut u8, len * std::mem::size_of::<u32>()) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Renders a frame and waits until it is finished.
+    fn render(ctx: &mut RenderContext2D, region: Option<Rectangle>, color: &str) {
+        match region {
+            Some(region) => ctx.start_region(region),
+            None => ctx.start(),
+        }
+        ctx.set_fill_style(Brush::from(color));
+        ctx.fill_rect(0.0, 0.0, 8.0, 8.0);
+        ctx.finish();
+        ctx.finish_receiver().recv().unwrap();
+    }
+
+    #[test]
+    fn test_present_regions() {
+        let mut ctx = RenderContext2D::new(8.0, 8.0);
+        ctx.set_scale_factor(2.0);
+        let mut frame = vec![];
+
+        render(&mut ctx, None, "#ff0000");
+        assert!(ctx.present(&mut frame));
+        assert_eq!(frame.len(), 256);
+        assert!(frame.iter().all(|pixel| *pixel == 0xffff_0000));
+
+        // pixels outside of the repainted region are not copied
+        frame[0] = 0;
+        render(
+            &mut ctx,
+            Some(Rectangle::new(2.0, 2.0, 1.5, 1.0)),
+            "#0000ff",
+        );
+        assert!(ctx.present(&mut frame));
+        assert!(!ctx.present(&mut frame));
+
+        let pixel = |x: usize, y: usize| frame[y * 16 + x];
+        assert_eq!(pixel(0, 0), 0);
+        assert_eq!(pixel(4, 4), 0xff00_00ff);
+        assert_eq!(pixel(6, 5), 0xff00_00ff);
+        assert_eq!(pixel(7, 4), 0xffff_0000);
+        assert_eq!(pixel(4, 6), 0xffff_0000);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::utils::*;
+
+/// Describes the properties of a registered font face that are used to select it.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct FontFace {
+    /// Names the face could be selected by, e.g. the name it is registered with and the family
+    /// name stored in the font file.
+    pub families: Vec<String>,
+    pub weight: FontWeight,
+    pub style: FontStyle,
+    pub stretch: FontStretch,
+}
+
+impl FontFace {
+    /// Checks if the face could be selected by the given family name, ignoring the case.
+    pub fn has_family(&self, family: &str) -> bool {
+        self.families.iter().any(|f| f.eq_ignore_ascii_case(family))
+    }
+}
+
+/// Stores the registered fonts of a render context and selects them by family, weight, style
+/// and stretch. Glyphs that are missing in the selected font are taken from the fallback fonts.
+#[derive(Clone, Debug)]
+pub struct FontDatabase<F> {
+    faces: Vec<FontFace>,
+    fonts: Vec<F>,
+    fallbacks: Vec<String>,
+}
+
+impl<F> Default for FontDatabase<F> {
+    fn default() -> Self {
+        FontDatabase {
+            faces: vec![],
+            fonts: vec![],
+            fallbacks: vec![],
+        }
+    }
+}
+
+impl<F> FontDatabase<F> {
+    /// Creates a new empty font database.
+    pub fn new() -> Self {
+        FontDatabase::default()
+    }
+
+    /// Registers a font with the properties of its face.
+    pub fn register(&mut self, face: FontFace, font: F) {
+        self.faces.push(face);
+        self.fonts.push(font);
+    }
+
+    /// Checks if a font of the given family is registered.
+    pub fn contains_family(&self, family: &str) -> bool {
+        self.faces.iter().any(|face| face.has_family(family))
+    }
+
+    /// Sets the families that are used in the given order for glyphs that are missing in the
+    /// selected font.
+    pub fn set_fallbacks(&mut self, families: Vec<String>) {
+        self.fallbacks = families;
+    }
+
+    /// Gets the font that matches the given properties best followed by the best matching fonts
+    /// of the fallback families.
+    pub fn select(
+        &self,
+        family: &str,
+        weight: FontWeight,
+        style: FontStyle,
+        stretch: FontStretch,
+    ) -> Vec<&F> {
+        let mut selected: Vec<usize> = vec![];
+
+        for family in std::iter::once(family).chain(self.fallbacks.iter().map(|f| f.as_str())) {
+            if let Some(index) = match_font_face(&self.faces, family, weight, style, stretch) {
+                if !selected.contains(&index) {
+                    selected.push(index);
+                }
+            }
+        }
+
+        selected.iter().map(|i| &self.fonts[*i]).collect()
+    }
+}
+
+/// Selects the face of the given family that matches the weight, style and stretch best, like
+/// the css font matching algorithm. Returns the index of the face.
+pub fn match_font_face(
+    faces: &[FontFace],
+    family: &str,
+    weight: FontWeight,
+    style: FontStyle,
+    stretch: FontStretch,
+) -> Option<usize> {
+    let candidates: Vec<usize> = (0..faces.len())
+        .filter(|i| faces[*i].has_family(family))
+        .collect();
+
+    // narrow down by stretch, then by style and at last by weight
+    let best_stretch = candidates
+        .iter()
+        .map(|i| faces[*i].stretch)
+        .min_by_key(|s| stretch_rank(stretch, *s))?;
+
+    let candidates: Vec<usize> = candidates
+        .into_iter()
+        .filter(|i| faces[*i].stretch == best_stretch)
+        .collect();
+
+    let best_style = candidates
+        .iter()
+        .map(|i| faces[*i].style)
+        .min_by_key(|s| style_rank(style, *s))?;
+
+    candidates
+        .into_iter()
+        .filter(|i| faces[*i].style == best_style)
+        .min_by_key(|i| weight_rank(weight, faces[*i].weight))
+}
+
+// Narrower widths are preferred for condensed and normal stretches, wider ones for expanded.
+fn stretch_rank(desired: FontStretch, stretch: FontStretch) -> (u8, u16) {
+    let (desired, width) = (desired.width_class(), stretch.width_class());
+
+    if width == desired {
+        (0, 0)
+    } else if desired <= FontStretch::Normal.width_class() {
+        if width < desired {
+            (1, desired - width)
+        } else {
+            (2, width - desired)
+        }
+    } else if width > desired {
+        (1, width - desired)
+    } else {
+        (2, desired - width)
+    }
+}
+
+fn style_rank(desired: FontStyle, style: FontStyle) -> u8 {
+    let order = match desired {
+        FontStyle::Italic => [FontStyle::Italic, FontStyle::Oblique, FontStyle::Normal],
+        FontStyle::Oblique => [FontStyle::Oblique, FontStyle::Italic, FontStyle::Normal],
+        FontStyle::Normal => [FontStyle::Normal, FontStyle::Oblique, FontStyle::Italic],
+    };
+
+    order
+        .iter()
+        .position(|s| *s == style)
+        .unwrap_or(order.len()) as u8
+}
+
+// For desired weights from 400 to 500 heavier weights up to 500 are tried first, then lighter
+// ones and then the heavier ones. Lighter weights are preferred below 400, heavier above 500.
+fn weight_rank(desired: FontWeight, weight: FontWeight) -> (u8, u16) {
+    let (desired, weight) = (desired.0, weight.0);
+
+    if weight == desired {
+        (0, 0)
+    } else if (400..=500).contains(&desired) {
+        if weight > desired && weight <= 500 {
+            (1, weight - desired)
+        } else if weight < desired {
+            (2, desired - weight)
+        } else {
+            (3, weight - desired)
+        }
+    } else if desired < 400 {
+        if weight < desired {
+            (1, desired - weight)
+        } else {
+            (2, weight - desired)
+        }
+    } else if weight > desired {
+        (1, weight - desired)
+    } else {
+        (2, desired - weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn face(family: &str, weight: u16, style: FontStyle) -> FontFace {
+        FontFace {
+            families: vec![family.to_string()],
+            weight: FontWeight(weight),
+            style,
+            stretch: FontStretch::Normal,
+        }
+    }
+
+    #[test]
+    fn test_match_font_face() {
+        let faces = vec![
+            face("Roboto", 400, FontStyle::Normal),
+            face("Roboto", 500, FontStyle::Normal),
+            face("Roboto", 700, FontStyle::Normal),
+            face("Roboto", 400, FontStyle::Italic),
+            face("Material Icons", 400, FontStyle::Normal),
+        ];
+
+        let find = |weight: u16, style: FontStyle| {
+            match_font_face(
+                &faces,
+                "roboto",
+                FontWeight(weight),
+                style,
+                FontStretch::Normal,
+            )
+        };
+
+        assert_eq!(find(400, FontStyle::Normal), Some(0));
+        assert_eq!(find(450, FontStyle::Normal), Some(1));
+        assert_eq!(find(600, FontStyle::Normal), Some(2));
+        assert_eq!(find(300, FontStyle::Normal), Some(0));
+        assert_eq!(find(900, FontStyle::Normal), Some(2));
+        assert_eq!(find(700, FontStyle::Italic), Some(3));
+        assert_eq!(find(400, FontStyle::Oblique), Some(3));
+        assert_eq!(
+            match_font_face(
+                &faces,
+                "Noto Sans",
+                FontWeight::NORMAL,
+                FontStyle::Normal,
+                FontStretch::Normal
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_select_with_fallbacks() {
+        let mut database = FontDatabase::new();
+        database.register(face("Roboto", 400, FontStyle::Normal), "roboto");
+        database.register(face("Roboto", 700, FontStyle::Normal), "roboto bold");
+        database.register(face("Noto Sans CJK", 400, FontStyle::Normal), "noto");
+        database.set_fallbacks(vec!["Noto Sans CJK".to_string(), "Roboto".to_string()]);
+
+        assert!(database.contains_family("Roboto"));
+        assert_eq!(
+            database.select(
+                "Roboto",
+                FontWeight::BOLD,
+                FontStyle::Normal,
+                FontStretch::Normal
+            ),
+            vec![&"roboto bold", &"noto"]
+        );
+        assert_eq!(
+            database.select(
+                "Unknown",
+                FontWeight::NORMAL,
+                FontStyle::Normal,
+                FontStretch::Normal
+            ),
+            vec![&"noto", &"roboto"]
+        );
+    }
+}
@@ -42,10 +42,30 @@ pub mod platform;
 #[cfg(target_arch = "wasm32")]
 pub use platform::RenderContext2D;
 
+pub use self::font_database::*;
 pub use self::render_target::*;
 
+mod font_database;
 mod render_target;
 
+pub use self::animated_image::*;
+pub use self::blur::*;
+pub use self::svg::*;
+pub use self::text_layout::*;
+pub use self::transform::*;
+
+mod animated_image;
+mod blur;
+mod svg;
+mod text_layout;
+mod transform;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::snapshot::*;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod snapshot;
+
 /// Defines the current configuration of the render ctx.
 #[derive(Debug, Clone)]
 pub struct RenderConfig {
@@ -54,6 +74,12 @@ pub struct RenderConfig {
     pub line_width: f64,
     pub font_config: FontConfig,
     pub alpha: f32,
+    pub line_dash: Vec<f64>,
+    pub line_dash_offset: f64,
+    pub line_cap: LineCap,
+    pub line_join: LineJoin,
+    pub miter_limit: f64,
+    pub fill_rule: FillRule,
 }
 
 impl Default for RenderConfig {
@@ -64,10 +90,88 @@ impl Default for RenderConfig {
             line_width: 1.,
             font_config: FontConfig::default(),
             alpha: 1.,
+            line_dash: vec![],
+            line_dash_offset: 0.,
+            line_cap: LineCap::default(),
+            line_join: LineJoin::default(),
+            miter_limit: 10.,
+            fill_rule: FillRule::default(),
         }
     }
 }
 
+/// Describes how the end points of lines are drawn.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LineCap {
+    /// The lines end squared off at the end points.
+    Butt,
+
+    /// The lines end with a half circle.
+    Round,
+
+    /// The lines end with a half square.
+    Square,
+}
+
+impl Default for LineCap {
+    fn default() -> Self {
+        LineCap::Butt
+    }
+}
+
+/// Describes how two connected segments of a line are joined.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LineJoin {
+    /// The outer edges of the segments are extended until they meet, limited by the miter limit.
+    Miter,
+
+    /// The corner is rounded.
+    Round,
+
+    /// The corner is filled with a triangle.
+    Bevel,
+}
+
+impl Default for LineJoin {
+    fn default() -> Self {
+        LineJoin::Miter
+    }
+}
+
+/// Describes which areas inside of a path are filled.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum FillRule {
+    /// Areas are filled if the path winds around them in total, e.g. if two overlapping shapes are
+    /// drawn in the same direction.
+    NonZero,
+
+    /// Areas are filled if a line from them to the outside crosses the path an odd number of
+    /// times, so overlapping parts are cut out.
+    EvenOdd,
+}
+
+impl Default for FillRule {
+    fn default() -> Self {
+        FillRule::NonZero
+    }
+}
+
+/// Gets the dash pattern like the canvas `setLineDash`. A list with an odd number of entries is
+/// repeated once. Returns `None` if the list contains negative or non finite values.
+pub(crate) fn line_dash(segments: &[f64]) -> Option<Vec<f64>> {
+    if segments.iter().any(|s| !s.is_finite() || *s < 0.) {
+        return None;
+    }
+
+    let mut line_dash = segments.to_vec();
+
+    if line_dash.len() % 2 == 1 {
+        line_dash.extend_from_slice(segments);
+    }
+
+    Some(line_dash)
+}
+
 /// The TextMetrics struct represents the dimension of a text.
 #[derive(Clone, Copy, Default, Debug)]
 pub struct TextMetrics {
@@ -80,11 +184,21 @@ pub struct TextMetrics {
 pub struct FontConfig {
     pub family: String,
     pub font_size: f64,
+    pub weight: utils::FontWeight,
+    pub style: utils::FontStyle,
+    pub stretch: utils::FontStretch,
 }
 
 impl ToString for FontConfig {
     fn to_string(&self) -> String {
-        format!("{}px {}", self.font_size, self.family)
+        format!(
+            "{} {} {} {}px {}",
+            self.style.to_string(),
+            self.weight.0,
+            self.stretch.keyword(),
+            self.font_size,
+            self.family
+        )
     }
 }
 
@@ -127,3 +241,45 @@ impl fmt::Debug for Box<dyn Pipeline> {
         write!(f, "Box<dyn Pipeline>")
     }
 }
+
+pub trait RenderPipeline2D {
+    /// Draws the pipeline with the 2D api of the render context. The origin of the render context
+    /// is the top left corner of the canvas and drawing is clipped to the given size.
+    fn draw(&self, render_context: &mut RenderContext2D, width: f64, height: f64);
+}
+
+/// Used to implement a custom render pipeline that draws with the 2D api of `RenderContext2D`,
+/// e.g. charts or gauges.
+pub trait Pipeline2D: RenderPipeline2D + Any + Send {
+    /// Equality for two Pipeline2D objects.
+    fn box_eq(&self, other: &dyn Any) -> bool;
+
+    /// Converts self to an any reference.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Clones self as box.
+    fn clone_box(&self) -> Box<dyn Pipeline2D>;
+
+    /// Draws the ctx of the pipeline.
+    fn draw_pipeline(&self, render_context: &mut RenderContext2D, width: f64, height: f64) {
+        self.draw(render_context, width, height);
+    }
+}
+
+impl PartialEq for Box<dyn Pipeline2D> {
+    fn eq(&self, other: &Box<dyn Pipeline2D>) -> bool {
+        self.box_eq(other.as_any())
+    }
+}
+
+impl Clone for Box<dyn Pipeline2D> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl fmt::Debug for Box<dyn Pipeline2D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Box<dyn Pipeline2D>")
+    }
+}
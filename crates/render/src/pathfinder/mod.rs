@@ -1,11 +1,16 @@
-use crate::{utils::*, Pipeline, RenderConfig, RenderTarget, TextMetrics};
+use crate::{
+    line_dash, utils::*, FillRule, LineCap, LineJoin, Pipeline, RenderConfig, RenderTarget,
+    TextMetrics,
+};
 
 use pathfinder_canvas::{
-    ArcDirection, Canvas, CanvasFontContext, CanvasRenderingContext2D, FillRule, FillStyle, Path2D,
-    RectF,
+    ArcDirection, Canvas, CanvasFontContext, CanvasRenderingContext2D, FillStyle, Path2D, RectF,
 };
 use pathfinder_color::{ColorF, ColorU};
-use pathfinder_geometry::vector::{vec2f, vec2i, Vector2F, Vector2I};
+use pathfinder_geometry::{
+    transform2d::Transform2F,
+    vector::{vec2f, vec2i, Vector2F, Vector2I},
+};
 use pathfinder_gl::{GLDevice, GLVersion};
 use pathfinder_renderer::concurrent::rayon::RayonExecutor;
 use pathfinder_renderer::concurrent::scene_proxy::SceneProxy;
@@ -18,6 +23,22 @@ use pathfinder_resources::fs::FilesystemResourceLoader;
 pub struct Image {}
 
 impl Image {
+    pub fn is_loading(&self) -> bool {
+        false
+    }
+
+    pub fn is_decoded(&self) -> bool {
+        false
+    }
+
+    pub fn poll(&mut self) -> bool {
+        false
+    }
+
+    pub fn on_decoded<F: FnOnce() + Send + 'static>(&self, on_decoded: F) {
+        on_decoded();
+    }
+
     pub fn width(&self) -> f64 {
         0.0
     }
@@ -46,6 +67,9 @@ pub struct RenderContext2D {
     size: (f64, f64),
     config: RenderConfig,
     saved_config: Option<RenderConfig>,
+
+    // physical pixels per logical pixel, all drawing is given in logical pixels
+    scale_factor: f64,
 }
 
 impl RenderContext2D {
@@ -60,6 +84,7 @@ impl RenderContext2D {
             size: (width, height),
             config: RenderConfig::default(),
             saved_config: None,
+            scale_factor: 1.0,
         }
     }
 
@@ -97,29 +122,69 @@ impl RenderContext2D {
             size,
             config: RenderConfig::default(),
             saved_config: None,
+            scale_factor: 1.0,
         }
     }
 
+    /// Resizes the render context to the given logical size.
     pub fn resize(&mut self, width: f64, height: f64) {
+        self.size = (width, height);
+
         if let Some(renderer) = &mut self.renderer {
             renderer.replace_dest_framebuffer(DestFramebuffer::full_window(vec2i(
-                width as i32,
-                height as i32,
+                (width * self.scale_factor).round() as i32,
+                (height * self.scale_factor).round() as i32,
             )));
         }
 
-        if let Some(font_context) = &self.font_context {
-            self.canvas.clear();
-            self.canvas.push(
-                Canvas::new(Vector2F::new(width as f32, height as f32))
-                    .get_context_2d(font_context.clone()),
-            )
+        self.canvas.clear();
+        if let Some(canvas) = self.new_canvas() {
+            self.canvas.push(canvas);
         }
     }
 
+    /// Sets the number of physical pixels per logical pixel. Drawing is given in logical pixels,
+    /// the frame buffer has the physical size.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        if scale_factor <= 0.0 {
+            return;
+        }
+
+        self.scale_factor = scale_factor;
+        self.resize(self.size.0, self.size.1);
+    }
+
+    /// Gets the number of physical pixels per logical pixel.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    // Creates a canvas with the physical size that is scaled from logical pixels.
+    fn new_canvas(&self) -> Option<CanvasRenderingContext2D> {
+        let font_context = self.font_context.as_ref()?;
+        let scale_factor = self.scale_factor as f32;
+
+        let mut canvas = Canvas::new(Vector2F::new(
+            self.size.0 as f32 * scale_factor,
+            self.size.1 as f32 * scale_factor,
+        ))
+        .get_context_2d(font_context.clone());
+        canvas.set_transform(&Transform2F::from_scale(vec2f(scale_factor, scale_factor)));
+
+        Some(canvas)
+    }
+
     /// Registers a new font file.
     pub fn register_font(&mut self, family: &str, font_file: &'static [u8]) {}
 
+    /// Loads and registers a font file from disk.
+    pub fn register_font_file(&mut self, path: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Sets the font families that are used for glyphs that are missing in the selected font.
+    pub fn set_font_fallbacks(&mut self, families: Vec<String>) {}
+
     // Rectangles
 
     /// Draws a filled rectangle whose starting point is at the coordinates {x, y} with the specified width and height and whose style is determined by the fillStyle attribute.
@@ -158,6 +223,18 @@ impl RenderContext2D {
         }
     }
 
+    /// Returns the advance of each character of the text with the given font.
+    pub fn measure_advances(
+        &mut self,
+        text: &str,
+        font_size: f64,
+        family: impl Into<String>,
+    ) -> Vec<f64> {
+        text.chars()
+            .map(|c| self.canvas().measure_text(&c.to_string()).width as f64)
+            .collect()
+    }
+
     /// Returns a TextMetrics object.
     pub fn measure_text(&mut self, text: &str) -> TextMetrics {
         let t_m = self.canvas().measure_text(text);
@@ -170,7 +247,11 @@ impl RenderContext2D {
     /// Fills the current or given path with the current file style.
     pub fn fill(&mut self) {
         let path = self.path.clone();
-        self.canvas().fill_path(path, FillRule::Winding);
+        let fill_rule = match self.config.fill_rule {
+            FillRule::NonZero => pathfinder_canvas::FillRule::Winding,
+            FillRule::EvenOdd => pathfinder_canvas::FillRule::EvenOdd,
+        };
+        self.canvas().fill_path(path, fill_rule);
     }
 
     /// Strokes {outlines} the current or given path with the current stroke style.
@@ -179,6 +260,14 @@ impl RenderContext2D {
         self.canvas().stroke_path(path);
     }
 
+    /// Fills the current path with the given color blurred by the blur radius, e.g. to draw the
+    /// shadow of a shape. The fill style is not changed.
+    pub fn fill_shadow(&mut self, color: Color, blur: f64) {}
+
+    /// Blurs the pixels of the given rectangle that are already drawn, e.g. for a frosted glass
+    /// effect behind a widget. Only the pixels inside of the current clip are changed.
+    pub fn blur_rect(&mut self, x: f64, y: f64, width: f64, height: f64, radius: f64) {}
+
     /// Starts a new path by emptying the list of sub-paths. Call this when you want to create a new path.
     pub fn begin_path(&mut self) {
         self.path = Path2D::new();
@@ -245,6 +334,18 @@ impl RenderContext2D {
     /// Draws the given part of the image.
     pub fn draw_image_with_clip(&mut self, image: &Image, clip: Rectangle, x: f64, y: f64) {}
 
+    /// Draws the given part of the image scaled to the given size.
+    pub fn draw_image_with_clip_and_size(
+        &mut self,
+        image: &Image,
+        clip: Rectangle,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) {
+    }
+
     pub fn draw_pipeline(
         &mut self,
         x: f64,
@@ -268,6 +369,51 @@ impl RenderContext2D {
         self.canvas().set_line_width(line_width as f32);
     }
 
+    /// Sets the dash pattern of lines as list of alternating line and gap lengths. An empty list
+    /// draws solid lines.
+    pub fn set_line_dash(&mut self, segments: &[f64]) {
+        if let Some(line_dash) = line_dash(segments) {
+            self.canvas()
+                .set_line_dash(line_dash.iter().map(|d| *d as f32).collect());
+        }
+    }
+
+    /// Sets the offset at which the dash pattern of lines starts.
+    pub fn set_line_dash_offset(&mut self, offset: f64) {
+        self.canvas().set_line_dash_offset(offset as f32);
+    }
+
+    /// Sets how the end points of lines are drawn.
+    pub fn set_line_cap(&mut self, line_cap: LineCap) {
+        self.canvas().set_line_cap(match line_cap {
+            LineCap::Butt => pathfinder_canvas::LineCap::Butt,
+            LineCap::Round => pathfinder_canvas::LineCap::Round,
+            LineCap::Square => pathfinder_canvas::LineCap::Square,
+        });
+    }
+
+    /// Sets how two connected segments of a line are joined.
+    pub fn set_line_join(&mut self, line_join: LineJoin) {
+        self.canvas().set_line_join(match line_join {
+            LineJoin::Miter => pathfinder_canvas::LineJoin::Miter,
+            LineJoin::Round => pathfinder_canvas::LineJoin::Round,
+            LineJoin::Bevel => pathfinder_canvas::LineJoin::Bevel,
+        });
+    }
+
+    /// Sets which areas inside of a path are filled by `fill`.
+    pub fn set_fill_rule(&mut self, fill_rule: FillRule) {
+        self.config.fill_rule = fill_rule;
+    }
+
+    /// Sets the limit of the ratio between the miter length and the line width. Longer miters are
+    /// drawn beveled.
+    pub fn set_miter_limit(&mut self, miter_limit: f64) {
+        if miter_limit > 0.0 {
+            self.canvas().set_miter_limit(miter_limit as f32);
+        }
+    }
+
     /// Sets the alpha value,
     pub fn set_alpha(&mut self, alpha: f32) {
         self.canvas().set_global_alpha(alpha as f32);
@@ -281,6 +427,15 @@ impl RenderContext2D {
         self.canvas().set_font_size(size as f32);
     }
 
+    /// Specifies the font weight.
+    pub fn set_font_weight(&mut self, weight: FontWeight) {}
+
+    /// Specifies the font style.
+    pub fn set_font_style(&mut self, style: FontStyle) {}
+
+    /// Specifies the font stretch.
+    pub fn set_font_stretch(&mut self, stretch: FontStretch) {}
+
     // Fill and stroke styley
 
     /// Specifies the fill color to use inside shapes.
@@ -297,7 +452,7 @@ impl RenderContext2D {
                         color.a(),
                     )))
             }
-            Brush::LinearGradient { start, end, stops } => {}
+            _ => {}
         }
     }
 
@@ -315,7 +470,7 @@ impl RenderContext2D {
                         color.a(),
                     )))
             }
-            Brush::LinearGradient { start, end, stops } => {}
+            _ => {}
         }
     }
 
@@ -361,14 +516,16 @@ impl RenderContext2D {
     pub fn start(&mut self) {
         self.canvas.clear();
         self.path = Path2D::new();
-        if let Some(font_context) = &self.font_context {
-            self.canvas.push(
-                Canvas::new(Vector2F::new(self.size.0 as f32, self.size.1 as f32))
-                    .get_context_2d(font_context.clone()),
-            )
+        if let Some(canvas) = self.new_canvas() {
+            self.canvas.push(canvas);
         }
     }
 
+    /// Starts a new frame. The scene is always rendered completely, so the region is ignored.
+    pub fn start_region(&mut self, region: Rectangle) {
+        self.start();
+    }
+
     pub fn finish(&mut self) {
         let canvas = self.canvas.pop().unwrap();
 
@@ -379,11 +536,8 @@ impl RenderContext2D {
             }
         }
 
-        if let Some(font_context) = &self.font_context {
-            self.canvas.push(
-                Canvas::new(Vector2F::new(self.size.0 as f32, self.size.1 as f32))
-                    .get_context_2d(font_context.clone()),
-            )
+        if let Some(canvas) = self.new_canvas() {
+            self.canvas.push(canvas);
         }
     }
 }
@@ -1,39 +1,164 @@
+use std::{
+    fmt, fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use raqote;
 use rusttype;
+use rustybuzz;
+use ttf_parser;
 
-use crate::utils::{Color, Rectangle};
+use crate::{
+    utils::{Color, FontStretch, FontStyle, FontWeight, Rectangle},
+    FontFace,
+};
 
-#[derive(Debug, Clone)]
+use super::{
+    glyph_cache::{GlyphCache, GlyphKey, RasterizedGlyph, TextKey},
+    shaping::{shape_text, ShapedText, ShapingFont},
+};
+
+// Source of the ids that identify the glyphs of a font in the glyph cache.
+static NEXT_FONT_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone)]
 pub struct Font {
+    id: usize,
     inner: rusttype::Font<'static>,
+
+    // the faces are parsed once when the font is loaded, they are used to shape texts
+    shaping_face: Arc<rustybuzz::Face<'static>>,
+    ttf_face: ttf_parser::Face<'static>,
 }
 
 impl Font {
     pub fn from_bytes(bytes: &'static [u8]) -> Result<Self, &'static str> {
-        rusttype::Font::try_from_bytes(bytes)
-            .map(|font| Font { inner: font })
-            .ok_or("Could not load font from bytes")
+        Font::parse(bytes).ok_or("Could not load font from bytes")
     }
 
-    pub fn measure_text(&self, text: &str, size: f64) -> (f64, f64) {
-        let scale = rusttype::Scale::uniform(size as f32);
-        let v_metrics = self.inner.v_metrics(scale);
-        let offset = rusttype::point(0.0, v_metrics.ascent);
+    /// Loads a font file, e.g. a ttf or otf file. The content of the file is kept until the
+    /// application exits.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let bytes = fs::read(path.as_ref()).map_err(|e| {
+            format!(
+                "Could not load font file {}: {}",
+                path.as_ref().display(),
+                e
+            )
+        })?;
 
-        let pixel_height = size.ceil();
+        // the parsed faces borrow the data, fonts are registered for the lifetime of the
+        // application
+        Font::parse(Box::leak(bytes.into_boxed_slice()))
+            .ok_or_else(|| format!("Could not load font file {}", path.as_ref().display()))
+    }
 
-        // Glyphs to draw for "RustType". Feel free to try other strings.
-        let glyphs: Vec<rusttype::PositionedGlyph> =
-            self.inner.layout(text, scale, offset).collect();
+    fn parse(data: &'static [u8]) -> Option<Self> {
+        Some(Font {
+            id: NEXT_FONT_ID.fetch_add(1, Ordering::Relaxed),
+            inner: rusttype::Font::try_from_bytes(data)?,
+            shaping_face: Arc::new(rustybuzz::Face::from_slice(data, 0)?),
+            ttf_face: ttf_parser::Face::from_slice(data, 0).ok()?,
+        })
+    }
 
-        let width = glyphs
-            .iter()
-            .rev()
-            .map(|g| g.position().x as f32 + g.unpositioned().h_metrics().advance_width)
-            .next()
-            .unwrap_or(0.0)
-            .ceil() as f64;
+    /// Gets the font for the shaper, glyph positions are scaled to the given font size.
+    pub fn shaping_font(&self, size: f64) -> ShapingFont<'_> {
+        ShapingFont {
+            face: &self.shaping_face,
+            ttf_face: &self.ttf_face,
+            scale: self.scale_factor(size),
+        }
+    }
+
+    /// Reads the family names, the weight, the style and the stretch of the font. The given alias
+    /// is used as additional family name.
+    pub fn face(&self, alias: &str) -> FontFace {
+        let mut face = FontFace {
+            families: vec![],
+            ..Default::default()
+        };
+
+        if !alias.is_empty() {
+            face.families.push(alias.to_string());
+        }
+
+        let ttf_face = &self.ttf_face;
+
+        for name in ttf_face.names() {
+            if name.name_id() != ttf_parser::name_id::FAMILY
+                && name.name_id() != ttf_parser::name_id::TYPOGRAPHIC_FAMILY
+            {
+                continue;
+            }
+
+            if let Some(family) = name.to_string() {
+                if !face.has_family(&family) {
+                    face.families.push(family);
+                }
+            }
+        }
+
+        face.weight = FontWeight::from(ttf_face.weight().to_number());
+        face.stretch = FontStretch::from_width_class(ttf_face.width().to_number());
+        face.style = if ttf_face.is_italic() {
+            FontStyle::Italic
+        } else if ttf_face.is_oblique() {
+            FontStyle::Oblique
+        } else {
+            FontStyle::Normal
+        };
+
+        face
+    }
+
+    // Gets the factor rusttype uses to scale font units to pixels for the given font size.
+    fn scale_factor(&self, size: f64) -> f32 {
+        let v_metrics = self.inner.v_metrics_unscaled();
+        size as f32 / (v_metrics.ascent - v_metrics.descent)
+    }
+
+    // Rasterizes the glyph with the given id, moved right by `offset` pixels from its origin.
+    fn rasterize(&self, id: u16, size: f64, offset: f32) -> RasterizedGlyph {
+        let glyph = self
+            .inner
+            .glyph(rusttype::GlyphId(id))
+            .scaled(rusttype::Scale::uniform(size as f32))
+            .positioned(rusttype::point(offset, 0.0));
+
+        let bb = match glyph.pixel_bounding_box() {
+            Some(bb) => bb,
+            None => return RasterizedGlyph::default(),
+        };
 
-        (width, pixel_height)
+        let mut rasterized_glyph = RasterizedGlyph {
+            left: bb.min.x,
+            top: bb.min.y,
+            width: bb.width(),
+            height: bb.height(),
+            coverage: vec![0; (bb.width() * bb.height()) as usize],
+        };
+
+        glyph.draw(|x, y, v| {
+            let index = (y as i32 * rasterized_glyph.width + x as i32) as usize;
+            rasterized_glyph.coverage[index] = (v * 255.0).round().min(255.0) as u8;
+        });
+
+        rasterized_glyph
+    }
+
+    pub fn measure_text(&self, text: &str, size: f64) -> (f64, f64) {
+        FontChain::new(vec![self]).measure_text(&mut GlyphCache::new(), text, size)
+    }
+
+    /// Renders the coverage of the text into an alpha mask, e.g. to fill it with a gradient.
+    /// Returns the width, the height and the mask data.
+    pub fn render_text_mask(&self, text: &str, size: f64, alpha: f32) -> (i32, i32, Vec<u8>) {
+        FontChain::new(vec![self]).render_text_mask(&mut GlyphCache::new(), text, size, alpha)
     }
 
     pub fn render_text(
@@ -45,13 +170,13 @@ impl Font {
         config: (f64, Color, f32),
         position: (f64, f64),
     ) {
-        self.render_text_clipped(
+        FontChain::new(vec![self]).render_text(
+            &mut GlyphCache::new(),
             text,
             data,
             width,
             config,
             position,
-            Rectangle::new(0.0, 0.0, width, std::f64::MAX),
         );
     }
 
@@ -65,70 +190,298 @@ impl Font {
         position: (f64, f64),
         clip: Rectangle,
     ) {
-        let scale = rusttype::Scale::uniform(config.0 as f32);
+        FontChain::new(vec![self]).render_text_clipped(
+            &mut GlyphCache::new(),
+            text,
+            data,
+            width,
+            config,
+            position,
+            clip,
+        );
+    }
+}
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Font ( id: {} )", self.id)
+    }
+}
+
+/// A font followed by the fonts that are used for the glyphs it does not contain.
+#[derive(Debug, Clone)]
+pub struct FontChain<'a> {
+    fonts: Vec<&'a Font>,
+}
+
+impl<'a> FontChain<'a> {
+    /// Creates a new font chain. The first font is the primary font, the others are fallbacks.
+    pub fn new(fonts: Vec<&'a Font>) -> Self {
+        FontChain { fonts }
+    }
+
+    /// Checks if the chain contains no font.
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+
+    // Shapes the text for the given font size or takes it from the cache.
+    fn shape(&self, cache: &mut GlyphCache, text: &str, size: f64) -> Arc<ShapedText> {
+        let key = TextKey {
+            fonts: self.fonts.iter().map(|font| font.id).collect(),
+            size: size.to_bits(),
+            text: text.to_string(),
+        };
+
+        cache.text(key, || {
+            let shaping_fonts: Vec<ShapingFont> = self
+                .fonts
+                .iter()
+                .map(|font| font.shaping_font(size))
+                .collect();
+
+            shape_text(&shaping_fonts, text)
+        })
+    }
+
+    // Calls `draw` with the position relative to the top left of the text and the coverage of
+    // each pixel of the glyphs. Returns the width of the text in pixels.
+    fn draw_glyphs<F>(&self, cache: &mut GlyphCache, text: &str, size: f64, mut draw: F) -> f32
+    where
+        F: FnMut(i32, i32, f32),
+    {
+        let primary = match self.fonts.first() {
+            Some(font) => font,
+            None => return 0.0,
+        };
 
         // The origin of a line of text is at the baseline (roughly where non-descending letters sit).
         // We don't want to clip the text, so we shift it down with an offset when laying it out.
         // v_metrics.ascent is the distance between the baseline and the highest edge of any glyph in
         // the font. That's enough to guarantee that there's no clipping.
-        let v_metrics = self.inner.v_metrics(scale);
-        let offset = rusttype::point(0.0, v_metrics.ascent);
+        let ascent = primary
+            .inner
+            .v_metrics(rusttype::Scale::uniform(size as f32))
+            .ascent;
 
-        // Glyphs to draw for "RustType". Feel free to try other strings.
-        let glyphs: Vec<rusttype::PositionedGlyph> =
-            self.inner.layout(text, scale, offset).collect();
+        let shaped_text = self.shape(cache, text, size);
 
-        let pixel_width = glyphs
-            .iter()
-            .rev()
-            .map(|g| g.position().x as f32 + g.unpositioned().h_metrics().advance_width)
-            .next()
-            .unwrap_or(0.0)
-            .ceil() as i32;
+        for g in &shaped_text.glyphs {
+            let font = self.fonts[g.font];
+            let (key, x) = GlyphKey::new(font.id, size, g.id, g.x);
+            let y = (ascent + g.y).round() as i32;
+            let glyph = cache.glyph(key, || font.rasterize(g.id, size, key.offset()));
 
-        let pixel_height = config.0.ceil() as i32;
+            for row in 0..glyph.height {
+                for column in 0..glyph.width {
+                    let coverage = glyph.coverage[(row * glyph.width + column) as usize];
 
-        for g in glyphs.iter() {
-            if let Some(bb) = g.pixel_bounding_box() {
-                g.draw(|off_x, off_y, v| {
-                    let off_x = off_x as i32 + bb.min.x;
-                    let off_y = off_y as i32 + bb.min.y;
-
-                    if off_x >= 0
-                        && off_x < pixel_width
-                        && off_y >= 0
-                        && off_y < pixel_height
-                        && position.0 + off_x as f64 >= clip.x
-                        && position.0 + off_x as f64 <= clip.x + clip.width
-                        && position.1 + off_y as f64 >= clip.y
-                        && position.1 + off_y as f64 <= clip.y + clip.height
-                    {
-                        // Alpha blending from orbclient
-                        let alpha = (config.2 * v * 255.0) as u32;
-                        let new = (alpha << 24) | (config.1.data & 0x00FF_FFFF);
-
-                        let index = ((position.1 as i32 + off_y) * width as i32
-                            + position.0 as i32
-                            + off_x) as usize;
-                        if index >= data.len() {
-                            return;
-                        }
-                        let old = &mut data[index];
-                        if alpha >= 255 {
-                            *old = new;
-                        } else if alpha > 0 {
-                            let n_alpha = 255 - alpha;
-                            let rb = ((n_alpha * (*old & 0x00FF_00FF))
-                                + (alpha * (new & 0x00FF_00FF)))
-                                >> 8;
-                            let ag = (n_alpha * ((*old & 0xFF00_FF00) >> 8))
-                                + (alpha * (0x0100_0000 | ((new & 0x0000_FF00) >> 8)));
-
-                            *old = (rb & 0x00FF_00FF) | (ag & 0xFF00_FF00);
-                        }
+                    if coverage > 0 {
+                        draw(
+                            x + glyph.left + column,
+                            y + glyph.top + row,
+                            coverage as f32 / 255.0,
+                        );
                     }
-                });
+                }
             }
         }
+
+        shaped_text.advance
+    }
+
+    /// Builds the outlines of the glyphs of the text as path, e.g. to fill a rotated or scaled
+    /// text through the current transformation. The top left of the text is at the given position.
+    pub fn text_path(
+        &self,
+        cache: &mut GlyphCache,
+        text: &str,
+        size: f64,
+        position: (f64, f64),
+    ) -> raqote::Path {
+        let mut builder = raqote::PathBuilder::new();
+
+        let primary = match self.fonts.first() {
+            Some(font) => font,
+            None => return builder.finish(),
+        };
+
+        // the baseline is moved down by the ascent like the rendered glyphs
+        let ascent = primary
+            .inner
+            .v_metrics(rusttype::Scale::uniform(size as f32))
+            .ascent;
+
+        let shaped_text = self.shape(cache, text, size);
+
+        for g in &shaped_text.glyphs {
+            let font = self.fonts[g.font];
+
+            font.ttf_face.outline_glyph(
+                ttf_parser::GlyphId(g.id),
+                &mut GlyphOutline {
+                    builder: &mut builder,
+                    scale: font.scale_factor(size),
+                    x: position.0 as f32 + g.x,
+                    y: position.1 as f32 + ascent + g.y,
+                },
+            );
+        }
+
+        builder.finish()
+    }
+
+    pub fn measure_text(&self, cache: &mut GlyphCache, text: &str, size: f64) -> (f64, f64) {
+        let width = self.shape(cache, text, size).advance;
+
+        (width.ceil() as f64, size.ceil())
+    }
+
+    /// Gets the advance of each character of the text in pixels.
+    pub fn measure_advances(&self, cache: &mut GlyphCache, text: &str, size: f64) -> Vec<f64> {
+        self.shape(cache, text, size)
+            .char_advances(text)
+            .iter()
+            .map(|a| *a as f64)
+            .collect()
+    }
+
+    /// Renders the coverage of the text into an alpha mask, e.g. to fill it with a gradient.
+    /// Returns the width, the height and the mask data.
+    pub fn render_text_mask(
+        &self,
+        cache: &mut GlyphCache,
+        text: &str,
+        size: f64,
+        alpha: f32,
+    ) -> (i32, i32, Vec<u8>) {
+        let pixel_width = self.shape(cache, text, size).advance.ceil() as i32;
+        let pixel_height = size.ceil() as i32;
+
+        let mut mask = vec![0; (pixel_width.max(0) * pixel_height.max(0)) as usize];
+
+        self.draw_glyphs(cache, text, size, |off_x, off_y, v| {
+            if off_x >= 0 && off_x < pixel_width && off_y >= 0 && off_y < pixel_height {
+                let coverage = &mut mask[(off_y * pixel_width + off_x) as usize];
+                *coverage = (*coverage).max((alpha * v * 255.0) as u8);
+            }
+        });
+
+        (pixel_width, pixel_height, mask)
+    }
+
+    pub fn render_text(
+        &self,
+        cache: &mut GlyphCache,
+        text: &str,
+        data: &mut [u32],
+        width: f64,
+        // size, color, alpha
+        config: (f64, Color, f32),
+        position: (f64, f64),
+    ) {
+        self.render_text_clipped(
+            cache,
+            text,
+            data,
+            width,
+            config,
+            position,
+            Rectangle::new(0.0, 0.0, width, std::f64::MAX),
+        );
+    }
+
+    pub fn render_text_clipped(
+        &self,
+        cache: &mut GlyphCache,
+        text: &str,
+        data: &mut [u32],
+        width: f64,
+        // size, color, alpha
+        config: (f64, Color, f32),
+        position: (f64, f64),
+        clip: Rectangle,
+    ) {
+        let pixel_width = self.shape(cache, text, config.0).advance.ceil() as i32;
+
+        let pixel_height = config.0.ceil() as i32;
+
+        self.draw_glyphs(cache, text, config.0, |off_x, off_y, v| {
+            if off_x >= 0
+                && off_x < pixel_width
+                && off_y >= 0
+                && off_y < pixel_height
+                && position.0 + off_x as f64 >= clip.x
+                && position.0 + off_x as f64 <= clip.x + clip.width
+                && position.1 + off_y as f64 >= clip.y
+                && position.1 + off_y as f64 <= clip.y + clip.height
+            {
+                // Alpha blending from orbclient
+                let alpha = (config.2 * v * 255.0) as u32;
+                let new = (alpha << 24) | (config.1.data & 0x00FF_FFFF);
+
+                let index = ((position.1 as i32 + off_y) * width as i32 + position.0 as i32 + off_x)
+                    as usize;
+                if index >= data.len() {
+                    return;
+                }
+                let old = &mut data[index];
+                if alpha >= 255 {
+                    *old = new;
+                } else if alpha > 0 {
+                    let n_alpha = 255 - alpha;
+                    let rb =
+                        ((n_alpha * (*old & 0x00FF_00FF)) + (alpha * (new & 0x00FF_00FF))) >> 8;
+                    let ag = (n_alpha * ((*old & 0xFF00_FF00) >> 8))
+                        + (alpha * (0x0100_0000 | ((new & 0x0000_FF00) >> 8)));
+
+                    *old = (rb & 0x00FF_00FF) | (ag & 0xFF00_FF00);
+                }
+            }
+        });
+    }
+}
+
+// Appends the outline of a glyph to a path. Font units are scaled to pixels and moved to the
+// origin of the glyph, the y axis of font units points up.
+struct GlyphOutline<'a> {
+    builder: &'a mut raqote::PathBuilder,
+    scale: f32,
+    x: f32,
+    y: f32,
+}
+
+impl GlyphOutline<'_> {
+    fn point(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.x + x * self.scale, self.y - y * self.scale)
+    }
+}
+
+impl ttf_parser::OutlineBuilder for GlyphOutline<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        self.builder.move_to(x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let (x, y) = self.point(x, y);
+        self.builder.line_to(x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x, y) = self.point(x, y);
+        self.builder.quad_to(x1, y1, x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (x1, y1) = self.point(x1, y1);
+        let (x2, y2) = self.point(x2, y2);
+        let (x, y) = self.point(x, y);
+        self.builder.cubic_to(x1, y1, x2, y2, x, y);
+    }
+
+    fn close(&mut self) {
+        self.builder.close();
     }
 }
@@ -0,0 +1,179 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::shaping::ShapedText;
+
+// Number of horizontal subpixel positions a glyph is rasterized for.
+const SUBPIXEL_STEPS: f32 = 4.0;
+
+// Maximum number of entries of each cache before it is emptied.
+const MAX_GLYPHS: usize = 4096;
+const MAX_TEXTS: usize = 1024;
+
+/// Identifies a rasterized glyph.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct GlyphKey {
+    /// Id of the font the glyph is taken from.
+    pub font: usize,
+
+    /// Bits of the font size.
+    pub size: u64,
+    pub id: u16,
+
+    /// Horizontal offset of the glyph from the pixel grid in `1 / SUBPIXEL_STEPS` pixels.
+    pub subpixel: u8,
+}
+
+impl GlyphKey {
+    /// Creates a key for the glyph with the given id at the horizontal position `x`. Returns the
+    /// key and the pixel the glyph is placed at.
+    pub fn new(font: usize, size: f64, id: u16, x: f32) -> (Self, i32) {
+        let mut pixel = x.floor();
+        let mut subpixel = ((x - pixel) * SUBPIXEL_STEPS).round();
+
+        if subpixel >= SUBPIXEL_STEPS {
+            pixel += 1.0;
+            subpixel = 0.0;
+        }
+
+        (
+            GlyphKey {
+                font,
+                size: size.to_bits(),
+                id,
+                subpixel: subpixel as u8,
+            },
+            pixel as i32,
+        )
+    }
+
+    /// Gets the horizontal offset of the glyph from the pixel grid in pixels.
+    pub fn offset(&self) -> f32 {
+        self.subpixel as f32 / SUBPIXEL_STEPS
+    }
+}
+
+/// The coverage of a rasterized glyph relative to its origin on the baseline.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct RasterizedGlyph {
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+
+    /// Coverage of each pixel row by row from `0` to `255`.
+    pub coverage: Vec<u8>,
+}
+
+/// Identifies a measured text.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct TextKey {
+    /// Ids of the font chain the text is shaped with.
+    pub fonts: Vec<usize>,
+
+    /// Bits of the font size.
+    pub size: u64,
+    pub text: String,
+}
+
+/// Caches rasterized glyphs and shaped texts of a render context, so unchanged texts are not
+/// shaped and rasterized again on every frame.
+#[derive(Clone, Default, Debug)]
+pub struct GlyphCache {
+    glyphs: HashMap<GlyphKey, Arc<RasterizedGlyph>>,
+    texts: HashMap<TextKey, Arc<ShapedText>>,
+}
+
+impl GlyphCache {
+    /// Creates a new empty cache.
+    pub fn new() -> Self {
+        GlyphCache::default()
+    }
+
+    /// Gets the rasterized glyph of the given key. It is rasterized by `rasterize` if it is not
+    /// cached yet.
+    pub fn glyph<F>(&mut self, key: GlyphKey, rasterize: F) -> Arc<RasterizedGlyph>
+    where
+        F: FnOnce() -> RasterizedGlyph,
+    {
+        if let Some(glyph) = self.glyphs.get(&key) {
+            return glyph.clone();
+        }
+
+        if self.glyphs.len() >= MAX_GLYPHS {
+            self.glyphs.clear();
+        }
+
+        let glyph = Arc::new(rasterize());
+        self.glyphs.insert(key, glyph.clone());
+        glyph
+    }
+
+    /// Gets the shaped text of the given key. It is shaped by `shape` if it is not cached yet.
+    pub fn text<F>(&mut self, key: TextKey, shape: F) -> Arc<ShapedText>
+    where
+        F: FnOnce() -> ShapedText,
+    {
+        if let Some(text) = self.texts.get(&key) {
+            return text.clone();
+        }
+
+        if self.texts.len() >= MAX_TEXTS {
+            self.texts.clear();
+        }
+
+        let text = Arc::new(shape());
+        self.texts.insert(key, text.clone());
+        text
+    }
+
+    /// Gets the number of cached glyphs.
+    pub fn glyph_count(&self) -> usize {
+        self.glyphs.len()
+    }
+
+    /// Gets the number of cached texts.
+    pub fn text_count(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// Removes all cached glyphs and texts.
+    pub fn clear(&mut self) {
+        self.glyphs.clear();
+        self.texts.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glyph_key() {
+        let (key, pixel) = GlyphKey::new(0, 12.0, 1, 10.3);
+        assert_eq!((key.subpixel, pixel), (1, 10));
+        assert_eq!(key.offset(), 0.25);
+
+        let (key, pixel) = GlyphKey::new(0, 12.0, 1, 10.9);
+        assert_eq!((key.subpixel, pixel), (0, 11));
+    }
+
+    #[test]
+    fn test_glyph_is_rasterized_once() {
+        let mut cache = GlyphCache::new();
+        let (key, _) = GlyphKey::new(0, 12.0, 1, 0.0);
+        let mut count = 0;
+
+        for _ in 0..3 {
+            cache.glyph(key, || {
+                count += 1;
+                RasterizedGlyph::default()
+            });
+        }
+
+        assert_eq!(count, 1);
+        assert_eq!(cache.glyph_count(), 1);
+
+        cache.clear();
+        assert_eq!(cache.glyph_count(), 0);
+    }
+}
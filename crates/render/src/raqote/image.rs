@@ -1,13 +1,34 @@
-use std::{fmt, path::Path};
+use std::{
+    fmt,
+    path::Path,
+    sync::{Arc, Mutex},
+    thread,
+};
 
 use image;
 
 use crate::RenderTarget;
 
+// The result of the background thread and the function that is called as soon as it is there.
+#[derive(Default)]
+struct Decoding {
+    result: Option<Result<RenderTarget, String>>,
+    on_decoded: Option<Box<dyn FnOnce() + Send>>,
+}
+
+// An image that is decoded by a background thread.
+#[derive(Clone)]
+struct Pending {
+    width: u32,
+    height: u32,
+    decoding: Arc<Mutex<Decoding>>,
+}
+
 #[derive(Clone, Default)]
 pub struct Image {
     render_target: RenderTarget,
     source: String,
+    pending: Option<Pending>,
 }
 
 impl fmt::Debug for Image {
@@ -28,6 +49,7 @@ impl Image {
         Image {
             render_target: RenderTarget::new(width, height),
             source: String::default(),
+            pending: None,
         }
     }
 
@@ -41,6 +63,7 @@ impl Image {
         Ok(Image {
             render_target: RenderTarget::from_data(width, height, data).unwrap(),
             source: String::new(),
+            pending: None,
         })
     }
 
@@ -54,6 +77,88 @@ impl Image {
         Self::from_data(image.width(), image.height(), data)
     }
 
+    /// Starts to decode the image from file path on a background thread. Only the size is read
+    /// from the file header before, the image stays empty until it is taken by `poll`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref().to_path_buf();
+        let (width, height) =
+            image::image_dimensions(&path).map_err(|_| "Could not load image.".to_string())?;
+        let decoding = Arc::new(Mutex::new(Decoding::default()));
+        let sender = decoding.clone();
+
+        thread::spawn(move || {
+            let decoded = Self::from_path(path).map(|image| image.render_target);
+
+            let on_decoded = sender.lock().ok().and_then(|mut decoding| {
+                decoding.result = Some(decoded);
+                decoding.on_decoded.take()
+            });
+
+            if let Some(on_decoded) = on_decoded {
+                on_decoded();
+            }
+        });
+
+        Ok(Image {
+            render_target: RenderTarget::default(),
+            source: String::new(),
+            pending: Some(Pending {
+                width,
+                height,
+                decoding,
+            }),
+        })
+    }
+
+    /// Checks if the image is loaded by `load` and not taken by `poll` yet.
+    pub fn is_loading(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Checks if the background thread started by `load` has finished decoding.
+    pub fn is_decoded(&self) -> bool {
+        self.pending.as_ref().map_or(false, |pending| {
+            pending
+                .decoding
+                .lock()
+                .map_or(true, |decoding| decoding.result.is_some())
+        })
+    }
+
+    /// Sets a function that is called by the background thread started by `load` as soon as the
+    /// image is decoded, e.g. to wake up the event loop. It replaces a function that is set before
+    /// and is called right away if the image is already decoded.
+    pub fn on_decoded<F: FnOnce() + Send + 'static>(&self, on_decoded: F) {
+        if let Some(pending) = &self.pending {
+            if let Ok(mut decoding) = pending.decoding.lock() {
+                if decoding.result.is_none() {
+                    decoding.on_decoded = Some(Box::new(on_decoded));
+                    return;
+                }
+            }
+        }
+
+        on_decoded();
+    }
+
+    /// Takes the decoded image of `load` if it is ready and returns `true`. If the image could
+    /// not be decoded it stays empty.
+    pub fn poll(&mut self) -> bool {
+        if !self.is_decoded() {
+            return false;
+        }
+
+        if let Some(pending) = self.pending.take() {
+            if let Ok(Some(Ok(render_target))) =
+                pending.decoding.lock().map(|mut d| d.result.take())
+            {
+                self.render_target = render_target;
+            }
+        }
+
+        true
+    }
+
     /// Load an image from file path. Supports BMP and PNG
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
         let img = image::open(path);
@@ -64,14 +169,20 @@ impl Image {
         Err("Could not load image.".to_string())
     }
 
-    /// Gets the width.
+    /// Gets the width. While the image is loading, the width read from the file is returned.
     pub fn width(&self) -> f64 {
-        self.render_target.width() as f64
+        match &self.pending {
+            Some(pending) => pending.width as f64,
+            None => self.render_target.width() as f64,
+        }
     }
 
-    /// Gets the height.
+    /// Gets the height. While the image is loading, the height read from the file is returned.
     pub fn height(&self) -> f64 {
-        self.render_target.height() as f64
+        match &self.pending {
+            Some(pending) => pending.height as f64,
+            None => self.render_target.height() as f64,
+        }
     }
 
     pub fn data(&self) -> &[u32] {
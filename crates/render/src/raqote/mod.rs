@@ -1,29 +1,58 @@
-use std::{cmp, collections::HashMap};
+use std::{cmp, collections::HashMap, fs};
 
 use raqote;
 
-use crate::{utils::*, Pipeline, RenderConfig, RenderTarget, TextMetrics};
+use crate::{
+    blur_mask, blur_pixels, line_dash, utils::*, FillRule, FontDatabase, LineCap, LineJoin,
+    Pipeline, RenderConfig, RenderTarget, TextMetrics, Transform,
+};
 
 pub use self::font::*;
+pub use self::glyph_cache::*;
 pub use self::image::Image;
+pub use self::shaping::*;
 
 mod font;
+mod glyph_cache;
 mod image;
+mod shaping;
+
+// Image files above this size in bytes are decoded on a background thread.
+const LOAD_ASYNC_SIZE: u64 = 256 * 1024;
+
+// Canvas state that is pushed by `save` and popped by `restore`.
+#[derive(Clone)]
+struct State {
+    config: RenderConfig,
+    transform: Transform,
+    clip_rect: Option<Rectangle>,
+    clips: usize,
+}
 
 /// The RenderContext2D trait, provides the rendering ctx. It is used for drawing shapes, text, images, and other objects.
 pub struct RenderContext2D {
     draw_target: raqote::DrawTarget,
     path: raqote::Path,
     config: RenderConfig,
-    saved_config: Option<RenderConfig>,
-    fonts: HashMap<String, Font>,
+    saved_states: Vec<State>,
+    fonts: FontDatabase<Font>,
+    glyph_cache: GlyphCache,
+
+    // images of image pattern brushes by source
+    images: HashMap<String, Image>,
+    transform: Transform,
+
+    // number of clips pushed since the last save
+    clips: usize,
 
     // hack / work around for faster text clipping
-    clip: bool,
     last_rect: Rectangle,
     clip_rect: Option<Rectangle>,
 
     background: Color,
+
+    // physical pixels per logical pixel, all drawing is given in logical pixels
+    scale_factor: f64,
 }
 
 impl RenderContext2D {
@@ -36,12 +65,16 @@ impl RenderContext2D {
                 winding: raqote::Winding::NonZero,
             },
             config: RenderConfig::default(),
-            saved_config: None,
-            fonts: HashMap::new(),
-            clip: false,
+            saved_states: vec![],
+            fonts: FontDatabase::new(),
+            glyph_cache: GlyphCache::new(),
+            images: HashMap::new(),
+            transform: Transform::default(),
+            clips: 0,
             last_rect: Rectangle::new(0.0, 0.0, width, height),
             clip_rect: None,
             background: Color::default(),
+            scale_factor: 1.0,
         }
     }
 
@@ -50,21 +83,63 @@ impl RenderContext2D {
         self.background = background;
     }
 
+    /// Resizes the render context to the given logical size. The pixel buffer has the size
+    /// multiplied by the scale factor.
     pub fn resize(&mut self, width: f64, height: f64) {
-        self.draw_target = raqote::DrawTarget::new(width as i32, height as i32);
+        self.draw_target = raqote::DrawTarget::new(
+            (width * self.scale_factor).round() as i32,
+            (height * self.scale_factor).round() as i32,
+        );
+        self.saved_states.clear();
+        self.transform = Transform::default();
+        self.clips = 0;
+        self.clip_rect = None;
+    }
+
+    /// Sets the number of physical pixels per logical pixel, e.g. `2.0` on a HiDPI display. Shapes,
+    /// text and images are given in logical pixels and drawn at the physical resolution. The pixel
+    /// buffer is resized and has to be drawn again.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        if scale_factor <= 0.0 || scale_factor == self.scale_factor {
+            return;
+        }
+
+        let width = self.draw_target.width() as f64 / self.scale_factor;
+        let height = self.draw_target.height() as f64 / self.scale_factor;
+        self.scale_factor = scale_factor;
+        self.resize(width, height);
+    }
+
+    /// Gets the number of physical pixels per logical pixel.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
     }
 
     /// Registers a new font file.
     pub fn register_font(&mut self, family: &str, font_file: &'static [u8]) {
-        if self.fonts.contains_key(family) {
+        if self.fonts.contains_family(family) {
             return;
         }
 
         if let Ok(font) = Font::from_bytes(font_file) {
-            self.fonts.insert(family.to_string(), font);
+            self.fonts.register(font.face(family), font);
         }
     }
 
+    /// Loads and registers a font file from disk. The font could be selected by the family name
+    /// stored in the file.
+    pub fn register_font_file(&mut self, path: &str) -> Result<(), String> {
+        let font = Font::from_path(path)?;
+        self.fonts.register(font.face(""), font);
+        Ok(())
+    }
+
+    /// Sets the font families that are used in the given order for glyphs that are missing in
+    /// the selected font, e.g. for CJK characters or emojis.
+    pub fn set_font_fallbacks(&mut self, families: Vec<String>) {
+        self.fonts.set_fallbacks(families);
+    }
+
     // Rectangles
 
     /// Draws a filled rectangle whose starting point is at the coordinates {x, y} with the specified width and height and whose style is determined by the fillStyle attribute.
@@ -74,7 +149,7 @@ impl RenderContext2D {
             y as f32,
             width as f32,
             height as f32,
-            &brush_to_source(&self.config.fill_style),
+            &brush_to_source(&self.config.fill_style, &self.images),
             &raqote::DrawOptions {
                 alpha: self.config.alpha,
                 ..Default::default()
@@ -96,49 +171,101 @@ impl RenderContext2D {
             return;
         }
 
+        if self.config.fill_style.is_transparent() || self.config.alpha == 0.0 {
+            return;
+        }
+
+        if !self.transform.is_translation() {
+            self.fill_text_outlines(text, x, y);
+            return;
+        }
+
+        // glyphs are rendered directly into the pixel buffer, only the position is transformed and
+        // the font size is scaled to physical pixels
+        let (x, y) = self.device_transform().transform_point(x, y);
+        let font_size = self.config.font_config.font_size * self.scale_factor;
+
         let color = match self.config.fill_style {
             Brush::SolidColor(color) => color,
-            _ => Color::from("#000000"),
+            _ => {
+                self.fill_text_with_mask(text, x, y, font_size);
+                return;
+            }
         };
 
-        if color.a() == 0 || self.config.alpha == 0.0 {
-            return;
-        }
+        let fonts = select_fonts(&self.fonts, &self.config);
 
-        if let Some(font) = self.fonts.get(&self.config.font_config.family) {
+        if !fonts.is_empty() {
             let width = self.draw_target.width() as f64;
 
-            if self.clip {
-                if let Some(rect) = self.clip_rect {
-                    font.render_text_clipped(
-                        text,
-                        self.draw_target.get_data_mut(),
-                        width,
-                        (self.config.font_config.font_size, color, self.config.alpha),
-                        (x, y),
-                        rect,
-                    );
-                } else {
-                    font.render_text(
-                        text,
-                        self.draw_target.get_data_mut(),
-                        width,
-                        (self.config.font_config.font_size, color, self.config.alpha),
-                        (x, y),
-                    );
-                }
+            if let Some(rect) = self.clip_rect {
+                fonts.render_text_clipped(
+                    &mut self.glyph_cache,
+                    text,
+                    self.draw_target.get_data_mut(),
+                    width,
+                    (font_size, color, self.config.alpha),
+                    (x, y),
+                    rect,
+                );
             } else {
-                font.render_text(
+                fonts.render_text(
+                    &mut self.glyph_cache,
                     text,
                     self.draw_target.get_data_mut(),
                     width,
-                    (self.config.font_config.font_size, color, self.config.alpha),
+                    (font_size, color, self.config.alpha),
                     (x, y),
                 );
             }
         }
     }
 
+    // Fills the outlines of the glyphs through the current transformation, e.g. to draw a rotated
+    // or scaled text. The clip is applied by raqote.
+    fn fill_text_outlines(&mut self, text: &str, x: f64, y: f64) {
+        let fonts = select_fonts(&self.fonts, &self.config);
+
+        if !fonts.is_empty() {
+            let path = fonts.text_path(
+                &mut self.glyph_cache,
+                text,
+                self.config.font_config.font_size,
+                (x, y),
+            );
+
+            self.draw_target.fill(
+                &path,
+                &brush_to_source(&self.config.fill_style, &self.images),
+                &raqote::DrawOptions {
+                    alpha: self.config.alpha,
+                    ..Default::default()
+                },
+            );
+        }
+    }
+
+    // Fills the coverage of the text with a gradient or a pattern. The clip is applied by raqote.
+    fn fill_text_with_mask(&mut self, text: &str, x: f64, y: f64, font_size: f64) {
+        let fonts = select_fonts(&self.fonts, &self.config);
+
+        if !fonts.is_empty() {
+            let (width, height, data) =
+                fonts.render_text_mask(&mut self.glyph_cache, text, font_size, self.config.alpha);
+
+            if width <= 0 || height <= 0 {
+                return;
+            }
+
+            draw_mask(
+                &mut self.draw_target,
+                &brush_to_source(&self.config.fill_style, &self.images),
+                (x as i32, y as i32, width, height),
+                &data,
+            );
+        }
+    }
+
     /// Returns a TextMetrics object.
     pub fn measure_text(&mut self, text: &str) -> TextMetrics {
         let mut text_metrics = TextMetrics::default();
@@ -147,8 +274,14 @@ impl RenderContext2D {
             return text_metrics;
         }
 
-        if let Some(font) = self.fonts.get(&self.config.font_config.family) {
-            let (width, height) = font.measure_text(text, self.config.font_config.font_size);
+        let fonts = select_fonts(&self.fonts, &self.config);
+
+        if !fonts.is_empty() {
+            let (width, height) = fonts.measure_text(
+                &mut self.glyph_cache,
+                text,
+                self.config.font_config.font_size,
+            );
 
             text_metrics.width = width;
             text_metrics.height = height;
@@ -157,11 +290,33 @@ impl RenderContext2D {
         text_metrics
     }
 
+    /// Returns the advance of each character of the text. The sum of the advances is the width
+    /// of the text, e.g. the position of a caret behind the third character is the sum of the
+    /// first three advances. Measured texts are cached.
+    pub fn measure_advances(&mut self, text: &str) -> Vec<f64> {
+        let fonts = select_fonts(&self.fonts, &self.config);
+
+        if fonts.is_empty() {
+            return vec![0.0; text.chars().count()];
+        }
+
+        fonts.measure_advances(
+            &mut self.glyph_cache,
+            text,
+            self.config.font_config.font_size,
+        )
+    }
+
     /// Fills the current or given path with the current file style.
     pub fn fill(&mut self) {
+        self.path.winding = match self.config.fill_rule {
+            FillRule::NonZero => raqote::Winding::NonZero,
+            FillRule::EvenOdd => raqote::Winding::EvenOdd,
+        };
+
         self.draw_target.fill(
             &self.path,
-            &brush_to_source(&self.config.fill_style),
+            &brush_to_source(&self.config.fill_style, &self.images),
             &raqote::DrawOptions {
                 alpha: self.config.alpha,
                 ..Default::default()
@@ -173,10 +328,22 @@ impl RenderContext2D {
     pub fn stroke(&mut self) {
         self.draw_target.stroke(
             &self.path,
-            &brush_to_source(&self.config.stroke_style),
+            &brush_to_source(&self.config.stroke_style, &self.images),
             &raqote::StrokeStyle {
                 width: self.config.line_width as f32,
-                ..Default::default()
+                cap: match self.config.line_cap {
+                    LineCap::Butt => raqote::LineCap::Butt,
+                    LineCap::Round => raqote::LineCap::Round,
+                    LineCap::Square => raqote::LineCap::Square,
+                },
+                join: match self.config.line_join {
+                    LineJoin::Miter => raqote::LineJoin::Miter,
+                    LineJoin::Round => raqote::LineJoin::Round,
+                    LineJoin::Bevel => raqote::LineJoin::Bevel,
+                },
+                miter_limit: self.config.miter_limit as f32,
+                dash_array: self.config.line_dash.iter().map(|d| *d as f32).collect(),
+                dash_offset: self.config.line_dash_offset as f32,
             },
             &raqote::DrawOptions {
                 alpha: self.config.alpha,
@@ -185,6 +352,161 @@ impl RenderContext2D {
         );
     }
 
+    /// Fills the current path with the given color blurred by the blur radius, e.g. to draw the
+    /// shadow of a shape. The fill style is not changed.
+    pub fn fill_shadow(&mut self, color: Color, blur: f64) {
+        let (path, bounds) = match self.device_path() {
+            Some(path) => path,
+            None => return,
+        };
+        let blur = blur * self.scale_factor;
+
+        // the blurred edges do not need to be computed far outside of the render context
+        let margin = blur.ceil() + 1.0;
+        let left = (bounds.x - margin).floor().max(-margin);
+        let top = (bounds.y - margin).floor().max(-margin);
+        let right = (bounds.x + bounds.width + margin)
+            .ceil()
+            .min(self.draw_target.width() as f64 + margin);
+        let bottom = (bounds.y + bounds.height + margin)
+            .ceil()
+            .min(self.draw_target.height() as f64 + margin);
+        let (width, height) = ((right - left) as i32, (bottom - top) as i32);
+
+        if width <= 0 || height <= 0 {
+            return;
+        }
+
+        let mut mask_target = raqote::DrawTarget::new(width, height);
+        mask_target.set_transform(&raqote::Transform::row_major(
+            1.0,
+            0.0,
+            0.0,
+            1.0,
+            -left as f32,
+            -top as f32,
+        ));
+        mask_target.fill(
+            &path,
+            &raqote::Source::Solid(raqote::SolidSource {
+                r: 0xFF,
+                g: 0xFF,
+                b: 0xFF,
+                a: 0xFF,
+            }),
+            &raqote::DrawOptions::default(),
+        );
+
+        let mut data: Vec<u8> = mask_target
+            .get_data()
+            .iter()
+            .map(|p| ((*p >> 24) as f32 * self.config.alpha) as u8)
+            .collect();
+        blur_mask(&mut data, width as usize, height as usize, blur);
+
+        draw_mask(
+            &mut self.draw_target,
+            &raqote::Source::Solid(raqote::SolidSource {
+                r: color.r(),
+                g: color.g(),
+                b: color.b(),
+                a: color.a(),
+            }),
+            (left as i32, top as i32, width, height),
+            &data,
+        );
+    }
+
+    /// Blurs the pixels of the given rectangle that are already drawn, e.g. for a frosted glass
+    /// effect behind a widget. Only the pixels inside of the current clip are changed.
+    pub fn blur_rect(&mut self, x: f64, y: f64, width: f64, height: f64, radius: f64) {
+        let radius = radius * self.scale_factor;
+        let mut rect = self
+            .device_transform()
+            .transform_rect(&Rectangle::new(x, y, width, height))
+            .intersection(&Rectangle::new(
+                0.0,
+                0.0,
+                self.draw_target.width() as f64,
+                self.draw_target.height() as f64,
+            ));
+
+        if let Some(clip_rect) = self.clip_rect {
+            rect = rect.intersection(&clip_rect);
+        }
+
+        let (left, top) = (rect.x.floor() as usize, rect.y.floor() as usize);
+        let right = (rect.x + rect.width).ceil() as usize;
+        let bottom = (rect.y + rect.height).ceil() as usize;
+
+        if radius <= 0.0 || right <= left || bottom <= top {
+            return;
+        }
+
+        let stride = self.draw_target.width() as usize;
+        let data = self.draw_target.get_data_mut();
+        let mut pixels = Vec::with_capacity((right - left) * (bottom - top));
+
+        for row in top..bottom {
+            pixels.extend_from_slice(&data[row * stride + left..row * stride + right]);
+        }
+
+        blur_pixels(&mut pixels, right - left, bottom - top, radius);
+
+        for (i, row) in (top..bottom).enumerate() {
+            data[row * stride + left..row * stride + right]
+                .copy_from_slice(&pixels[i * (right - left)..(i + 1) * (right - left)]);
+        }
+    }
+
+    // Gets the current path in device coordinates and its bounds.
+    fn device_path(&self) -> Option<(raqote::Path, Rectangle)> {
+        let transform = self.device_transform();
+        let mut builder = raqote::PathBuilder::new();
+        let mut points = vec![];
+
+        for op in &self.path.ops {
+            let mut map = |p: &raqote::Point| {
+                let (x, y) = transform.transform_point(p.x as f64, p.y as f64);
+                points.push((x, y));
+                (x as f32, y as f32)
+            };
+
+            match op {
+                raqote::PathOp::MoveTo(p) => {
+                    let (x, y) = map(p);
+                    builder.move_to(x, y);
+                }
+                raqote::PathOp::LineTo(p) => {
+                    let (x, y) = map(p);
+                    builder.line_to(x, y);
+                }
+                raqote::PathOp::QuadTo(c, p) => {
+                    let ((cx, cy), (x, y)) = (map(c), map(p));
+                    builder.quad_to(cx, cy, x, y);
+                }
+                raqote::PathOp::CubicTo(c1, c2, p) => {
+                    let ((c1x, c1y), (c2x, c2y), (x, y)) = (map(c1), map(c2), map(p));
+                    builder.cubic_to(c1x, c1y, c2x, c2y, x, y);
+                }
+                raqote::PathOp::Close => builder.close(),
+            }
+        }
+
+        let (first, rest) = points.split_first()?;
+        let (min, max) = rest.iter().fold((*first, *first), |(min, max), p| {
+            (
+                (min.0.min(p.0), min.1.min(p.1)),
+                (max.0.max(p.0), max.1.max(p.1)),
+            )
+        });
+
+        Some((
+            builder.finish(),
+            Rectangle::new(min.0, min.1, max.0 - min.0, max.1 - min.1),
+        ))
+    }
+
     /// Starts a new path by emptying the list of sub-paths. Call this when you want to create a new path.
     pub fn begin_path(&mut self) {
         self.path = raqote::Path {
@@ -277,6 +599,10 @@ impl RenderContext2D {
 
     /// Draws the image.
     pub fn draw_image(&mut self, image: &Image, x: f64, y: f64) {
+        if image.is_loading() {
+            return;
+        }
+
         self.draw_target.draw_image_at(
             x as f32,
             y as f32,
@@ -294,6 +620,10 @@ impl RenderContext2D {
 
     /// Draws the given part of the image.
     pub fn draw_image_with_clip(&mut self, image: &Image, clip: Rectangle, x: f64, y: f64) {
+        if image.is_loading() {
+            return;
+        }
+
         let mut y = y as i32;
         let stride = image.width();
         let mut offset = clip.y.mul_add(stride, clip.x) as usize;
@@ -322,6 +652,53 @@ impl RenderContext2D {
         }
     }
 
+    /// Draws the given part of the image scaled to the given size with bilinear filtering.
+    pub fn draw_image_with_clip_and_size(
+        &mut self,
+        image: &Image,
+        clip: Rectangle,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) {
+        let stride = image.width() as usize;
+        let clip_x = (clip.x.max(0.0) as usize).min(stride);
+        let clip_y = (clip.y.max(0.0) as usize).min(image.height() as usize);
+        let clip_width = (clip.width.ceil().max(0.0) as usize).min(stride - clip_x);
+        let clip_height =
+            (clip.height.ceil().max(0.0) as usize).min(image.height() as usize - clip_y);
+
+        if image.is_loading() || clip_width == 0 || clip_height == 0 || width <= 0. || height <= 0.
+        {
+            return;
+        }
+
+        let data: Vec<u32> = (clip_y..clip_y + clip_height)
+            .flat_map(|row| {
+                image.data()[row * stride + clip_x..row * stride + clip_x + clip_width]
+                    .iter()
+                    .cloned()
+            })
+            .collect();
+
+        self.draw_target.draw_image_with_size_at(
+            x as f32,
+            y as f32,
+            width as f32,
+            height as f32,
+            &raqote::Image {
+                data: &data,
+                width: clip_width as i32,
+                height: clip_height as i32,
+            },
+            &raqote::DrawOptions {
+                alpha: self.config.alpha,
+                ..Default::default()
+            },
+        );
+    }
+
     pub fn draw_pipeline(
         &mut self,
         x: f64,
@@ -330,15 +707,39 @@ impl RenderContext2D {
         height: f64,
         pipeline: Box<dyn Pipeline>,
     ) {
-        let mut render_target = RenderTarget::new(width as u32, height as u32);
+        // the pipeline draws with the physical resolution
+        let mut render_target = RenderTarget::new(
+            (width * self.scale_factor).round() as u32,
+            (height * self.scale_factor).round() as u32,
+        );
         pipeline.draw_pipeline(&mut render_target);
-        self.draw_render_target(&render_target, x, y);
+
+        self.draw_target.draw_image_with_size_at(
+            x as f32,
+            y as f32,
+            width as f32,
+            height as f32,
+            &raqote::Image {
+                data: &render_target.data(),
+                width: render_target.width() as i32,
+                height: render_target.height() as i32,
+            },
+            &raqote::DrawOptions {
+                alpha: self.config.alpha,
+                ..Default::default()
+            },
+        );
     }
 
     /// Creates a clipping path from the current sub-paths. Everything drawn after clip() is called appears inside the clipping path only.
+    /// Nested clips are intersected with the clips of the outer states.
     pub fn clip(&mut self) {
-        self.clip_rect = Some(self.last_rect);
-        self.clip = true;
+        let rect = self.device_transform().transform_rect(&self.last_rect);
+        self.clip_rect = Some(match self.clip_rect {
+            Some(outer) => outer.intersection(&rect),
+            None => rect,
+        });
+        self.clips += 1;
         self.draw_target.push_clip(&self.path);
     }
 
@@ -349,6 +750,42 @@ impl RenderContext2D {
         self.config.line_width = line_width;
     }
 
+    /// Sets the dash pattern of lines as list of alternating line and gap lengths. An empty list
+    /// draws solid lines.
+    pub fn set_line_dash(&mut self, segments: &[f64]) {
+        if let Some(line_dash) = line_dash(segments) {
+            self.config.line_dash = line_dash;
+        }
+    }
+
+    /// Sets the offset at which the dash pattern of lines starts.
+    pub fn set_line_dash_offset(&mut self, offset: f64) {
+        self.config.line_dash_offset = offset;
+    }
+
+    /// Sets how the end points of lines are drawn.
+    pub fn set_line_cap(&mut self, line_cap: LineCap) {
+        self.config.line_cap = line_cap;
+    }
+
+    /// Sets how two connected segments of a line are joined.
+    pub fn set_line_join(&mut self, line_join: LineJoin) {
+        self.config.line_join = line_join;
+    }
+
+    /// Sets which areas inside of a path are filled by `fill`.
+    pub fn set_fill_rule(&mut self, fill_rule: FillRule) {
+        self.config.fill_rule = fill_rule;
+    }
+
+    /// Sets the limit of the ratio between the miter length and the line width. Longer miters are
+    /// drawn beveled.
+    pub fn set_miter_limit(&mut self, miter_limit: f64) {
+        if miter_limit > 0.0 {
+            self.config.miter_limit = miter_limit;
+        }
+    }
+
     /// Sets the alpha value,
     pub fn set_alpha(&mut self, alpha: f32) {
         self.config.alpha = alpha;
@@ -364,18 +801,48 @@ impl RenderContext2D {
         self.config.font_config.font_size = size + 4.0;
     }
 
+    /// Specifies the font weight.
+    pub fn set_font_weight(&mut self, weight: FontWeight) {
+        self.config.font_config.weight = weight;
+    }
+
+    /// Specifies the font style.
+    pub fn set_font_style(&mut self, style: FontStyle) {
+        self.config.font_config.style = style;
+    }
+
+    /// Specifies the font stretch.
+    pub fn set_font_stretch(&mut self, stretch: FontStretch) {
+        self.config.font_config.stretch = stretch;
+    }
+
     // Fill and stroke style
 
     /// Specifies the fill color to use inside shapes.
     pub fn set_fill_style(&mut self, fill_style: Brush) {
+        self.load_pattern_image(&fill_style);
         self.config.fill_style = fill_style;
     }
 
     /// Specifies the fill stroke to use inside shapes.
     pub fn set_stroke_style(&mut self, stroke_style: Brush) {
+        self.load_pattern_image(&stroke_style);
         self.config.stroke_style = stroke_style;
     }
 
+    // Loads the image of an image pattern brush once.
+    fn load_pattern_image(&mut self, brush: &Brush) {
+        if let Brush::ImagePattern { source, .. } = brush {
+            if self.images.contains_key(source) {
+                return;
+            }
+
+            if let Ok(image) = Image::from_path(source) {
+                self.images.insert(source.clone(), image);
+            }
+        }
+    }
+
     // Transformations
 
     /// Sets the transformation.
@@ -388,14 +855,46 @@ impl RenderContext2D {
         h_moving: f64,
         v_moving: f64,
     ) {
+        self.transform = Transform::new(
+            h_scaling, h_skewing, v_skewing, v_scaling, h_moving, v_moving,
+        );
+        self.apply_transform();
+    }
+
+    /// Adds a translation to the current transformation.
+    pub fn translate(&mut self, x: f64, y: f64) {
+        self.transform = self.transform.translate(x, y);
+        self.apply_transform();
+    }
+
+    /// Adds a scaling to the current transformation.
+    pub fn scale(&mut self, x: f64, y: f64) {
+        self.transform = self.transform.scale(x, y);
+        self.apply_transform();
+    }
+
+    /// Adds a clockwise rotation by the given angle in radians to the current transformation.
+    pub fn rotate(&mut self, angle: f64) {
+        self.transform = self.transform.rotate(angle);
+        self.apply_transform();
+    }
+
+    // Gets the transformation from logical to physical pixels.
+    fn device_transform(&self) -> Transform {
+        self.transform
+            .then_scale(self.scale_factor, self.scale_factor)
+    }
+
+    fn apply_transform(&mut self) {
+        let t = self.device_transform();
         self.draw_target
             .set_transform(&raqote::Transform::row_major(
-                h_scaling as f32,
-                h_skewing as f32,
-                v_skewing as f32,
-                v_scaling as f32,
-                h_moving as f32,
-                v_moving as f32,
+                t.h_scaling as f32,
+                t.h_skewing as f32,
+                t.v_skewing as f32,
+                t.v_scaling as f32,
+                t.h_moving as f32,
+                t.v_moving as f32,
             ));
     }
 
@@ -403,40 +902,64 @@ impl RenderContext2D {
 
     /// Saves the entire state of the canvas by pushing the current state onto a stack.
     pub fn save(&mut self) {
-        self.saved_config = Some(self.config.clone());
+        self.saved_states.push(State {
+            config: self.config.clone(),
+            transform: self.transform,
+            clip_rect: self.clip_rect,
+            clips: self.clips,
+        });
+        self.clips = 0;
     }
 
     /// Restores the most recently saved canvas state by popping the top entry in the drawing state stack.
     /// If there is no saved state, this method does nothing.
     pub fn restore(&mut self) {
-        self.clip = false;
-        self.clip_rect = None;
-        self.draw_target.pop_clip();
-        if let Some(config) = &self.saved_config {
-            self.config = config.clone();
-        }
+        if let Some(state) = self.saved_states.pop() {
+            for _ in 0..self.clips {
+                self.draw_target.pop_clip();
+            }
 
-        self.saved_config = None;
+            self.config = state.config;
+            self.transform = state.transform;
+            self.clip_rect = state.clip_rect;
+            self.clips = state.clips;
+            self.apply_transform();
+        }
     }
 
+    /// Replaces all pixels of the render context by the given brush.
     pub fn clear(&mut self, brush: &Brush) {
-        let solid = match *brush {
-            Brush::SolidColor(color) => raqote::SolidSource {
+        if let Brush::SolidColor(color) = *brush {
+            self.draw_target.clear(raqote::SolidSource {
                 r: color.r(),
                 g: color.g(),
                 b: color.b(),
                 a: color.a(),
-            },
+            });
+            return;
+        }
 
-            _ => raqote::SolidSource {
-                r: 0x0,
-                g: 0x0,
-                b: 0x80,
-                a: 0x80,
-            },
-        };
+        self.load_pattern_image(brush);
+        self.draw_target.clear(raqote::SolidSource {
+            r: 0x0,
+            g: 0x0,
+            b: 0x0,
+            a: 0x0,
+        });
 
-        self.draw_target.clear(solid);
+        // the brush covers the whole render context independent of the current transformation
+        self.draw_target
+            .set_transform(&raqote::Transform::identity());
+        let (width, height) = (self.draw_target.width(), self.draw_target.height());
+        self.draw_target.fill_rect(
+            0.0,
+            0.0,
+            width as f32,
+            height as f32,
+            &brush_to_source(brush, &self.images),
+            &raqote::DrawOptions::default(),
+        );
+        self.apply_transform();
     }
 
     pub fn data(&self) -> &[u32] {
@@ -452,28 +975,105 @@ impl RenderContext2D {
     }
 
     pub fn start(&mut self) {
+        self.reset();
         self.clear(&Brush::from(self.background));
     }
+
+    /// Starts a new frame that only repaints the given region. The pixels outside of the region
+    /// are kept from the last frame and all drawing is clipped to the region.
+    pub fn start_region(&mut self, region: Rectangle) {
+        self.reset();
+
+        // the region is extended to whole physical pixels, else its edges are blended twice
+        let scale_factor = self.scale_factor;
+        let (left, top) = (
+            (region.x * scale_factor).floor() / scale_factor,
+            (region.y * scale_factor).floor() / scale_factor,
+        );
+        let region = Rectangle::new(
+            left,
+            top,
+            ((region.x + region.width) * scale_factor).ceil() / scale_factor - left,
+            ((region.y + region.height) * scale_factor).ceil() / scale_factor - top,
+        );
+
+        self.begin_path();
+        self.rect(region.x, region.y, region.width, region.height);
+        self.clip();
+
+        self.draw_target.fill_rect(
+            region.x as f32,
+            region.y as f32,
+            region.width as f32,
+            region.height as f32,
+            &raqote::Source::Solid(raqote::SolidSource {
+                r: self.background.r(),
+                g: self.background.g(),
+                b: self.background.b(),
+                a: self.background.a(),
+            }),
+            &raqote::DrawOptions {
+                blend_mode: raqote::BlendMode::Src,
+                ..Default::default()
+            },
+        );
+        self.begin_path();
+    }
+
+    // Drops the saved states, the clips and the transformation of the last frame.
+    fn reset(&mut self) {
+        while !self.saved_states.is_empty() {
+            self.restore();
+        }
+
+        for _ in 0..self.clips {
+            self.draw_target.pop_clip();
+        }
+        self.clips = 0;
+        self.clip_rect = None;
+        self.transform = Transform::default();
+        self.apply_transform();
+    }
     pub fn finish(&mut self) {}
 }
 
 // --- Conversions ---
 
 impl From<&str> for Image {
+    /// Loads the image from file path, large files are decoded on a background thread like by
+    /// `Image::load`. An empty image is used if the file could not be loaded.
     fn from(s: &str) -> Image {
-        Image::from_path(s).unwrap()
+        let image = if fs::metadata(s).map_or(false, |m| m.len() > LOAD_ASYNC_SIZE) {
+            Image::load(s)
+        } else {
+            Image::from_path(s)
+        };
+
+        image.unwrap_or_default()
     }
 }
 
 impl From<String> for Image {
     fn from(s: String) -> Image {
-        Image::from_path(s).unwrap()
+        Image::from(s.as_str())
     }
 }
 
 // --- Conversions ---
 
-fn brush_to_source<'a>(brush: &Brush) -> raqote::Source<'a> {
+// Gets the font that matches the font config followed by its fallbacks.
+fn select_fonts<'a>(fonts: &'a FontDatabase<Font>, config: &RenderConfig) -> FontChain<'a> {
+    let font_config = &config.font_config;
+
+    FontChain::new(fonts.select(
+        &font_config.family,
+        font_config.weight,
+        font_config.style,
+        font_config.stretch,
+    ))
+}
+
+fn brush_to_source<'a>(brush: &Brush, images: &'a HashMap<String, Image>) -> raqote::Source<'a> {
     match brush {
         Brush::SolidColor(color) => raqote::Source::Solid(raqote::SolidSource {
             r: color.r(),
@@ -481,26 +1081,196 @@ fn brush_to_source<'a>(brush: &Brush) -> raqote::Source<'a> {
             b: color.b(),
             a: color.a(),
         }),
-        Brush::LinearGradient { start, end, stops } => {
-            let g_stops = stops
-                .iter()
-                .map(|stop| raqote::GradientStop {
-                    position: stop.position as f32,
-                    color: raqote::Color::new(
-                        stop.color.a(),
-                        stop.color.r(),
-                        stop.color.g(),
-                        stop.color.b(),
-                    ),
-                })
-                .collect();
-
-            raqote::Source::new_linear_gradient(
-                raqote::Gradient { stops: g_stops },
-                raqote::Point::new(start.x as f32, start.y as f32),
-                raqote::Point::new(end.x as f32, start.y as f32),
-                raqote::Spread::Pad,
-            )
+        Brush::LinearGradient { start, end, stops } => raqote::Source::new_linear_gradient(
+            gradient(stops),
+            raqote::Point::new(start.x as f32, start.y as f32),
+            raqote::Point::new(end.x as f32, end.y as f32),
+            raqote::Spread::Pad,
+        ),
+        Brush::RadialGradient {
+            center,
+            radius,
+            stops,
+        } => raqote::Source::new_radial_gradient(
+            gradient(stops),
+            raqote::Point::new(center.x as f32, center.y as f32),
+            *radius as f32,
+            raqote::Spread::Pad,
+        ),
+        Brush::ImagePattern { source, origin } => match images.get(source) {
+            Some(image) => raqote::Source::Image(
+                raqote::Image {
+                    width: image.width() as i32,
+                    height: image.height() as i32,
+                    data: image.data(),
+                },
+                raqote::ExtendMode::Repeat,
+                raqote::FilterMode::Bilinear,
+                // maps from user space to image space
+                raqote::Transform::row_major(
+                    1.0,
+                    0.0,
+                    0.0,
+                    1.0,
+                    -origin.x as f32,
+                    -origin.y as f32,
+                ),
+            ),
+            None => raqote::Source::Solid(raqote::SolidSource {
+                r: 0x0,
+                g: 0x0,
+                b: 0x0,
+                a: 0x0,
+            }),
+        },
+        // relative brushes are resolved by `Brush::with_bounds` before they are drawn
+        Brush::Relative(_) => raqote::Source::Solid(raqote::SolidSource {
+            r: 0x0,
+            g: 0x0,
+            b: 0x0,
+            a: 0x0,
+        }),
+    }
+}
+
+// Fills the pixels of the given rectangle in device space with the source, each pixel is covered
+// by the alpha value of the mask. The source is given in user space like for a fill.
+fn draw_mask(
+    draw_target: &mut raqote::DrawTarget,
+    source: &raqote::Source,
+    (x, y, width, height): (i32, i32, i32, i32),
+    mask: &[u8],
+) {
+    let transform = *draw_target.get_transform();
+
+    let inverse = match transform.inverse() {
+        Some(inverse) => inverse,
+        None => return,
+    };
+
+    // the rectangle is filled in user space, the layer starts at its top left corner
+    let mut layer = raqote::DrawTarget::new(width, height);
+    layer.set_transform(&raqote::Transform::row_major(
+        transform.m11,
+        transform.m12,
+        transform.m21,
+        transform.m22,
+        transform.m31 - x as f32,
+        transform.m32 - y as f32,
+    ));
+
+    let corners = [
+        (x, y),
+        (x + width, y),
+        (x + width, y + height),
+        (x, y + height),
+    ];
+    let mut builder = raqote::PathBuilder::new();
+
+    for (i, (cx, cy)) in corners.iter().enumerate() {
+        let p = inverse.transform_point(raqote::Point::new(*cx as f32, *cy as f32));
+
+        if i == 0 {
+            builder.move_to(p.x, p.y);
+        } else {
+            builder.line_to(p.x, p.y);
         }
     }
+    builder.close();
+
+    layer.fill(&builder.finish(), source, &raqote::DrawOptions::default());
+
+    // the pixels are premultiplied, all channels are scaled by the coverage
+    for (pixel, coverage) in layer.get_data_mut().iter_mut().zip(mask) {
+        let coverage = *coverage as u32;
+        let channel = |shift: u32| ((*pixel >> shift & 0xFF) * coverage / 255) << shift;
+        *pixel = channel(24) | channel(16) | channel(8) | channel(0);
+    }
+
+    draw_target.set_transform(&raqote::Transform::identity());
+    draw_target.draw_image_at(
+        x as f32,
+        y as f32,
+        &raqote::Image {
+            width,
+            height,
+            data: layer.get_data(),
+        },
+        &raqote::DrawOptions::default(),
+    );
+    draw_target.set_transform(&transform);
+}
+
+fn gradient(stops: &[LinearGradientStop]) -> raqote::Gradient {
+    raqote::Gradient {
+        stops: stops
+            .iter()
+            .map(|stop| raqote::GradientStop {
+                position: stop.position as f32,
+                color: raqote::Color::new(
+                    stop.color.a(),
+                    stop.color.r(),
+                    stop.color.g(),
+                    stop.color.b(),
+                ),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROBOTO: &[u8] = include_bytes!("../../../theme/src/fonts/Roboto-Regular.ttf");
+
+    // Draws the text with the given transformation and returns the bounds of the drawn pixels.
+    fn text_bounds(transform: impl Fn(&mut RenderContext2D)) -> Rectangle {
+        let mut render_context = RenderContext2D::new(200.0, 200.0);
+        render_context.register_font("Roboto", ROBOTO);
+        render_context.set_font_family("Roboto");
+        render_context.set_font_size(16.0);
+        render_context.set_fill_style(Brush::from("#000000"));
+        render_context.translate(100.0, 10.0);
+        transform(&mut render_context);
+        render_context.fill_text("OrbTk", 0.0, 0.0);
+
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (200, 200, 0, 0);
+
+        for (i, pixel) in render_context.data().iter().enumerate() {
+            if pixel >> 24 > 0 {
+                let (x, y) = (i % 200, i / 200);
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x + 1);
+                max_y = max_y.max(y + 1);
+            }
+        }
+
+        Rectangle::new(
+            min_x as f64,
+            min_y as f64,
+            max_x as f64 - min_x as f64,
+            max_y as f64 - min_y as f64,
+        )
+    }
+
+    #[test]
+    fn test_fill_transformed_text() {
+        let bounds = text_bounds(|_| {});
+        assert!(bounds.width() > bounds.height());
+        assert!(bounds.x() >= 100.0 && bounds.y() >= 10.0);
+
+        // the glyphs are scaled with the text
+        let scaled = text_bounds(|render_context| render_context.scale(2.0, 2.0));
+        assert!((scaled.width() - 2.0 * bounds.width()).abs() <= 2.0);
+        assert!((scaled.height() - 2.0 * bounds.height()).abs() <= 2.0);
+
+        // the text runs down and its glyphs stand left of the rotated origin
+        let rotated =
+            text_bounds(|render_context| render_context.rotate(std::f64::consts::FRAC_PI_2));
+        assert!((rotated.height() - bounds.width()).abs() <= 2.0);
+        assert!((rotated.width() - bounds.height()).abs() <= 2.0);
+        assert!(rotated.x() + rotated.width() <= 100.0 && rotated.y() >= 10.0);
+    }
 }
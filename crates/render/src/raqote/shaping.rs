@@ -0,0 +1,262 @@
+use std::ops::Range;
+
+use rustybuzz;
+use ttf_parser;
+use unicode_bidi::{BidiClass, BidiInfo};
+
+/// Describes a font that is used to shape a text. The faces are parsed once when the font is
+/// loaded.
+#[derive(Copy, Clone)]
+pub struct ShapingFont<'a> {
+    /// The face that is used by the shaper.
+    pub face: &'a rustybuzz::Face<'a>,
+
+    /// The face that is used to look up the glyphs of characters.
+    pub ttf_face: &'a ttf_parser::Face<'a>,
+
+    /// Factor that scales font units to pixels.
+    pub scale: f32,
+}
+
+/// Describes a glyph of a shaped text. Positions are in pixels, `y` points down.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ShapedGlyph {
+    pub id: u16,
+
+    /// Index of the font that contains the glyph.
+    pub font: usize,
+
+    /// Byte index of the first character of the glyph in the text.
+    pub cluster: usize,
+
+    pub x: f32,
+    pub y: f32,
+
+    /// Horizontal advance of the glyph in pixels.
+    pub advance: f32,
+}
+
+/// Describes a text that is shaped and reordered for display from left to right.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ShapedText {
+    /// The glyphs in visual order.
+    pub glyphs: Vec<ShapedGlyph>,
+
+    /// The sum of all glyph advances in pixels.
+    pub advance: f32,
+}
+
+impl ShapedText {
+    /// Gets the advance of each character of the shaped text in logical order. The advance of a
+    /// glyph cluster, e.g. a ligature, is split evenly between its characters.
+    pub fn char_advances(&self, text: &str) -> Vec<f32> {
+        let mut clusters: Vec<(usize, f32)> = vec![];
+
+        for glyph in &self.glyphs {
+            match clusters.iter_mut().find(|(c, _)| *c == glyph.cluster) {
+                Some((_, advance)) => *advance += glyph.advance,
+                None => clusters.push((glyph.cluster, glyph.advance)),
+            }
+        }
+
+        clusters.sort_by_key(|(cluster, _)| *cluster);
+
+        let mut advances = vec![0.0; text.chars().count()];
+
+        for (i, (cluster, advance)) in clusters.iter().enumerate() {
+            let end = clusters.get(i + 1).map_or(text.len(), |(c, _)| *c);
+            let start = text[..*cluster].chars().count();
+            let count = text[*cluster..end].chars().count();
+
+            for a in advances.iter_mut().skip(start).take(count) {
+                *a = advance / count as f32;
+            }
+        }
+
+        advances
+    }
+}
+
+/// Shapes the given text. Each character is taken from the first of the fonts that contains it,
+/// the following fonts are the fallbacks of the first one. The text is split into runs of the
+/// same direction by the unicode bidi algorithm, each run is shaped on its own and the runs are
+/// placed in visual order. Ligatures, kerning and combining marks are resolved by the shaper.
+pub fn shape_text(fonts: &[ShapingFont], text: &str) -> ShapedText {
+    let mut shaped_text = ShapedText::default();
+
+    if text.is_empty() || fonts.is_empty() {
+        return shaped_text;
+    }
+
+    let bidi_info = BidiInfo::new(text, None);
+    let segments = font_segments(fonts, text, &bidi_info.original_classes);
+
+    for paragraph in &bidi_info.paragraphs {
+        let (levels, runs) = bidi_info.visual_runs(paragraph, paragraph.range.clone());
+
+        for run in runs {
+            let rtl = levels[run.start].is_rtl();
+
+            let mut parts: Vec<(Range<usize>, usize)> = segments
+                .iter()
+                .filter(|(range, _)| range.start < run.end && range.end > run.start)
+                .map(|(range, font)| (range.start.max(run.start)..range.end.min(run.end), *font))
+                .collect();
+
+            if rtl {
+                parts.reverse();
+            }
+
+            for (range, font) in parts {
+                shape_run(
+                    fonts[font].face,
+                    text,
+                    range,
+                    rtl,
+                    (font, fonts[font].scale),
+                    &mut shaped_text,
+                );
+            }
+        }
+    }
+
+    shaped_text
+}
+
+// Splits the text into ranges of characters that are taken from the same font. Combining marks
+// use the font of the character they belong to.
+fn font_segments(
+    fonts: &[ShapingFont],
+    text: &str,
+    classes: &[BidiClass],
+) -> Vec<(Range<usize>, usize)> {
+    let mut segments: Vec<(Range<usize>, usize)> = vec![];
+
+    for (index, c) in text.char_indices() {
+        let end = index + c.len_utf8();
+
+        let font = match segments.last() {
+            Some((_, font)) if classes[index] == BidiClass::NSM => *font,
+            _ => fonts
+                .iter()
+                .position(|font| font.ttf_face.glyph_index(c).is_some())
+                .unwrap_or(0),
+        };
+
+        match segments.last_mut() {
+            Some((range, last)) if *last == font => range.end = end,
+            _ => segments.push((index..end, font)),
+        }
+    }
+
+    segments
+}
+
+// Shapes a range of the text with a single direction and font and appends its glyphs.
+fn shape_run(
+    face: &rustybuzz::Face,
+    text: &str,
+    range: Range<usize>,
+    rtl: bool,
+    (font, scale): (usize, f32),
+    shaped_text: &mut ShapedText,
+) {
+    let mut buffer = rustybuzz::UnicodeBuffer::new();
+    buffer.push_str(&text[range.clone()]);
+    buffer.set_direction(if rtl {
+        rustybuzz::Direction::RightToLeft
+    } else {
+        rustybuzz::Direction::LeftToRight
+    });
+    buffer.guess_segment_properties();
+
+    let output = rustybuzz::shape(face, &[], buffer);
+
+    for (info, position) in output
+        .glyph_infos()
+        .iter()
+        .zip(output.glyph_positions().iter())
+    {
+        shaped_text.glyphs.push(ShapedGlyph {
+            id: info.codepoint as u16,
+            font,
+            cluster: range.start + info.cluster as usize,
+            x: shaped_text.advance + position.x_offset as f32 * scale,
+            y: -position.y_offset as f32 * scale,
+            advance: position.x_advance as f32 * scale,
+        });
+
+        shaped_text.advance += position.x_advance as f32 * scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Font;
+    use super::*;
+
+    const ROBOTO: &[u8] = include_bytes!("../../../theme/src/fonts/Roboto-Regular.ttf");
+    const MATERIAL_ICONS: &[u8] =
+        include_bytes!("../../../theme/src/fonts/MaterialIcons-Regular.ttf");
+
+    fn shape(fonts: &[&'static [u8]], text: &str) -> ShapedText {
+        let fonts: Vec<Font> = fonts
+            .iter()
+            .map(|data| Font::from_bytes(data).unwrap())
+            .collect();
+        let shaping_fonts: Vec<ShapingFont> = fonts.iter().map(|f| f.shaping_font(1.0)).collect();
+
+        shape_text(&shaping_fonts, text)
+    }
+
+    fn clusters(shaped_text: &ShapedText) -> Vec<usize> {
+        shaped_text.glyphs.iter().map(|g| g.cluster).collect()
+    }
+
+    #[test]
+    fn test_shape_ltr() {
+        let shaped_text = shape(&[ROBOTO], "abc");
+
+        assert_eq!(clusters(&shaped_text), vec![0, 1, 2]);
+        assert!(shaped_text.glyphs[0].x < shaped_text.glyphs[1].x);
+        assert!(shaped_text.advance > 0.0);
+    }
+
+    #[test]
+    fn test_shape_bidi() {
+        // two hebrew letters (two bytes each) after a latin letter
+        let shaped_text = shape(&[ROBOTO], "a\u{5d0}\u{5d1}");
+
+        assert_eq!(clusters(&shaped_text), vec![0, 3, 1]);
+    }
+
+    #[test]
+    fn test_shape_fallback() {
+        // the icon is only contained in the icon font
+        let shaped_text = shape(&[ROBOTO, MATERIAL_ICONS], "a\u{e87c}");
+
+        assert_eq!(
+            shaped_text
+                .glyphs
+                .iter()
+                .map(|g| g.font)
+                .collect::<Vec<usize>>(),
+            vec![0, 1]
+        );
+    }
+
+    #[test]
+    fn test_char_advances() {
+        let text = "a\u{5d0}\u{5d1}";
+        let shaped_text = shape(&[ROBOTO], text);
+        let advances = shaped_text.char_advances(text);
+
+        assert_eq!(advances.len(), 3);
+        assert!((advances.iter().sum::<f32>() - shaped_text.advance).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_shape_empty() {
+        assert_eq!(shape(&[ROBOTO], ""), ShapedText::default());
+    }
+}
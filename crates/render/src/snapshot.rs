@@ -0,0 +1,143 @@
+use std::path::Path;
+
+use crate::{utils::*, RenderTarget};
+
+/// Describes the difference between two render targets, e.g. a rendered frame and a golden image.
+#[derive(Clone, Debug)]
+pub struct RenderTargetDiff {
+    /// Number of pixels whose difference is greater than the tolerance.
+    pub mismatched_pixels: usize,
+
+    /// Largest difference of a single color channel.
+    pub max_difference: u8,
+
+    /// Visualization of the difference. Mismatched pixels are red, all other pixels are dimmed.
+    pub image: RenderTarget,
+}
+
+impl RenderTarget {
+    /// Loads a render target from a png file.
+    pub fn from_png<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let image = image::open(path.as_ref())
+            .map_err(|e| format!("Could not load png {:?}: {}", path.as_ref(), e))?
+            .to_rgba();
+
+        let data = image
+            .pixels()
+            .map(|p| {
+                ((p[3] as u32) << 24) | ((p[0] as u32) << 16) | ((p[1] as u32) << 8) | (p[2] as u32)
+            })
+            .collect();
+
+        RenderTarget::from_data(image.width(), image.height(), data)
+    }
+
+    /// Saves the render target as png file.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let mut buffer = Vec::with_capacity(self.data.len() * 4);
+
+        for pixel in &self.data {
+            let color = Color { data: *pixel };
+            buffer.extend_from_slice(&[color.r(), color.g(), color.b(), color.a()]);
+        }
+
+        image::save_buffer(
+            path.as_ref(),
+            &buffer,
+            self.width() as u32,
+            self.height() as u32,
+            image::ColorType::Rgba8,
+        )
+        .map_err(|e| format!("Could not save png {:?}: {}", path.as_ref(), e))
+    }
+
+    /// Compares the render target pixel by pixel with the `other` one. Two pixels are equal if the
+    /// difference of each color channel is less or equal than the given `tolerance`.
+    ///
+    /// Returns `None` if both render targets are equal.
+    pub fn diff(&self, other: &RenderTarget, tolerance: u8) -> Option<RenderTargetDiff> {
+        if self.width() != other.width() || self.height() != other.height() {
+            return Some(RenderTargetDiff {
+                mismatched_pixels: self.data.len().max(other.data.len()),
+                max_difference: 255,
+                image: RenderTarget::from_data(
+                    other.width() as u32,
+                    other.height() as u32,
+                    vec![Color::rgb(255, 0, 0).data; other.data.len()],
+                )
+                .unwrap(),
+            });
+        }
+
+        let mut mismatched_pixels = 0;
+        let mut max_difference = 0;
+        let mut image = RenderTarget::new(self.width() as u32, self.height() as u32);
+
+        for (i, (expected, actual)) in self.data.iter().zip(other.data.iter()).enumerate() {
+            let expected = Color { data: *expected };
+            let actual = Color { data: *actual };
+
+            let difference = [
+                (expected.r() as i16 - actual.r() as i16).abs(),
+                (expected.g() as i16 - actual.g() as i16).abs(),
+                (expected.b() as i16 - actual.b() as i16).abs(),
+                (expected.a() as i16 - actual.a() as i16).abs(),
+            ]
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0) as u8;
+
+            max_difference = max_difference.max(difference);
+
+            image.data[i] = if difference > tolerance {
+                mismatched_pixels += 1;
+                Color::rgb(255, 0, 0).data
+            } else {
+                let gray =
+                    ((expected.r() as u32 + expected.g() as u32 + expected.b() as u32) / 3 / 4
+                        + 191) as u8;
+                Color::rgb(gray, gray, gray).data
+            };
+        }
+
+        if mismatched_pixels == 0 {
+            return None;
+        }
+
+        Some(RenderTargetDiff {
+            mismatched_pixels,
+            max_difference,
+            image,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let expected = RenderTarget::from_data(2, 1, vec![0xFF00_0000, 0xFF10_1010]).unwrap();
+        let actual = RenderTarget::from_data(2, 1, vec![0xFF00_0000, 0xFF12_1010]).unwrap();
+
+        assert!(expected.diff(&expected, 0).is_none());
+        assert!(expected.diff(&actual, 2).is_none());
+
+        let diff = expected.diff(&actual, 1).unwrap();
+        assert_eq!(diff.mismatched_pixels, 1);
+        assert_eq!(diff.max_difference, 2);
+        assert_eq!(diff.image.data[1], { Color::rgb(255, 0, 0).data });
+    }
+
+    #[test]
+    fn test_diff_size() {
+        let expected = RenderTarget::new(2, 2);
+        let actual = RenderTarget::new(1, 2);
+
+        let diff = expected.diff(&actual, 255).unwrap();
+        assert_eq!(diff.mismatched_pixels, 4);
+        assert_eq!(diff.image.width(), 1.0);
+    }
+}
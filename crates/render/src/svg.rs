@@ -0,0 +1,279 @@
+use std::{fs, path::Path};
+
+use usvg::NodeExt;
+
+use crate::{utils::*, FillRule, RenderContext2D};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum PathSegment {
+    MoveTo(Point),
+    LineTo(Point),
+    CurveTo(Point, Point, Point),
+    ClosePath,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct SvgPath {
+    segments: Vec<PathSegment>,
+    fill: Option<Color>,
+    fill_rule: FillRule,
+
+    // color and line width
+    stroke: Option<(Color, f64)>,
+}
+
+/// A vector graphic loaded from a SVG document. It is drawn as paths by `RenderContext2D`, so it
+/// stays sharp at every size. Gradients and patterns are not supported, such parts are skipped.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Svg {
+    width: f64,
+    height: f64,
+    view_box: Rectangle,
+    paths: Vec<SvgPath>,
+}
+
+impl Svg {
+    /// Parses a SVG document.
+    pub fn from_data(data: &[u8]) -> Result<Self, String> {
+        let tree = usvg::Tree::from_data(data, &usvg::Options::default())
+            .map_err(|e| format!("Could not parse svg: {}", e))?;
+
+        let svg_node = tree.svg_node();
+        let view_box = svg_node.view_box.rect;
+        let mut paths = vec![];
+
+        for node in tree.root().descendants() {
+            if let usvg::NodeKind::Path(ref path) = *node.borrow() {
+                let transform = node.abs_transform();
+
+                paths.push(SvgPath {
+                    segments: path.data.iter().map(|s| segment(s, &transform)).collect(),
+                    fill: path
+                        .fill
+                        .as_ref()
+                        .and_then(|fill| color(&fill.paint, fill.opacity.value())),
+                    fill_rule: match path.fill.as_ref().map(|fill| fill.rule) {
+                        Some(usvg::FillRule::EvenOdd) => FillRule::EvenOdd,
+                        _ => FillRule::NonZero,
+                    },
+                    stroke: path.stroke.as_ref().and_then(|stroke| {
+                        color(&stroke.paint, stroke.opacity.value())
+                            .map(|color| (color, stroke.width.value() * transform.get_scale().0))
+                    }),
+                });
+            }
+        }
+
+        Ok(Svg {
+            width: svg_node.size.width(),
+            height: svg_node.size.height(),
+            view_box: Rectangle::new(
+                view_box.x(),
+                view_box.y(),
+                view_box.width(),
+                view_box.height(),
+            ),
+            paths,
+        })
+    }
+
+    /// Loads a SVG document from file path.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let data = fs::read(path).map_err(|e| format!("Could not load svg: {}", e))?;
+        Self::from_data(&data)
+    }
+
+    /// Gets the width of the document.
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    /// Gets the height of the document.
+    pub fn height(&self) -> f64 {
+        self.height
+    }
+
+    /// Checks if the graphic has nothing to draw.
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    // Gets the scale and the offset that fit the view box into the rectangle like
+    // `preserveAspectRatio="xMidYMid meet"`.
+    fn fit(&self, rect: &Rectangle) -> (f64, Point) {
+        if self.view_box.width <= 0.0 || self.view_box.height <= 0.0 {
+            return (1.0, Point::new(rect.x, rect.y));
+        }
+
+        let scale = (rect.width / self.view_box.width).min(rect.height / self.view_box.height);
+
+        (
+            scale,
+            Point::new(
+                rect.x + (rect.width - self.view_box.width * scale) / 2.0 - self.view_box.x * scale,
+                rect.y + (rect.height - self.view_box.height * scale) / 2.0
+                    - self.view_box.y * scale,
+            ),
+        )
+    }
+
+    /// Draws the graphic scaled into the given rectangle keeping its aspect ratio. If a color is
+    /// given, all parts are drawn with it instead of their own colors, e.g. to recolor an icon.
+    pub fn render(&self, ctx: &mut RenderContext2D, rect: Rectangle, color: Option<Color>) {
+        let (scale, offset) = self.fit(&rect);
+        let point = |p: Point| (offset.x + p.x * scale, offset.y + p.y * scale);
+
+        // the opacity of the part is kept
+        let paint = |part: Color| match color {
+            Some(color) => Color::rgba(
+                color.r(),
+                color.g(),
+                color.b(),
+                (color.a() as u32 * part.a() as u32 / 255) as u8,
+            ),
+            None => part,
+        };
+
+        // the fill rule, line width and styles of the context are kept
+        ctx.save();
+
+        for path in &self.paths {
+            ctx.begin_path();
+
+            for segment in &path.segments {
+                match *segment {
+                    PathSegment::MoveTo(p) => {
+                        let (x, y) = point(p);
+                        ctx.move_to(x, y);
+                    }
+                    PathSegment::LineTo(p) => {
+                        let (x, y) = point(p);
+                        ctx.line_to(x, y);
+                    }
+                    PathSegment::CurveTo(c1, c2, p) => {
+                        let ((c1x, c1y), (c2x, c2y), (x, y)) = (point(c1), point(c2), point(p));
+                        ctx.bezier_curve_to(c1x, c1y, c2x, c2y, x, y);
+                    }
+                    PathSegment::ClosePath => ctx.close_path(),
+                }
+            }
+
+            if let Some(fill) = path.fill {
+                ctx.set_fill_rule(path.fill_rule);
+                ctx.set_fill_style(Brush::from(paint(fill)));
+                ctx.fill();
+            }
+
+            if let Some((stroke, line_width)) = path.stroke {
+                ctx.set_line_width(line_width * scale);
+                ctx.set_stroke_style(Brush::from(paint(stroke)));
+                ctx.stroke();
+            }
+        }
+
+        ctx.restore();
+    }
+}
+
+// Converts a segment of the document to absolute coordinates.
+fn segment(segment: &usvg::PathSegment, transform: &usvg::Transform) -> PathSegment {
+    let point = |x: f64, y: f64| {
+        let (x, y) = transform.apply(x, y);
+        Point::new(x, y)
+    };
+
+    match *segment {
+        usvg::PathSegment::MoveTo { x, y } => PathSegment::MoveTo(point(x, y)),
+        usvg::PathSegment::LineTo { x, y } => PathSegment::LineTo(point(x, y)),
+        usvg::PathSegment::CurveTo {
+            x1,
+            y1,
+            x2,
+            y2,
+            x,
+            y,
+        } => PathSegment::CurveTo(point(x1, y1), point(x2, y2), point(x, y)),
+        usvg::PathSegment::ClosePath => PathSegment::ClosePath,
+    }
+}
+
+// Converts a solid paint of the document. Gradients and patterns are skipped.
+fn color(paint: &usvg::Paint, opacity: f64) -> Option<Color> {
+    match paint {
+        usvg::Paint::Color(c) => Some(Color::rgba(
+            c.red,
+            c.green,
+            c.blue,
+            (opacity * 255.0).round() as u8,
+        )),
+        _ => None,
+    }
+}
+
+// --- Conversions ---
+
+impl From<&str> for Svg {
+    /// Loads the document from the given file path. An empty graphic is used if it could not be
+    /// loaded.
+    fn from(s: &str) -> Svg {
+        Svg::from_path(s).unwrap_or_default()
+    }
+}
+
+impl From<String> for Svg {
+    fn from(s: String) -> Svg {
+        Svg::from(s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ICON: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="24" height="12" viewBox="0 0 24 12">
+        <rect x="2" y="2" width="4" height="4" fill="#ff0000"/>
+        <path d="M 8 0 H 20 V 12 H 8 Z M 12 4 H 16 V 8 H 12 Z" fill="#0000ff" fill-rule="evenodd"/>
+    </svg>"##;
+
+    #[test]
+    fn test_from_data() {
+        let svg = Svg::from_data(ICON.as_bytes()).unwrap();
+
+        assert_eq!((svg.width(), svg.height()), (24.0, 12.0));
+        assert_eq!(svg.paths.len(), 2);
+        assert_eq!(svg.paths[0].fill, Some(Color::rgb(255, 0, 0)));
+        assert_eq!(svg.paths[0].fill_rule, FillRule::NonZero);
+        assert_eq!(svg.paths[1].fill_rule, FillRule::EvenOdd);
+        assert_eq!(
+            svg.paths[0].segments[0],
+            PathSegment::MoveTo(Point::new(2.0, 2.0))
+        );
+        assert!(Svg::from_data(b"no svg").is_err());
+    }
+
+    #[test]
+    fn test_fit() {
+        let svg = Svg::from_data(ICON.as_bytes()).unwrap();
+        let (scale, offset) = svg.fit(&Rectangle::new(10.0, 10.0, 48.0, 48.0));
+
+        assert_eq!(scale, 2.0);
+        assert_eq!(offset, Point::new(10.0, 22.0));
+    }
+
+    #[test]
+    fn test_render_even_odd() {
+        let svg = Svg::from_data(ICON.as_bytes()).unwrap();
+        let mut ctx = RenderContext2D::new(24.0, 12.0);
+        ctx.start();
+        svg.render(&mut ctx, Rectangle::new(0.0, 0.0, 24.0, 12.0), None);
+        ctx.finish();
+        ctx.finish_receiver().recv().unwrap();
+
+        let data = ctx.data().unwrap().to_vec();
+        let alpha = |x: usize, y: usize| data[y * 24 + x] >> 24;
+
+        // the inner square of the second path is cut out
+        assert_ne!(alpha(10, 2), 0);
+        assert_eq!(alpha(14, 6), 0);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::utils::*;
+
+/// Appended to the last line if a text is truncated.
+pub const ELLIPSIS: &str = "…";
+
+/// Describes how a text is broken into lines by `layout_text`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextLayoutConfig {
+    /// Width the lines are wrapped at and aligned in. With `f64::MAX` the lines are aligned in
+    /// the width of the widest line.
+    pub max_width: f64,
+
+    /// Distance between the tops of two lines in pixels.
+    pub line_height: f64,
+    pub text_align: TextAlignment,
+    pub text_wrap: TextWrap,
+
+    /// Maximum number of lines, `0` means unlimited. The last line of a truncated text ends with
+    /// an ellipsis.
+    pub max_lines: usize,
+}
+
+impl Default for TextLayoutConfig {
+    fn default() -> Self {
+        TextLayoutConfig {
+            max_width: std::f64::MAX,
+            line_height: 0.0,
+            text_align: TextAlignment::default(),
+            text_wrap: TextWrap::default(),
+            max_lines: 0,
+        }
+    }
+}
+
+/// Describes a line of a laid out text.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextLine {
+    pub text: String,
+
+    /// Position of the line relative to the top left of the text.
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+}
+
+/// The lines of a laid out text.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextLayout {
+    pub lines: Vec<TextLine>,
+
+    /// Width of the widest line.
+    pub width: f64,
+
+    /// Height of all lines.
+    pub height: f64,
+}
+
+/// Breaks the text into lines at line breaks and, if wrapping is enabled, at word boundaries.
+/// `measure` returns the width of a text in pixels.
+pub fn layout_text<F>(text: &str, config: &TextLayoutConfig, mut measure: F) -> TextLayout
+where
+    F: FnMut(&str) -> f64,
+{
+    let mut lines: Vec<(String, f64)> = vec![];
+
+    for paragraph in text.split('\n') {
+        let paragraph = paragraph.trim_end_matches('\r');
+
+        if config.text_wrap == TextWrap::Wrap {
+            wrap_paragraph(paragraph, config.max_width, &mut measure, &mut lines);
+        } else {
+            lines.push((paragraph.to_string(), measure(paragraph)));
+        }
+    }
+
+    if config.max_lines > 0 {
+        let truncated = lines.len() > config.max_lines;
+        lines.truncate(config.max_lines);
+
+        if let Some(last) = lines.last_mut() {
+            if truncated || last.1 > config.max_width {
+                *last = ellipsize(&last.0, config.max_width, &mut measure);
+            }
+        }
+    }
+
+    let width = lines.iter().map(|(_, width)| *width).fold(0.0, f64::max);
+    let align_width = if config.max_width < std::f64::MAX {
+        config.max_width
+    } else {
+        width
+    };
+
+    TextLayout {
+        height: lines.len() as f64 * config.line_height,
+        width,
+        lines: lines
+            .into_iter()
+            .enumerate()
+            .map(|(index, (text, width))| TextLine {
+                x: match config.text_align {
+                    TextAlignment::Right | TextAlignment::End => align_width - width,
+                    TextAlignment::Center => (align_width - width) / 2.0,
+                    _ => 0.0,
+                },
+                y: index as f64 * config.line_height,
+                text,
+                width,
+            })
+            .collect(),
+    }
+}
+
+// Greedily fills the lines with the words of the paragraph.
+fn wrap_paragraph<F>(
+    paragraph: &str,
+    max_width: f64,
+    measure: &mut F,
+    lines: &mut Vec<(String, f64)>,
+) where
+    F: FnMut(&str) -> f64,
+{
+    let mut line = String::new();
+    let mut line_width = 0.0;
+
+    for (index, word) in paragraph.split(' ').enumerate() {
+        let candidate = if index == 0 {
+            word.to_string()
+        } else {
+            format!("{} {}", line, word)
+        };
+        let width = measure(&candidate);
+
+        if width <= max_width {
+            line = candidate;
+            line_width = width;
+            continue;
+        }
+
+        if index > 0 && !line.is_empty() {
+            lines.push((line, line_width));
+        }
+
+        let (rest, rest_width) = break_word(word, max_width, measure, lines);
+        line = rest;
+        line_width = rest_width;
+    }
+
+    lines.push((line, line_width));
+}
+
+// Pushes the parts of a word that do not fit into a line. Returns the rest of the word.
+fn break_word<F>(
+    word: &str,
+    max_width: f64,
+    measure: &mut F,
+    lines: &mut Vec<(String, f64)>,
+) -> (String, f64)
+where
+    F: FnMut(&str) -> f64,
+{
+    let mut part = String::new();
+    let mut part_width = 0.0;
+
+    for c in word.chars() {
+        let candidate = format!("{}{}", part, c);
+        let width = measure(&candidate);
+
+        if width > max_width && !part.is_empty() {
+            lines.push((part, part_width));
+            part = c.to_string();
+            part_width = measure(&part);
+        } else {
+            part = candidate;
+            part_width = width;
+        }
+    }
+
+    (part, part_width)
+}
+
+// Removes characters from the end of the line until it fits with an ellipsis.
+fn ellipsize<F>(line: &str, max_width: f64, measure: &mut F) -> (String, f64)
+where
+    F: FnMut(&str) -> f64,
+{
+    let mut chars: Vec<char> = line.trim_end().chars().collect();
+
+    loop {
+        let candidate = format!("{}{}", chars.iter().collect::<String>(), ELLIPSIS);
+        let width = measure(&candidate);
+
+        if width <= max_width || chars.is_empty() {
+            return (candidate, width);
+        }
+
+        chars.pop();
+        while chars.last() == Some(&' ') {
+            chars.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // every character is 10 pixels wide
+    fn measure(text: &str) -> f64 {
+        text.chars().count() as f64 * 10.0
+    }
+
+    fn texts(layout: &TextLayout) -> Vec<&str> {
+        layout.lines.iter().map(|l| l.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_line_breaks() {
+        let config = TextLayoutConfig {
+            line_height: 20.0,
+            ..Default::default()
+        };
+        let layout = layout_text("one\ntwo lines", &config, measure);
+
+        assert_eq!(texts(&layout), vec!["one", "two lines"]);
+        assert_eq!(layout.width, 90.0);
+        assert_eq!(layout.height, 40.0);
+        assert_eq!(layout.lines[1].y, 20.0);
+    }
+
+    #[test]
+    fn test_wrap() {
+        let config = TextLayoutConfig {
+            max_width: 100.0,
+            line_height: 20.0,
+            text_wrap: TextWrap::Wrap,
+            ..Default::default()
+        };
+        let layout = layout_text("the quick brown fox jumps", &config, measure);
+        assert_eq!(texts(&layout), vec!["the quick", "brown fox", "jumps"]);
+
+        let layout = layout_text("abcdefghijklmnop", &config, measure);
+        assert_eq!(texts(&layout), vec!["abcdefghij", "klmnop"]);
+    }
+
+    #[test]
+    fn test_alignment() {
+        let config = TextLayoutConfig {
+            max_width: 100.0,
+            text_align: TextAlignment::Center,
+            ..Default::default()
+        };
+        let layout = layout_text("abc\nabcde", &config, measure);
+
+        assert_eq!(layout.lines[0].x, 35.0);
+        assert_eq!(layout.lines[1].x, 25.0);
+    }
+
+    #[test]
+    fn test_max_lines() {
+        let config = TextLayoutConfig {
+            max_width: 100.0,
+            text_wrap: TextWrap::Wrap,
+            max_lines: 2,
+            ..Default::default()
+        };
+        let layout = layout_text("the quick brown fox jumps", &config, measure);
+
+        assert_eq!(texts(&layout), vec!["the quick", "brown fox…"]);
+        assert_eq!(layout.lines[1].width, 100.0);
+    }
+}
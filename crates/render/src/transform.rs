@@ -0,0 +1,161 @@
+use crate::utils::*;
+
+/// Describes a 2D transformation matrix with the same parameters as `set_transform` of the render context.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    pub h_scaling: f64,
+    pub h_skewing: f64,
+    pub v_skewing: f64,
+    pub v_scaling: f64,
+    pub h_moving: f64,
+    pub v_moving: f64,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::new(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+}
+
+impl Transform {
+    /// Creates a new transformation matrix.
+    pub fn new(
+        h_scaling: f64,
+        h_skewing: f64,
+        v_skewing: f64,
+        v_scaling: f64,
+        h_moving: f64,
+        v_moving: f64,
+    ) -> Self {
+        Transform {
+            h_scaling,
+            h_skewing,
+            v_skewing,
+            v_scaling,
+            h_moving,
+            v_moving,
+        }
+    }
+
+    /// Returns the transformation that moves by `x` and `y` before applying this transformation.
+    pub fn translate(&self, x: f64, y: f64) -> Self {
+        Transform {
+            h_moving: self.h_scaling * x + self.v_skewing * y + self.h_moving,
+            v_moving: self.h_skewing * x + self.v_scaling * y + self.v_moving,
+            ..*self
+        }
+    }
+
+    /// Returns the transformation that scales by `x` and `y` before applying this transformation.
+    pub fn scale(&self, x: f64, y: f64) -> Self {
+        Transform {
+            h_scaling: self.h_scaling * x,
+            h_skewing: self.h_skewing * x,
+            v_skewing: self.v_skewing * y,
+            v_scaling: self.v_scaling * y,
+            ..*self
+        }
+    }
+
+    /// Returns the transformation that rotates clockwise by the given angle in radians before
+    /// applying this transformation.
+    pub fn rotate(&self, angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+
+        Transform {
+            h_scaling: self.h_scaling * cos + self.v_skewing * sin,
+            h_skewing: self.h_skewing * cos + self.v_scaling * sin,
+            v_skewing: self.v_skewing * cos - self.h_scaling * sin,
+            v_scaling: self.v_scaling * cos - self.h_skewing * sin,
+            ..*self
+        }
+    }
+
+    /// Returns the transformation that scales by `x` and `y` after applying this transformation,
+    /// e.g. to map logical to physical pixels.
+    pub fn then_scale(&self, x: f64, y: f64) -> Self {
+        Transform {
+            h_scaling: self.h_scaling * x,
+            h_skewing: self.h_skewing * y,
+            v_skewing: self.v_skewing * x,
+            v_scaling: self.v_scaling * y,
+            h_moving: self.h_moving * x,
+            v_moving: self.v_moving * y,
+        }
+    }
+
+    /// Checks if the transformation only moves, e.g. it neither rotates, skews nor scales.
+    pub fn is_translation(&self) -> bool {
+        self.h_scaling == 1.0
+            && self.h_skewing == 0.0
+            && self.v_skewing == 0.0
+            && self.v_scaling == 1.0
+    }
+
+    /// Transforms the given point.
+    pub fn transform_point(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            self.h_scaling * x + self.v_skewing * y + self.h_moving,
+            self.h_skewing * x + self.v_scaling * y + self.v_moving,
+        )
+    }
+
+    /// Gets the bounding box of the transformed rectangle.
+    pub fn transform_rect(&self, rect: &Rectangle) -> Rectangle {
+        let corners = [
+            self.transform_point(rect.x, rect.y),
+            self.transform_point(rect.x + rect.width, rect.y),
+            self.transform_point(rect.x, rect.y + rect.height),
+            self.transform_point(rect.x + rect.width, rect.y + rect.height),
+        ];
+
+        let (mut min_x, mut min_y) = corners[0];
+        let (mut max_x, mut max_y) = corners[0];
+
+        for &(x, y) in &corners[1..] {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+
+        Rectangle::new(min_x, min_y, max_x - min_x, max_y - min_y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_scale() {
+        let transform = Transform::default().translate(10.0, 20.0).scale(2.0, 3.0);
+
+        assert_eq!(transform.transform_point(1.0, 1.0), (12.0, 23.0));
+        assert!(!transform.is_translation());
+        assert!(Transform::default().translate(10.0, 20.0).is_translation());
+        assert_eq!(
+            transform.transform_rect(&Rectangle::new(0.0, 0.0, 5.0, 5.0)),
+            Rectangle::new(10.0, 20.0, 10.0, 15.0)
+        );
+    }
+
+    #[test]
+    fn test_then_scale() {
+        let transform = Transform::default()
+            .translate(10.0, 20.0)
+            .scale(2.0, 3.0)
+            .then_scale(2.0, 2.0);
+
+        assert_eq!(transform.transform_point(1.0, 1.0), (24.0, 46.0));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let transform = Transform::default().rotate(std::f64::consts::FRAC_PI_2);
+        let (x, y) = transform.transform_point(1.0, 0.0);
+
+        assert!(x.abs() < 1e-9);
+        assert!((y - 1.0).abs() < 1e-9);
+    }
+}
@@ -59,6 +59,32 @@ impl Image {
         Ok(Image { source })
     }
 
+    /// Loads the image from file path. The browser loads images in the background, so it is the
+    /// same as `from_path`.
+    pub fn load<P: std::string::ToString + AsRef<Path>>(path: P) -> Result<Self, String> {
+        Self::from_path(path)
+    }
+
+    /// Images are drawn by the browser as soon as they are loaded, so it is always `false`.
+    pub fn is_loading(&self) -> bool {
+        false
+    }
+
+    /// Images are drawn by the browser as soon as they are loaded, so it is always `false`.
+    pub fn is_decoded(&self) -> bool {
+        false
+    }
+
+    /// Images are drawn by the browser as soon as they are loaded, so there is nothing to take.
+    pub fn poll(&mut self) -> bool {
+        false
+    }
+
+    /// Images are drawn by the browser as soon as they are loaded, so the function is called right away.
+    pub fn on_decoded<F: FnOnce() + Send + 'static>(&self, on_decoded: F) {
+        on_decoded();
+    }
+
     /// Draws a u32 slice into the image.
     pub fn draw(&mut self, _data: &[u32]) {
         // todo
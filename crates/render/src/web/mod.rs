@@ -1,11 +1,16 @@
+use std::collections::HashMap;
+
 use stdweb::{
     js,
     unstable::TryInto,
-    web::{document, html_element::CanvasElement, CanvasRenderingContext2d, FillRule},
+    web::{document, html_element::CanvasElement, CanvasGradient, CanvasRenderingContext2d},
 };
 
 // pub use crate::image::Image as InnerImage;
-use crate::{utils::*, FontConfig, Pipeline, RenderConfig, RenderTarget, TextMetrics};
+use crate::{
+    line_dash, utils::*, FillRule, FontConfig, LineCap, LineJoin, Pipeline, RenderConfig,
+    RenderTarget, TextMetrics, Transform,
+};
 
 pub use self::image::*;
 
@@ -15,10 +20,20 @@ mod image;
 pub struct RenderContext2D {
     canvas_render_context_2_d: CanvasRenderingContext2d,
     font_config: FontConfig,
+    font_fallbacks: Vec<String>,
     config: RenderConfig,
-    saved_config: Option<RenderConfig>,
+    saved_configs: Vec<RenderConfig>,
+
+    // images of image pattern brushes by source
+    images: HashMap<String, Image>,
     export_data: Vec<u32>,
     background: Color,
+
+    // true if a region is repainted
+    region: bool,
+
+    // physical pixels per logical pixel, all drawing is given in logical pixels
+    scale_factor: f64,
 }
 
 impl RenderContext2D {
@@ -39,11 +54,15 @@ impl RenderContext2D {
         ctx.set_text_baseline(stdweb::web::TextBaseline::Middle);
         RenderContext2D {
             config: RenderConfig::default(),
-            saved_config: None,
+            saved_configs: vec![],
+            images: HashMap::new(),
             canvas_render_context_2_d: ctx,
             font_config: FontConfig::default(),
+            font_fallbacks: vec![],
             export_data,
             background: Color::default(),
+            region: false,
+            scale_factor: 1.0,
         }
     }
 
@@ -63,14 +82,41 @@ impl RenderContext2D {
         canvas_render_context_2_d.set_text_baseline(stdweb::web::TextBaseline::Middle);
         RenderContext2D {
             config: RenderConfig::default(),
-            saved_config: None,
+            saved_configs: vec![],
+            images: HashMap::new(),
             canvas_render_context_2_d,
             font_config: FontConfig::default(),
+            font_fallbacks: vec![],
             export_data,
             background: Color::default(),
+            region: false,
+            scale_factor: 1.0,
         }
     }
 
+    /// Sets the number of physical pixels per logical pixel, e.g. the device pixel ratio of the
+    /// browser. The canvas has to be sized in physical pixels.
+    pub fn set_scale_factor(&mut self, scale_factor: f64) {
+        if scale_factor <= 0.0 {
+            return;
+        }
+
+        self.scale_factor = scale_factor;
+        self.canvas_render_context_2_d.set_transform(
+            scale_factor,
+            0.0,
+            0.0,
+            scale_factor,
+            0.0,
+            0.0,
+        );
+    }
+
+    /// Gets the number of physical pixels per logical pixel.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
     // Rectangles
 
     /// Draws a filled rectangle whose starting point is at the coordinates {x, y} with the
@@ -83,7 +129,7 @@ impl RenderContext2D {
 
     /// Draws a rectangle that is stroked (outlined) according to the current strokeStyle and other ctx settings.
     pub fn stroke_rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
-        self.stroke_style(&self.config.fill_style);
+        self.stroke_style(&self.config.stroke_style);
         self.canvas_render_context_2_d
             .stroke_rect(x, y, width, height);
     }
@@ -126,10 +172,38 @@ impl RenderContext2D {
         }
     }
 
+    /// Returns the advance of each character of the text with the given font. The sum of the
+    /// advances is the width of the text.
+    pub fn measure_advances(
+        &mut self,
+        text: &str,
+        font_size: f64,
+        family: impl Into<String>,
+    ) -> Vec<f64> {
+        self.set_font_family(family);
+        self.set_font_size(font_size);
+
+        // prefixes are measured, so the kerning between the characters is respected
+        let mut advances = vec![];
+        let mut last = 0.0;
+
+        for (index, c) in text.char_indices() {
+            let width = self.measure_text(&text[..index + c.len_utf8()]).width;
+            advances.push(width - last);
+            last = width;
+        }
+
+        advances
+    }
+
     /// Fills the current or given path with the current file style.
     pub fn fill(&mut self) {
         self.fill_style(&self.config.fill_style);
-        self.canvas_render_context_2_d.fill(FillRule::default());
+        self.canvas_render_context_2_d
+            .fill(match self.config.fill_rule {
+                FillRule::NonZero => stdweb::web::FillRule::NonZero,
+                FillRule::EvenOdd => stdweb::web::FillRule::EvenOdd,
+            });
     }
 
     /// Strokes {outlines} the current or given path with the current stroke style.
@@ -138,6 +212,45 @@ impl RenderContext2D {
         self.canvas_render_context_2_d.stroke();
     }
 
+    /// Fills the current path with the given color blurred by the blur radius, e.g. to draw the
+    /// shadow of a shape. The fill style is not changed.
+    pub fn fill_shadow(&mut self, color: Color, blur: f64) {
+        // the css blur filter takes the standard deviation, which is half of the blur radius
+        let filter = format!("blur({}px)", blur / 2.0 * self.scale_factor);
+
+        js!(
+            var ctx = @{&self.canvas_render_context_2_d};
+            ctx.save();
+            ctx.filter = @{filter};
+            ctx.fillStyle = @{color.to_string()};
+            ctx.fill();
+            ctx.restore();
+        );
+    }
+
+    /// Blurs the pixels of the given rectangle that are already drawn, e.g. for a frosted glass
+    /// effect behind a widget. Only the pixels inside of the current clip are changed.
+    pub fn blur_rect(&mut self, x: f64, y: f64, width: f64, height: f64, radius: f64) {
+        if radius <= 0.0 {
+            return;
+        }
+
+        let filter = format!("blur({}px)", radius / 2.0 * self.scale_factor);
+
+        // the canvas is drawn blurred onto itself, clipped to the rectangle
+        js!(
+            var ctx = @{&self.canvas_render_context_2_d};
+            var region = new Path2D();
+            region.rect(@{x}, @{y}, @{width}, @{height});
+            ctx.save();
+            ctx.clip(region);
+            ctx.setTransform(1, 0, 0, 1, 0, 0);
+            ctx.filter = @{filter};
+            ctx.drawImage(ctx.canvas, 0, 0);
+            ctx.restore();
+        );
+    }
+
     /// Starts a new path by emptying the list of sub-paths. Call this when you want to create a new path.
     pub fn begin_path(&mut self) {
         self.canvas_render_context_2_d.begin_path();
@@ -228,6 +341,32 @@ impl RenderContext2D {
         );
     }
 
+    /// Draws the given part of the image scaled to the given size.
+    pub fn draw_image_with_clip_and_size(
+        &mut self,
+        image: &Image,
+        clip: Rectangle,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) {
+        js!(
+            var img = document.image_store.image(@{&image.source});
+
+            if(img == null) {
+                img = document.image_store.load_image(@{&image.source});
+                img.then(
+                    function(i) {
+                         @{&self.canvas_render_context_2_d}.drawImage(i, @{&clip.x}, @{&clip.y}, @{&clip.width}, @{&clip.height}, @{&x}, @{&y}, @{&width}, @{&height});
+                    }
+                )
+            } else {
+                 @{&self.canvas_render_context_2_d}.drawImage(img, @{&clip.x}, @{&clip.y}, @{&clip.width}, @{&clip.height}, @{&x}, @{&y}, @{&width}, @{&height});
+            }
+        );
+    }
+
     pub fn draw_pipeline(
         &mut self,
         x: f64,
@@ -288,7 +427,8 @@ impl RenderContext2D {
 
     /// Creates a clipping path from the current sub-paths. Everything drawn after clip() is called appears inside the clipping path only.
     pub fn clip(&mut self) {
-        self.canvas_render_context_2_d.clip(FillRule::EvenOdd);
+        self.canvas_render_context_2_d
+            .clip(stdweb::web::FillRule::EvenOdd);
     }
 
     // Line styles
@@ -299,6 +439,56 @@ impl RenderContext2D {
         self.canvas_render_context_2_d.set_line_width(line_width);
     }
 
+    /// Sets the dash pattern of lines as list of alternating line and gap lengths. An empty list
+    /// draws solid lines.
+    pub fn set_line_dash(&mut self, segments: &[f64]) {
+        if let Some(line_dash) = line_dash(segments) {
+            self.config.line_dash = line_dash.clone();
+            self.canvas_render_context_2_d.set_line_dash(line_dash);
+        }
+    }
+
+    /// Sets the offset at which the dash pattern of lines starts.
+    pub fn set_line_dash_offset(&mut self, offset: f64) {
+        self.config.line_dash_offset = offset;
+        self.canvas_render_context_2_d.set_line_dash_offset(offset);
+    }
+
+    /// Sets how the end points of lines are drawn.
+    pub fn set_line_cap(&mut self, line_cap: LineCap) {
+        self.config.line_cap = line_cap;
+        self.canvas_render_context_2_d.set_line_cap(match line_cap {
+            LineCap::Butt => stdweb::web::LineCap::Butt,
+            LineCap::Round => stdweb::web::LineCap::Round,
+            LineCap::Square => stdweb::web::LineCap::Square,
+        });
+    }
+
+    /// Sets how two connected segments of a line are joined.
+    pub fn set_line_join(&mut self, line_join: LineJoin) {
+        self.config.line_join = line_join;
+        self.canvas_render_context_2_d
+            .set_line_join(match line_join {
+                LineJoin::Miter => stdweb::web::LineJoin::Miter,
+                LineJoin::Round => stdweb::web::LineJoin::Round,
+                LineJoin::Bevel => stdweb::web::LineJoin::Bevel,
+            });
+    }
+
+    /// Sets which areas inside of a path are filled by `fill`.
+    pub fn set_fill_rule(&mut self, fill_rule: FillRule) {
+        self.config.fill_rule = fill_rule;
+    }
+
+    /// Sets the limit of the ratio between the miter length and the line width. Longer miters are
+    /// drawn beveled.
+    pub fn set_miter_limit(&mut self, miter_limit: f64) {
+        if miter_limit > 0.0 {
+            self.config.miter_limit = miter_limit;
+            self.canvas_render_context_2_d.set_miter_limit(miter_limit);
+        }
+    }
+
     /// Sets the alpha value,
     pub fn set_alpha(&mut self, alpha: f32) {
         self.canvas_render_context_2_d
@@ -308,29 +498,78 @@ impl RenderContext2D {
     /// Specific the font family.
     pub fn set_font_family(&mut self, family: impl Into<String>) {
         self.font_config.family = family.into();
-        self.canvas_render_context_2_d
-            .set_font(&self.font_config.to_string());
+        self.update_font();
     }
 
     /// Specifies the font size.
     pub fn set_font_size(&mut self, size: f64) {
         self.font_config.font_size = size;
-        self.canvas_render_context_2_d
-            .set_font(&self.font_config.to_string());
+        self.update_font();
+    }
+
+    /// Specifies the font weight.
+    pub fn set_font_weight(&mut self, weight: FontWeight) {
+        self.font_config.weight = weight;
+        self.update_font();
+    }
+
+    /// Specifies the font style.
+    pub fn set_font_style(&mut self, style: FontStyle) {
+        self.font_config.style = style;
+        self.update_font();
+    }
+
+    /// Specifies the font stretch.
+    pub fn set_font_stretch(&mut self, stretch: FontStretch) {
+        self.font_config.stretch = stretch;
+        self.update_font();
+    }
+
+    /// Sets the font families that are used in the given order for glyphs that are missing in
+    /// the selected font, e.g. for CJK characters or emojis.
+    pub fn set_font_fallbacks(&mut self, families: Vec<String>) {
+        self.font_fallbacks = families;
+        self.update_font();
+    }
+
+    // The browser picks the fallbacks from the family list of the css font.
+    fn update_font(&mut self) {
+        let mut font = self.font_config.to_string();
+
+        for family in &self.font_fallbacks {
+            font.push_str(&format!(", \"{}\"", family));
+        }
+
+        self.canvas_render_context_2_d.set_font(&font);
     }
 
     // Fill and stroke style
 
     /// Specifies the fill color to use inside shapes.
     pub fn set_fill_style(&mut self, fill_style: Brush) {
+        self.load_pattern_image(&fill_style);
         self.config.fill_style = fill_style;
     }
 
     /// Specifies the fill stroke to use inside shapes.
     pub fn set_stroke_style(&mut self, stroke_style: Brush) {
+        self.load_pattern_image(&stroke_style);
         self.config.stroke_style = stroke_style;
     }
 
+    // Starts to load the image of an image pattern brush once.
+    fn load_pattern_image(&mut self, brush: &Brush) {
+        if let Brush::ImagePattern { source, .. } = brush {
+            if self.images.contains_key(source) {
+                return;
+            }
+
+            if let Ok(image) = Image::from_path(source.as_str()) {
+                self.images.insert(source.clone(), image);
+            }
+        }
+    }
+
     // Transformations
 
     /// Sets the transformation.
@@ -343,40 +582,70 @@ impl RenderContext2D {
         h_moving: f64,
         v_moving: f64,
     ) {
-        self.canvas_render_context_2_d.set_transform(
+        // the transformation is given in logical pixels
+        let t = Transform::new(
             h_scaling, h_skewing, v_skewing, v_scaling, h_moving, v_moving,
+        )
+        .then_scale(self.scale_factor, self.scale_factor);
+
+        self.canvas_render_context_2_d.set_transform(
+            t.h_scaling,
+            t.h_skewing,
+            t.v_skewing,
+            t.v_scaling,
+            t.h_moving,
+            t.v_moving,
         );
     }
 
+    /// Adds a translation to the current transformation.
+    pub fn translate(&mut self, x: f64, y: f64) {
+        self.canvas_render_context_2_d.translate(x, y);
+    }
+
+    /// Adds a scaling to the current transformation.
+    pub fn scale(&mut self, x: f64, y: f64) {
+        self.canvas_render_context_2_d.scale(x, y);
+    }
+
+    /// Adds a clockwise rotation by the given angle in radians to the current transformation.
+    pub fn rotate(&mut self, angle: f64) {
+        self.canvas_render_context_2_d.rotate(angle);
+    }
+
     // Canvas states
 
     /// Saves the entire state of the canvas by pushing the current state onto a stack.
+    /// The transformation and the clipping region are stored by the canvas itself.
     pub fn save(&mut self) {
-        self.saved_config = Some(self.config.clone());
+        self.saved_configs.push(self.config.clone());
         self.canvas_render_context_2_d.save();
     }
 
     /// Restores the most recently saved canvas state by popping the top entry in the drawing state stack.
     /// If there is no saved state, this method does nothing.
     pub fn restore(&mut self) {
-        self.canvas_render_context_2_d.restore();
-        if let Some(config) = &self.saved_config {
-            self.config = config.clone();
+        if let Some(config) = self.saved_configs.pop() {
+            self.config = config;
+            self.canvas_render_context_2_d.restore();
         }
-
-        self.saved_config = None;
     }
 
+    /// Replaces all pixels of the render context by the given brush.
     pub fn clear(&mut self, brush: &Brush) {
-        let color = match brush {
-            Brush::SolidColor(color) => color.to_string(),
-            _ => Color::rgba(0, 0, 0, 0).to_string(),
-        };
+        self.load_pattern_image(brush);
 
         self.save();
         self.canvas_render_context_2_d
-            .set_fill_style_color(color.as_str());
+            .set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0);
         let canvas = self.canvas_render_context_2_d.get_canvas();
+        self.canvas_render_context_2_d.clear_rect(
+            0.0,
+            0.0,
+            canvas.width() as f64,
+            canvas.height() as f64,
+        );
+        self.fill_style(brush);
         self.canvas_render_context_2_d.fill_rect(
             0.0,
             0.0,
@@ -391,6 +660,7 @@ impl RenderContext2D {
         canvas_render_context_2_d: CanvasRenderingContext2d,
     ) {
         self.canvas_render_context_2_d = canvas_render_context_2_d;
+        self.set_scale_factor(self.scale_factor);
     }
 
     pub fn data(&mut self) -> &[u32] {
@@ -453,52 +723,124 @@ impl RenderContext2D {
         let background = Brush::from(self.background);
         self.clear(&background)
     }
-    pub fn finish(&mut self) {}
 
-    fn fill_style<'a>(&self, brush: &Brush) {
+    /// Starts a new frame that only repaints the given region. The pixels outside of the region
+    /// are kept from the last frame and all drawing is clipped to the region.
+    pub fn start_region(&mut self, region: Rectangle) {
+        self.end_region();
+        self.canvas_render_context_2_d.save();
+        self.canvas_render_context_2_d.begin_path();
+        self.canvas_render_context_2_d
+            .rect(region.x, region.y, region.width, region.height);
+        self.canvas_render_context_2_d
+            .clip(stdweb::web::FillRule::EvenOdd);
+        self.canvas_render_context_2_d
+            .clear_rect(region.x, region.y, region.width, region.height);
+        self.canvas_render_context_2_d
+            .set_fill_style_color(&self.background.to_string());
+        self.canvas_render_context_2_d
+            .fill_rect(region.x, region.y, region.width, region.height);
+        self.region = true;
+    }
+
+    pub fn finish(&mut self) {
+        self.end_region();
+    }
+
+    // Removes the clip of the current region.
+    fn end_region(&mut self) {
+        if self.region {
+            self.canvas_render_context_2_d.restore();
+            self.region = false;
+        }
+    }
+
+    fn fill_style(&self, brush: &Brush) {
         match brush {
             Brush::SolidColor(color) => {
                 self.canvas_render_context_2_d
                     .set_fill_style_color(&color.to_string());
             }
-            Brush::LinearGradient { start, end, stops } => {
-                let web_gradient = self
-                    .canvas_render_context_2_d
-                    .create_linear_gradient(start.x, start.y, end.x, end.y);
-
-                for stop in stops {
-                    web_gradient
-                        .add_color_stop(stop.position, stop.color.to_string().as_str())
-                        .unwrap();
+            Brush::ImagePattern { source, origin } => {
+                self.pattern_style("fillStyle", source, origin);
+            }
+            _ => {
+                if let Some(gradient) = self.gradient(brush) {
+                    self.canvas_render_context_2_d
+                        .set_fill_style_gradient(&gradient);
                 }
-
-                self.canvas_render_context_2_d
-                    .set_fill_style_gradient(&web_gradient);
             }
         }
     }
 
-    fn stroke_style<'a>(&self, brush: &Brush) {
+    fn stroke_style(&self, brush: &Brush) {
         match brush {
             Brush::SolidColor(color) => {
                 self.canvas_render_context_2_d
                     .set_stroke_style_color(&color.to_string());
             }
-            Brush::LinearGradient { start, end, stops } => {
-                let web_gradient = self
-                    .canvas_render_context_2_d
-                    .create_linear_gradient(start.x, start.y, end.x, end.y);
-
-                for stop in stops {
-                    web_gradient
-                        .add_color_stop(stop.position, stop.color.to_string().as_str())
-                        .unwrap();
+            Brush::ImagePattern { source, origin } => {
+                self.pattern_style("strokeStyle", source, origin);
+            }
+            _ => {
+                if let Some(gradient) = self.gradient(brush) {
+                    self.canvas_render_context_2_d
+                        .set_stroke_style_gradient(&gradient);
                 }
+            }
+        }
+    }
 
+    // Creates a canvas gradient from a linear or radial gradient brush.
+    fn gradient(&self, brush: &Brush) -> Option<CanvasGradient> {
+        let (web_gradient, stops) = match brush {
+            Brush::LinearGradient { start, end, stops } => (
                 self.canvas_render_context_2_d
-                    .set_stroke_style_gradient(&web_gradient);
-            }
+                    .create_linear_gradient(start.x, start.y, end.x, end.y),
+                stops,
+            ),
+            Brush::RadialGradient {
+                center,
+                radius,
+                stops,
+            } => (
+                self.canvas_render_context_2_d
+                    .create_radial_gradient(center.x, center.y, 0.0, center.x, center.y, *radius)
+                    .ok()?,
+                stops,
+            ),
+            _ => return None,
+        };
+
+        for stop in stops {
+            web_gradient
+                .add_color_stop(stop.position, stop.color.to_string().as_str())
+                .unwrap();
         }
+
+        Some(web_gradient)
+    }
+
+    // Sets a repeating pattern of the image as fill or stroke style. Nothing is drawn until the
+    // image is loaded.
+    fn pattern_style(&self, style: &str, source: &str, origin: &Point) {
+        js!(
+            var ctx = @{&self.canvas_render_context_2_d};
+            var image = document.image_store.image(@{source});
+
+            if(image == null) {
+                ctx[@{style}] = "rgba(0, 0, 0, 0)";
+                return;
+            }
+
+            var pattern = ctx.createPattern(image, "repeat");
+
+            if(pattern.setTransform) {
+                pattern.setTransform(new DOMMatrix([1, 0, 0, 1, @{origin.x}, @{origin.y}]));
+            }
+
+            ctx[@{style}] = pattern;
+        );
     }
 }
 
@@ -506,12 +848,12 @@ impl RenderContext2D {
 
 impl From<&str> for Image {
     fn from(s: &str) -> Image {
-        Image::from_path(s).unwrap()
+        Image::from_path(s).unwrap_or_default()
     }
 }
 
 impl From<String> for Image {
     fn from(s: String) -> Image {
-        Image::from_path(s).unwrap()
+        Image::from_path(s).unwrap_or_default()
     }
 }
@@ -25,7 +25,15 @@ where
     update: bool,
     redraw: bool,
     close: bool,
+
+    // the mouse position in logical pixels
     mouse_pos: (f64, f64),
+
+    // physical pixels per logical pixel, events are reported in logical pixels
+    scale_factor: f64,
+
+    // overrides the scale factor of the platform
+    fixed_scale_factor: Option<f64>,
 }
 
 impl<A> Window<A>
@@ -52,8 +60,24 @@ where
                 if !window_id.eq(&self.id()) {
                     return;
                 }
-                self.adapter.resize(s.width as f64, s.height as f64);
-                self.render_context.resize(s.width as f64, s.height as f64);
+                let width = s.width as f64 / self.scale_factor;
+                let height = s.height as f64 / self.scale_factor;
+                self.adapter.resize(width, height);
+                self.render_context.resize(width, height);
+                self.update = true;
+                *control_flow = ControlFlow::Wait;
+            }
+            event::Event::WindowEvent {
+                event: event::WindowEvent::ScaleFactorChanged { scale_factor, .. },
+                window_id,
+            } => {
+                if !window_id.eq(&self.id()) {
+                    return;
+                }
+                // the new size is reported by a following resize event
+                self.scale_factor = crate::scale_factor(self.fixed_scale_factor, *scale_factor);
+                self.render_context.set_scale_factor(self.scale_factor);
+                self.adapter.scale_factor(self.scale_factor);
                 self.update = true;
                 *control_flow = ControlFlow::Wait;
             }
@@ -128,8 +152,11 @@ where
                 if !window_id.eq(&self.id()) {
                     return;
                 }
-                self.mouse_pos = (position.x, position.y);
-                self.adapter.mouse(position.x, position.y);
+                self.mouse_pos = (
+                    position.x / self.scale_factor,
+                    position.y / self.scale_factor,
+                );
+                self.adapter.mouse(self.mouse_pos.0, self.mouse_pos.1);
                 self.update = true;
                 self.redraw = true;
                 *control_flow = ControlFlow::Wait;
@@ -34,6 +34,7 @@ where
     fonts: HashMap<String, &'static [u8]>,
     request_receiver: Option<mpsc::Receiver<WindowRequest>>,
     bounds: Rectangle,
+    scale_factor: Option<f64>,
 }
 
 impl<'a, A> WindowBuilder<'a, A>
@@ -49,6 +50,7 @@ where
             fonts: HashMap::new(),
             request_receiver: None,
             bounds: Rectangle::default(),
+            scale_factor: None,
         }
     }
 
@@ -75,6 +77,7 @@ where
                 settings.size.0,
                 settings.size.1,
             ),
+            scale_factor: settings.scale_factor,
         }
     }
 
@@ -112,6 +115,12 @@ where
         self
     }
 
+    /// Sets a fixed scale factor instead of the one of the platform.
+    pub fn scale_factor(mut self, scale_factor: f64) -> Self {
+        self.scale_factor = Some(scale_factor);
+        self
+    }
+
     /// Registers a new font with family key.
     pub fn font(mut self, family: impl Into<String>, font_file: &'static [u8]) -> Self {
         self.fonts.insert(family.into(), font_file);
@@ -125,7 +134,7 @@ where
     }
 
     /// Builds the window shell and add it to the application `Shell`.
-    pub fn build(self) {
+    pub fn build(mut self) {
         // Create an OpenGL 3.x context for Pathfinder to use.
         let gl_context = ContextBuilder::new()
             .with_gl(GlRequest::Latest)
@@ -139,20 +148,32 @@ where
 
         let window_size = (self.bounds.width(), self.bounds.height());
 
-        let logical_size = PhysicalSize::new(window_size.0, window_size.1);
+        // the bounds are given in logical pixels
+        let scale_factor =
+            crate::scale_factor(self.scale_factor, gl_context.window().scale_factor());
+        let physical_size = PhysicalSize::new(
+            (window_size.0 * scale_factor).round() as u32,
+            (window_size.1 * scale_factor).round() as u32,
+        );
+        gl_context.window().set_inner_size(physical_size);
 
         // Create a Pathfinder renderer.
         let mut renderer = Renderer::new(
             GLDevice::new(GLVersion::GL3, 0),
             &EmbeddedResourceLoader::new(),
-            DestFramebuffer::full_window(vec2i(window_size.0 as i32, window_size.1 as i32)),
+            DestFramebuffer::full_window(vec2i(
+                physical_size.width as i32,
+                physical_size.height as i32,
+            )),
             RendererOptions {
                 background_color: Some(ColorF::white()),
                 ..RendererOptions::default()
             },
         );
 
-        let render_context = RenderContext2D::new_ex(window_size, renderer);
+        let mut render_context = RenderContext2D::new_ex(window_size, renderer);
+        render_context.set_scale_factor(scale_factor);
+        self.adapter.scale_factor(scale_factor);
 
         self.shell.window_shells.push(Window::new(
             gl_context,
@@ -163,6 +184,8 @@ where
             true,
             false,
             (0.0, 0.0),
+            scale_factor,
+            self.scale_factor,
         ))
     }
 }
@@ -0,0 +1,122 @@
+//! This module contains a headless implementation of the window shell. It renders into an
+//! in-memory frame buffer and receives its events from code instead of a window system, e.g.
+//! to run OrbTk applications on machines without a display.
+
+use std::{sync::mpsc, thread, time::Duration};
+
+pub use super::native::*;
+
+use crate::{window_adapter::WindowAdapter, ShellRequest, WindowSettings};
+
+pub use self::window::*;
+pub use self::window_builder::*;
+
+mod window;
+mod window_builder;
+
+/// Does nothing. This function is only use by the web backend.
+pub fn initialize() {}
+
+/// Represents an application shell that could handle multiple headless windows.
+pub struct Shell<A: 'static>
+where
+    A: WindowAdapter,
+{
+    window_shells: Vec<Window<A>>,
+    requests: mpsc::Receiver<ShellRequest<A>>,
+}
+
+impl<A> Shell<A>
+where
+    A: WindowAdapter,
+{
+    /// Creates a new application shell.
+    pub fn new(requests: mpsc::Receiver<ShellRequest<A>>) -> Self {
+        Shell {
+            window_shells: vec![],
+            requests,
+        }
+    }
+
+    /// Creates a window builder, that could be used to create a window and add it to the application shell.
+    pub fn create_window(&mut self, adapter: A) -> WindowBuilder<A> {
+        WindowBuilder::new(self, adapter)
+    }
+
+    /// Creates a window builder from a settings object.
+    pub fn create_window_from_settings(
+        &mut self,
+        settings: WindowSettings,
+        adapter: A,
+    ) -> WindowBuilder<A> {
+        WindowBuilder::from_settings(settings, self, adapter)
+    }
+
+    /// Gets the windows of the shell.
+    pub fn windows(&self) -> &[Window<A>] {
+        &self.window_shells
+    }
+
+    /// Gets a mutable reference of the windows of the shell. Could be used to push events to a window.
+    pub fn windows_mut(&mut self) -> &mut [Window<A>] {
+        &mut self.window_shells
+    }
+
+    /// Receives window request from the application and handles them.
+    pub fn receive_requests(&mut self) {
+        let mut requests = vec![];
+        for request in self.requests.try_iter() {
+            requests.push(request);
+        }
+
+        for request in requests {
+            match request {
+                ShellRequest::CreateWindow(adapter, settings, window_requests) => {
+                    self.create_window_from_settings(settings, adapter)
+                        .request_receiver(window_requests)
+                        .build();
+                }
+            }
+        }
+    }
+
+    /// Runs one iteration of the shell: updates and renders each window and handles the requests.
+    ///
+    /// Returns `false` if there are no open windows left.
+    pub fn step(&mut self) -> bool {
+        if self.window_shells.is_empty() {
+            return false;
+        }
+
+        for i in 0..self.window_shells.len() {
+            let mut remove = false;
+            if let Some(window_shell) = self.window_shells.get_mut(i) {
+                window_shell.update();
+                window_shell.render();
+
+                window_shell.receive_requests();
+
+                if !window_shell.is_open() {
+                    remove = true;
+                }
+            }
+
+            if remove {
+                self.window_shells.remove(i);
+                break;
+            }
+        }
+
+        self.receive_requests();
+
+        !self.window_shells.is_empty()
+    }
+
+    /// Runs (starts) the application shell and its windows until all windows are closed.
+    pub fn run(&mut self) {
+        while self.step() {
+            // Limit to max ~60 fps update rate
+            thread::sleep(Duration::from_micros(16600));
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::{sync::mpsc, time::Duration};
+
+use derive_more::Constructor;
+
+use crate::{
+    event::{KeyEvent, MouseEvent},
+    render::RenderContext2D,
+    window_adapter::WindowAdapter,
+    WindowRequest,
+};
+
+// Maximum time to wait for the render thread to finish a frame.
+const FRAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Represents a headless window. Events are pushed by code, the rendered frames are stored in an
+/// in-memory frame buffer that could be read with `frame`.
+#[derive(Constructor)]
+pub struct Window<A>
+where
+    A: WindowAdapter,
+{
+    adapter: A,
+    render_context: RenderContext2D,
+    request_receiver: Option<mpsc::Receiver<WindowRequest>>,
+    title: String,
+    size: (f64, f64),
+    frame: Vec<u32>,
+    update: bool,
+    redraw: bool,
+    close: bool,
+    scale_factor: f64,
+}
+
+impl<A> Window<A>
+where
+    A: WindowAdapter,
+{
+    /// Check if the window is open.
+    pub fn is_open(&self) -> bool {
+        !self.close
+    }
+
+    /// Gets the title of the window.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Gets the logical size of the window.
+    pub fn size(&self) -> (f64, f64) {
+        self.size
+    }
+
+    /// Gets the scale factor of the window. The frame has the size multiplied by it.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Gets the last rendered frame. Each pixel is stored as argb `u32` value.
+    pub fn frame(&self) -> &[u32] {
+        &self.frame
+    }
+
+    /// Gets a reference of the window adapter.
+    pub fn adapter(&self) -> &A {
+        &self.adapter
+    }
+
+    /// Gets a mutable reference of the window adapter.
+    pub fn adapter_mut(&mut self) -> &mut A {
+        &mut self.adapter
+    }
+
+    /// Pushes a mouse move to the adapter.
+    pub fn mouse(&mut self, x: f64, y: f64) {
+        self.adapter.mouse(x, y);
+        self.update = true;
+    }
+
+    /// Pushes a mouse button event to the adapter.
+    pub fn mouse_event(&mut self, event: MouseEvent) {
+        self.adapter.mouse(event.x, event.y);
+        self.adapter.mouse_event(event);
+        self.update = true;
+    }
+
+    /// Pushes a scroll event to the adapter.
+    pub fn scroll(&mut self, delta_x: f64, delta_y: f64) {
+        self.adapter.scroll(delta_x, delta_y);
+        self.update = true;
+    }
+
+    /// Pushes a keyboard event to the adapter.
+    pub fn key_event(&mut self, event: KeyEvent) {
+        self.adapter.key_event(event);
+        self.update = true;
+    }
+
+    /// Resizes the window and its frame buffer.
+    pub fn resize(&mut self, width: f64, height: f64) {
+        self.size = (width, height);
+        self.render_context.resize(width, height);
+        self.adapter.resize(width, height);
+        self.update = true;
+    }
+
+    /// Changes the active state of the window.
+    pub fn active(&mut self, active: bool) {
+        self.adapter.active(active);
+        self.update = true;
+    }
+
+    /// Requests to quit the window.
+    pub fn quit_event(&mut self) {
+        self.adapter.quit_event();
+        self.update = true;
+    }
+
+    /// Requests an update of the window on the next iteration of the shell.
+    pub fn request_update(&mut self) {
+        self.update = true;
+    }
+
+    /// Receives window request from the application and handles them.
+    pub fn receive_requests(&mut self) {
+        if let Some(request_receiver) = &self.request_receiver {
+            for request in request_receiver.try_iter() {
+                match request {
+                    WindowRequest::Redraw => {
+                        self.update = true;
+                        self.redraw = true;
+                    }
+                    WindowRequest::ChangeTitle(title) => {
+                        self.title = title;
+                        self.update = true;
+                        self.redraw = true;
+                    }
+                    WindowRequest::Close => {
+                        self.close = true;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Runs update on the adapter.
+    pub fn update(&mut self) {
+        if !self.update {
+            return;
+        }
+        self.adapter.run(&mut self.render_context);
+        self.update = false;
+        self.redraw = true;
+    }
+
+    /// Waits until the render thread has finished the current frame and stores it in the frame buffer.
+    pub fn render(&mut self) {
+        if !self.redraw {
+            return;
+        }
+
+        if self
+            .render_context
+            .finish_receiver()
+            .recv_timeout(FRAME_TIMEOUT)
+            .is_ok()
+        {
+            // drop finish notifications of older frames
+            while self.render_context.finish_receiver().try_recv().is_ok() {}
+
+            // only the repainted regions are copied into the frame buffer
+            self.render_context.present(&mut self.frame);
+        }
+
+        self.redraw = false;
+    }
+}
@@ -0,0 +1,133 @@
+use std::{collections::HashMap, sync::mpsc};
+
+use super::{Shell, Window};
+use crate::{
+    render::RenderContext2D, utils::Rectangle, window_adapter::WindowAdapter, WindowRequest,
+    WindowSettings,
+};
+
+/// The `WindowBuilder` is used to construct a window shell for the headless backend.
+pub struct WindowBuilder<'a, A: 'static>
+where
+    A: WindowAdapter,
+{
+    shell: &'a mut Shell<A>,
+    adapter: A,
+    title: String,
+    fonts: HashMap<String, &'static [u8]>,
+    bounds: Rectangle,
+    scale_factor: Option<f64>,
+    request_receiver: Option<mpsc::Receiver<WindowRequest>>,
+}
+
+impl<'a, A> WindowBuilder<'a, A>
+where
+    A: WindowAdapter,
+{
+    /// Creates a new window builder.
+    pub fn new(shell: &'a mut Shell<A>, adapter: A) -> Self {
+        WindowBuilder {
+            shell,
+            adapter,
+            title: String::default(),
+            fonts: HashMap::new(),
+            bounds: Rectangle::new(0.0, 0.0, 100.0, 75.0),
+            scale_factor: None,
+            request_receiver: None,
+        }
+    }
+
+    /// Creates the window builder from a settings object.
+    pub fn from_settings(settings: WindowSettings, shell: &'a mut Shell<A>, adapter: A) -> Self {
+        WindowBuilder {
+            shell,
+            adapter,
+            title: settings.title,
+            fonts: settings.fonts,
+            bounds: Rectangle::new(
+                settings.position.0,
+                settings.position.1,
+                settings.size.0,
+                settings.size.1,
+            ),
+            scale_factor: settings.scale_factor,
+            request_receiver: None,
+        }
+    }
+
+    /// Sets the title.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets borderless. Has no effect on a headless window.
+    pub fn borderless(self, _borderless: bool) -> Self {
+        self
+    }
+
+    /// Sets resizeable. Has no effect on a headless window.
+    pub fn resizeable(self, _resizeable: bool) -> Self {
+        self
+    }
+
+    /// Sets always_on_top. Has no effect on a headless window.
+    pub fn always_on_top(self, _always_on_top: bool) -> Self {
+        self
+    }
+
+    /// Sets the bounds.
+    pub fn bounds(mut self, bounds: impl Into<Rectangle>) -> Self {
+        self.bounds = bounds.into();
+        self
+    }
+
+    /// Sets the scale factor. The scale factor of a headless window is `1.0` if it is not set,
+    /// the `ORBTK_SCALE_FACTOR` environment variable is ignored to keep the frames reproducible.
+    pub fn scale_factor(mut self, scale_factor: f64) -> Self {
+        self.scale_factor = Some(scale_factor);
+        self
+    }
+
+    /// Registers a new font with family key.
+    pub fn font(mut self, family: impl Into<String>, font_file: &'static [u8]) -> Self {
+        self.fonts.insert(family.into(), font_file);
+        self
+    }
+
+    /// Register a window request receiver to communicate with the window shell from outside.
+    pub fn request_receiver(mut self, request_receiver: mpsc::Receiver<WindowRequest>) -> Self {
+        self.request_receiver = Some(request_receiver);
+        self
+    }
+
+    /// Builds the window shell and add it to the application `Shell`.
+    pub fn build(mut self) {
+        let scale_factor = self.scale_factor.filter(|s| *s > 0.0).unwrap_or(1.0);
+
+        let mut render_context = RenderContext2D::new(self.bounds.width, self.bounds.height);
+        render_context.set_scale_factor(scale_factor);
+        self.adapter.scale_factor(scale_factor);
+
+        for (family, font) in self.fonts {
+            render_context.register_font(&family, font);
+        }
+
+        self.shell.window_shells.push(Window::new(
+            self.adapter,
+            render_context,
+            self.request_receiver,
+            self.title,
+            (self.bounds.width, self.bounds.height),
+            vec![
+                0;
+                (self.bounds.width * scale_factor).round() as usize
+                    * (self.bounds.height * scale_factor).round() as usize
+            ],
+            true,
+            true,
+            false,
+            scale_factor,
+        ));
+    }
+}
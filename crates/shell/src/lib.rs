@@ -39,12 +39,28 @@ pub mod platform;
 
 #[cfg(all(
     not(target_arch = "wasm32"),
-    feature = "default",
+    feature = "minifb",
     not(feature = "pfinder")
 ))]
 #[path = "minifb/mod.rs"]
 pub mod platform;
 
+// The headless shell is also available next to a window shell, e.g. for tests.
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    feature = "headless",
+    not(feature = "pfinder")
+))]
+pub mod headless;
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    feature = "headless",
+    not(feature = "minifb"),
+    not(feature = "pfinder")
+))]
+pub use self::headless as platform;
+
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native;
 
@@ -96,9 +112,54 @@ pub struct WindowSettings {
     /// The initial position of the window.
     pub position: (f64, f64),
 
-    /// The initial size of the window.
+    /// The initial size of the window in logical pixels.
     pub size: (f64, f64),
 
+    /// Fixed scale factor of the window. If it is not set, the scale factor of the platform is
+    /// used.
+    pub scale_factor: Option<f64>,
+
     /// List of fonts to register.
     pub fonts: HashMap<String, &'static [u8]>,
 }
+
+/// Name of the environment variable that overrides the scale factor of all windows, e.g.
+/// `ORBTK_SCALE_FACTOR=2`.
+pub const SCALE_FACTOR_ENV: &str = "ORBTK_SCALE_FACTOR";
+
+/// Gets the scale factor of a window, that is the number of physical pixels per logical pixel.
+/// The scale factor reported by the platform is overridden by the scale factor of the window
+/// settings, both are overridden by the `ORBTK_SCALE_FACTOR` environment variable.
+pub fn scale_factor(settings: Option<f64>, platform: f64) -> f64 {
+    #[cfg(not(target_arch = "wasm32"))]
+    let env = std::env::var(SCALE_FACTOR_ENV).ok();
+
+    #[cfg(target_arch = "wasm32")]
+    let env: Option<String> = None;
+
+    select_scale_factor(env.as_deref(), settings, platform)
+}
+
+// Invalid or not positive scale factors are skipped.
+fn select_scale_factor(env: Option<&str>, settings: Option<f64>, platform: f64) -> f64 {
+    env.and_then(|env| env.trim().parse::<f64>().ok())
+        .into_iter()
+        .chain(settings)
+        .chain(Some(platform))
+        .find(|scale_factor| scale_factor.is_finite() && *scale_factor > 0.0)
+        .unwrap_or(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_scale_factor() {
+        assert_eq!(select_scale_factor(None, None, 2.0), 2.0);
+        assert_eq!(select_scale_factor(None, Some(1.5), 2.0), 1.5);
+        assert_eq!(select_scale_factor(Some("1.25"), Some(1.5), 2.0), 1.25);
+        assert_eq!(select_scale_factor(Some("large"), None, 2.0), 2.0);
+        assert_eq!(select_scale_factor(Some("0"), Some(-1.0), 0.0), 1.0);
+    }
+}
@@ -28,6 +28,12 @@ where
     close: bool,
     key_states: Vec<KeyState>,
     key_events: Rc<RefCell<Vec<KeyEvent>>>,
+
+    // physical pixels per logical pixel, events are reported in logical pixels
+    scale_factor: f64,
+
+    // the presented pixels, only the repainted regions of a frame are copied into it
+    frame: Vec<u32>,
 }
 
 impl<A> Window<A>
@@ -42,8 +48,8 @@ where
         };
 
         self.adapter.mouse_event(MouseEvent {
-            x: self.mouse.mouse_pos.0 as f64,
-            y: self.mouse.mouse_pos.1 as f64,
+            x: self.mouse.mouse_pos.0 as f64 / self.scale_factor,
+            y: self.mouse.mouse_pos.1 as f64 / self.scale_factor,
             button,
             state,
         });
@@ -101,7 +107,10 @@ where
         // mouse move
         if let Some(pos) = self.window.get_mouse_pos(minifb::MouseMode::Discard) {
             if (pos.0.floor(), pos.1.floor()) != self.mouse.mouse_pos {
-                self.adapter.mouse(pos.0 as f64, pos.1 as f64);
+                self.adapter.mouse(
+                    pos.0 as f64 / self.scale_factor,
+                    pos.1 as f64 / self.scale_factor,
+                );
                 self.mouse.mouse_pos = (pos.0.floor(), pos.1.floor());
                 self.update = true;
             }
@@ -148,17 +157,13 @@ where
             self.update = true;
         }
 
-        // resize
+        // resize, the size of the window is given in physical pixels
         if self.window_state.size != self.window.get_size() {
             self.window_state.size = self.window.get_size();
-            self.render_context.resize(
-                self.window_state.size.0 as f64,
-                self.window_state.size.1 as f64,
-            );
-            self.adapter.resize(
-                self.window_state.size.0 as f64,
-                self.window_state.size.1 as f64,
-            );
+            let width = self.window_state.size.0 as f64 / self.scale_factor;
+            let height = self.window_state.size.1 as f64 / self.scale_factor;
+            self.render_context.resize(width, height);
+            self.adapter.resize(width, height);
             self.update = true;
         }
 
@@ -216,18 +221,16 @@ where
         self.redraw = true;
     }
 
-    /// Swaps the current frame buffer.
+    /// Presents the repainted regions of the current frame.
     pub fn render(&mut self) {
-        if self.redraw {
-            if let Some(data) = self.render_context.data() {
-                let _ = self.window.update_with_buffer(
-                    data,
-                    self.window_state.size.0 as usize,
-                    self.window_state.size.1 as usize,
-                );
-                // CONSOLE.time_end("render");
-                self.redraw = false;
-            }
+        if self.redraw && self.render_context.present(&mut self.frame) {
+            let _ = self.window.update_with_buffer(
+                &self.frame,
+                self.window_state.size.0 as usize,
+                self.window_state.size.1 as usize,
+            );
+            // CONSOLE.time_end("render");
+            self.redraw = false;
         }
     }
 }
@@ -24,6 +24,7 @@ where
     borderless: bool,
     fonts: HashMap<String, &'static [u8]>,
     bounds: Rectangle,
+    scale_factor: Option<f64>,
     request_receiver: Option<mpsc::Receiver<WindowRequest>>,
 }
 
@@ -42,6 +43,7 @@ where
             borderless: false,
             fonts: HashMap::new(),
             bounds: Rectangle::new(0.0, 0.0, 100.0, 75.0),
+            scale_factor: None,
             request_receiver: None,
         }
     }
@@ -62,6 +64,7 @@ where
                 settings.size.0,
                 settings.size.1,
             ),
+            scale_factor: settings.scale_factor,
             request_receiver: None,
         }
     }
@@ -96,6 +99,12 @@ where
         self
     }
 
+    /// Sets a fixed scale factor instead of the one of the platform.
+    pub fn scale_factor(mut self, scale_factor: f64) -> Self {
+        self.scale_factor = Some(scale_factor);
+        self
+    }
+
     /// Registers a new font with family key.
     pub fn font(mut self, family: impl Into<String>, font_file: &'static [u8]) -> Self {
         self.fonts.insert(family.into(), font_file);
@@ -109,7 +118,10 @@ where
     }
 
     /// Builds the window shell and add it to the application `Shell`.
-    pub fn build(self) {
+    pub fn build(mut self) {
+        // minifb does not report the scale factor of the display
+        let scale_factor = crate::scale_factor(self.scale_factor, 1.0);
+
         let window_options = minifb::WindowOptions {
             resize: self.resizeable,
             topmost: self.always_on_top,
@@ -121,8 +133,8 @@ where
 
         let mut window = minifb::Window::new(
             self.title.as_str(),
-            self.bounds.width as usize,
-            self.bounds.height as usize,
+            (self.bounds.width * scale_factor).round() as usize,
+            (self.bounds.height * scale_factor).round() as usize,
             window_options,
         )
         .unwrap_or_else(|e| {
@@ -141,6 +153,8 @@ where
         window.set_position(self.bounds.x as isize, self.bounds.y as isize);
 
         let mut render_context = RenderContext2D::new(self.bounds.width, self.bounds.height);
+        render_context.set_scale_factor(scale_factor);
+        self.adapter.scale_factor(scale_factor);
 
         for (family, font) in self.fonts {
             render_context.register_font(&family, font);
@@ -194,6 +208,8 @@ where
                 KeyState::new(minifb::Key::X, Key::X(false)),
             ],
             key_events,
+            scale_factor,
+            vec![],
         ));
     }
 }
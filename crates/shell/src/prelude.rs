@@ -1,3 +1,10 @@
 pub use crate::{
     event::*, platform::*, window_adapter::*, ShellRequest, WindowRequest, WindowSettings,
 };
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    feature = "headless",
+    not(feature = "pfinder")
+))]
+pub use crate::headless;
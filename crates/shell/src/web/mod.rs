@@ -1,6 +1,10 @@
 //! This module contains a platform specific implementation of the window shell.
 use std::sync::mpsc;
-use stdweb::{js, web::window};
+use stdweb::{
+    js,
+    unstable::TryInto,
+    web::{html_element::CanvasElement, window, CanvasRenderingContext2d},
+};
 
 use crate::prelude::*;
 
@@ -23,6 +27,41 @@ fn set_panic_hook() {
     console_error_panic_hook::set_once();
 }
 
+// Sizes the canvas to the given logical size in physical pixels and returns the scale factor. The
+// scale factor of the platform is the device pixel ratio of the browser.
+fn scale_canvas(canvas: &CanvasElement, size: (f64, f64), fixed_scale_factor: Option<f64>) -> f64 {
+    let ctx: CanvasRenderingContext2d = canvas.get_context().unwrap();
+
+    let device_pixel_ratio = window().device_pixel_ratio();
+
+    let backing_store_ratio = js! {
+        var ctx = @{&ctx};
+         return ctx.webkitBackingStorePixelRatio ||
+             ctx.mozBackingStorePixelRatio ||
+             ctx.msBackingStorePixelRatio ||
+             ctx.oBackingStorePixelRatio ||
+             ctx.backingStorePixelRatio || 1;
+    };
+
+    let ratio: f64 = js! {
+        return @{&device_pixel_ratio} / @{&backing_store_ratio};
+    }
+    .try_into()
+    .unwrap();
+
+    let scale_factor = crate::scale_factor(fixed_scale_factor, ratio);
+
+    canvas.set_width((size.0 * scale_factor).round() as u32);
+    canvas.set_height((size.1 * scale_factor).round() as u32);
+
+    js! {
+        @{canvas}.style.width = @{size.0} + "px";
+        @{canvas}.style.height = @{size.1} + "px";
+    }
+
+    scale_factor
+}
+
 /// Initializes web stuff.
 pub fn initialize() {
     set_panic_hook();
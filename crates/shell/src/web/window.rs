@@ -9,7 +9,7 @@ use stdweb::{
 
 use derive_more::Constructor;
 
-use super::EventState;
+use super::{scale_canvas, EventState};
 use crate::{
     event::{ButtonState, Key, KeyEvent, MouseButton, MouseEvent},
     render::RenderContext2D,
@@ -33,6 +33,12 @@ where
     update: bool,
     redraw: bool,
     close: bool,
+
+    // physical pixels per logical pixel
+    scale_factor: f64,
+
+    // overrides the device pixel ratio of the browser
+    fixed_scale_factor: Option<f64>,
 }
 
 impl<A> Window<A>
@@ -141,9 +147,6 @@ where
                 .try_into()
                 .unwrap();
 
-            canvas.set_width(window_size.0 as u32);
-            canvas.set_height(window_size.1 as u32);
-
             js! {
                 document.body.style.padding = 0;
                 document.body.style.margin = 0;
@@ -151,39 +154,18 @@ where
                 @{&canvas}.style.margin = "0";
             }
 
-            let device_pixel_ratio = window().device_pixel_ratio();
+            let scale_factor = scale_canvas(&canvas, window_size, self.fixed_scale_factor);
             let ctx: CanvasRenderingContext2d = canvas.get_context().unwrap();
 
-            let backing_store_ratio = js! {
-                var ctx = @{&ctx};
-                 return ctx.webkitBackingStorePixelRatio ||
-                     ctx.mozBackingStorePixelRatio ||
-                     ctx.msBackingStorePixelRatio ||
-                     ctx.oBackingStorePixelRatio ||
-                     ctx.backingStorePixelRatio || 1;
-            };
-
-            let ratio: f64 = js! {
-                return @{&device_pixel_ratio} / @{&backing_store_ratio};
-            }
-            .try_into()
-            .unwrap();
-
-            if device_pixel_ratio != backing_store_ratio {
-                let old_width = canvas.width();
-                let old_height = canvas.height();
-                canvas.set_width((old_width as f64 * ratio) as u32);
-                canvas.set_height((old_height as f64 * ratio) as u32);
-
-                js! {
-                    @{&canvas}.style.width = @{&old_width} + "px";
-                    @{&canvas}.style.height = @{&old_height} + "px";
-                }
+            self.render_context.set_canvas_render_context_2d(ctx);
+            self.render_context.set_scale_factor(scale_factor);
 
-                ctx.scale(ratio, ratio);
+            // e.g. the browser is zoomed or moved to another display
+            if scale_factor != self.scale_factor {
+                self.scale_factor = scale_factor;
+                self.adapter.scale_factor(scale_factor);
             }
 
-            self.render_context.set_canvas_render_context_2d(ctx);
             self.adapter.resize(window_size.0, window_size.1);
             self.old_canvas = Some(self.canvas.clone());
             self.canvas = canvas;
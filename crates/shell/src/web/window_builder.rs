@@ -10,10 +10,10 @@ use stdweb::{
     js,
     traits::*,
     unstable::TryInto,
-    web::{document, event, html_element::CanvasElement, window, CanvasRenderingContext2d},
+    web::{document, event, html_element::CanvasElement, window},
 };
 
-use super::{EventState, Shell, Window};
+use super::{scale_canvas, EventState, Shell, Window};
 use crate::{
     render::RenderContext2D, utils::Rectangle, window_adapter::WindowAdapter, WindowRequest,
     WindowSettings,
@@ -32,6 +32,7 @@ where
     borderless: bool,
     fonts: HashMap<String, &'static [u8]>,
     bounds: Rectangle,
+    scale_factor: Option<f64>,
     request_receiver: Option<mpsc::Receiver<WindowRequest>>,
 }
 
@@ -50,6 +51,7 @@ where
             borderless: false,
             fonts: HashMap::new(),
             bounds: Rectangle::new(0.0, 0.0, 100.0, 75.0),
+            scale_factor: None,
             request_receiver: None,
         }
     }
@@ -70,6 +72,7 @@ where
                 settings.size.0,
                 settings.size.1,
             ),
+            scale_factor: settings.scale_factor,
             request_receiver: None,
         }
     }
@@ -104,6 +107,12 @@ where
         self
     }
 
+    /// Sets a fixed scale factor instead of the device pixel ratio of the browser.
+    pub fn scale_factor(mut self, scale_factor: f64) -> Self {
+        self.scale_factor = Some(scale_factor);
+        self
+    }
+
     /// Registers a new font with family key.
     pub fn font(mut self, family: impl Into<String>, font_file: &'static [u8]) -> Self {
         self.fonts.insert(family.into(), font_file);
@@ -129,9 +138,6 @@ where
             window().inner_height() as f64,
         );
 
-        canvas.set_width(window_size.0 as u32);
-        canvas.set_height(window_size.1 as u32);
-
         let adapter = &mut self.adapter;
         adapter.resize(window_size.0, window_size.1);
 
@@ -242,40 +248,11 @@ where
         });
 
         document().body().unwrap().append_child(&canvas);
-        let ctx: CanvasRenderingContext2d = canvas.get_context().unwrap();
-
-        let device_pixel_ratio = window().device_pixel_ratio();
-
-        let backing_store_ratio = js! {
-            var ctx = @{&ctx};
-             return ctx.webkitBackingStorePixelRatio ||
-                 ctx.mozBackingStorePixelRatio ||
-                 ctx.msBackingStorePixelRatio ||
-                 ctx.oBackingStorePixelRatio ||
-                 ctx.backingStorePixelRatio || 1;
-        };
-
-        let ratio: f64 = js! {
-            return @{&device_pixel_ratio} / @{&backing_store_ratio};
-        }
-        .try_into()
-        .unwrap();
-
-        if device_pixel_ratio != backing_store_ratio {
-            let old_width = canvas.width();
-            let old_height = canvas.height();
-            canvas.set_width((old_width as f64 * ratio) as u32);
-            canvas.set_height((old_height as f64 * ratio) as u32);
-
-            js! {
-                @{&canvas}.style.width = @{&old_width} + "px";
-                @{&canvas}.style.height = @{&old_height} + "px";
-            }
-
-            ctx.scale(ratio, ratio);
-        }
+        let scale_factor = scale_canvas(&canvas, window_size, self.scale_factor);
 
-        let render_context = RenderContext2D::from_context(canvas.get_context().unwrap());
+        let mut render_context = RenderContext2D::from_context(canvas.get_context().unwrap());
+        render_context.set_scale_factor(scale_factor);
+        self.adapter.scale_factor(scale_factor);
 
         document().set_title(self.title.as_str());
 
@@ -302,6 +279,8 @@ where
             true,
             true,
             false,
+            scale_factor,
+            self.scale_factor,
         ));
     }
 }
@@ -6,9 +6,14 @@ use crate::{event::*, utils::Point};
 /// The `WindowAdapter` represents the bridge to the `Shell` backend.
 /// It receives events from the `Window` and runs it's own logic.  
 pub trait WindowAdapter {
-    /// Is called after the window is resized.
+    /// Is called after the window is resized. The size is given in logical pixels.
     fn resize(&mut self, _width: f64, _height: f64) {}
 
+    /// Is called after the scale factor of the window is changed and once after the window is
+    /// created. Events are reported in logical pixels, that are physical pixels divided by the
+    /// scale factor.
+    fn scale_factor(&mut self, _scale_factor: f64) {}
+
     /// Is called after the mouse was moved.
     fn mouse(&mut self, _x: f64, _y: f64) {}
 
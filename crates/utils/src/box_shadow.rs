@@ -0,0 +1,90 @@
+use crate::{Color, Rectangle};
+
+/// Describes the shadow of a widget like the css `box-shadow`. The shadow has the shape of the
+/// widget, is moved by the offset, grown by the spread and blurred by the blur radius.
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub struct BoxShadow {
+    /// Horizontal offset of the shadow.
+    pub offset_x: f64,
+
+    /// Vertical offset of the shadow.
+    pub offset_y: f64,
+
+    /// The shadow fades out over this distance on each side of its edges.
+    pub blur: f64,
+
+    /// Distance the shadow is grown on each side, a negative spread shrinks it.
+    pub spread: f64,
+
+    pub color: Color,
+}
+
+impl BoxShadow {
+    /// Creates a new box shadow.
+    pub fn new(offset_x: f64, offset_y: f64, blur: f64, spread: f64, color: Color) -> Self {
+        BoxShadow {
+            offset_x,
+            offset_y,
+            blur: blur.max(0.0),
+            spread,
+            color,
+        }
+    }
+
+    /// Checks if the shadow is drawn. The default shadow is transparent.
+    pub fn is_visible(&self) -> bool {
+        self.color.a() > 0
+    }
+
+    /// Gets the shape of the shadow of the given rectangle before it is blurred.
+    pub fn shape(&self, rect: &Rectangle) -> Rectangle {
+        Rectangle::new(
+            rect.x + self.offset_x - self.spread,
+            rect.y + self.offset_y - self.spread,
+            (rect.width + 2.0 * self.spread).max(0.0),
+            (rect.height + 2.0 * self.spread).max(0.0),
+        )
+    }
+
+    /// Gets the area that is covered by the blurred shadow of the given rectangle.
+    pub fn bounds(&self, rect: &Rectangle) -> Rectangle {
+        let shape = self.shape(rect);
+
+        Rectangle::new(
+            shape.x - self.blur,
+            shape.y - self.blur,
+            shape.width + 2.0 * self.blur,
+            shape.height + 2.0 * self.blur,
+        )
+    }
+}
+
+// --- Conversions ---
+
+impl From<(f64, f64, f64, Color)> for BoxShadow {
+    fn from(s: (f64, f64, f64, Color)) -> Self {
+        BoxShadow::new(s.0, s.1, s.2, 0.0, s.3)
+    }
+}
+
+impl From<(f64, f64, f64, f64, Color)> for BoxShadow {
+    fn from(s: (f64, f64, f64, f64, Color)) -> Self {
+        BoxShadow::new(s.0, s.1, s.2, s.3, s.4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_bounds() {
+        let shadow = BoxShadow::new(2.0, 4.0, 6.0, 1.0, Color::rgba(0, 0, 0, 80));
+        let rect = Rectangle::new(10.0, 10.0, 20.0, 10.0);
+
+        assert!(shadow.is_visible());
+        assert_eq!(shadow.shape(&rect), Rectangle::new(11.0, 13.0, 22.0, 12.0));
+        assert_eq!(shadow.bounds(&rect), Rectangle::new(5.0, 7.0, 34.0, 24.0));
+        assert!(!BoxShadow::default().is_visible());
+    }
+}
@@ -19,6 +19,22 @@ pub enum Brush {
         end: Point,
         stops: Vec<LinearGradientStop>,
     },
+
+    /// Paints an area with a circular gradient from the center to the given radius.
+    RadialGradient {
+        center: Point,
+        radius: f64,
+        stops: Vec<LinearGradientStop>,
+    },
+
+    /// Paints an area by tiling the image with the given source. The first tile starts at
+    /// `origin`.
+    ImagePattern { source: String, origin: Point },
+
+    /// Paints an area with the given gradient or image pattern, whose coordinates are relative to
+    /// the bounds of the filled area. It is produced by css and has to be resolved with
+    /// `with_bounds` before it is drawn.
+    Relative(Box<Brush>),
 }
 
 impl Brush {
@@ -28,6 +44,48 @@ impl Brush {
             _ => false,
         }
     }
+
+    /// Resolves a relative brush inside of the given bounds. The start and end of a linear
+    /// gradient and the center of a radial gradient are mapped from 0.0 to 1.0 to absolute
+    /// coordinates, the radius is relative to the larger side of the bounds and the origin of an
+    /// image pattern is moved by the position of the bounds. Other brushes are returned unchanged.
+    pub fn with_bounds(&self, bounds: &Rectangle) -> Brush {
+        let brush = match self {
+            Brush::Relative(brush) => brush,
+            _ => return self.clone(),
+        };
+
+        match &**brush {
+            Brush::LinearGradient { start, end, stops } => Brush::LinearGradient {
+                start: Point::new(
+                    bounds.x() + start.x * bounds.width(),
+                    bounds.y() + start.y * bounds.height(),
+                ),
+                end: Point::new(
+                    bounds.x() + end.x * bounds.width(),
+                    bounds.y() + end.y * bounds.height(),
+                ),
+                stops: stops.clone(),
+            },
+            Brush::RadialGradient {
+                center,
+                radius,
+                stops,
+            } => Brush::RadialGradient {
+                center: Point::new(
+                    bounds.x() + center.x * bounds.width(),
+                    bounds.y() + center.y * bounds.height(),
+                ),
+                radius: radius * bounds.width().max(bounds.height()),
+                stops: stops.clone(),
+            },
+            Brush::ImagePattern { source, origin } => Brush::ImagePattern {
+                source: source.clone(),
+                origin: Point::new(bounds.x() + origin.x, bounds.y() + origin.y),
+            },
+            brush => brush.with_bounds(bounds),
+        }
+    }
 }
 
 impl From<Brush> for Color {
@@ -71,6 +129,68 @@ impl From<String> for Brush {
 
 #[cfg(test)]
 mod tests {
-    //  use crate::prelude::*;
-    // todo: tbd after brush struct is finished
+    use crate::prelude::*;
+
+    #[test]
+    fn with_bounds() {
+        let brush = Brush::Relative(Box::new(Brush::LinearGradient {
+            start: Point::new(0.5, 0.0),
+            end: Point::new(0.5, 1.0),
+            stops: vec![],
+        }));
+
+        assert_eq!(
+            brush.with_bounds(&Rectangle::new(10.0, 20.0, 100.0, 50.0)),
+            Brush::LinearGradient {
+                start: Point::new(60.0, 20.0),
+                end: Point::new(60.0, 70.0),
+                stops: vec![],
+            }
+        );
+
+        let brush = Brush::Relative(Box::new(Brush::RadialGradient {
+            center: Point::new(0.5, 0.5),
+            radius: 0.5,
+            stops: vec![],
+        }));
+
+        assert_eq!(
+            brush.with_bounds(&Rectangle::new(10.0, 20.0, 100.0, 50.0)),
+            Brush::RadialGradient {
+                center: Point::new(60.0, 45.0),
+                radius: 50.0,
+                stops: vec![],
+            }
+        );
+
+        let brush = Brush::Relative(Box::new(Brush::ImagePattern {
+            source: "pattern.png".to_string(),
+            origin: Point::new(2.0, 4.0),
+        }));
+
+        assert_eq!(
+            brush.with_bounds(&Rectangle::new(10.0, 20.0, 100.0, 50.0)),
+            Brush::ImagePattern {
+                source: "pattern.png".to_string(),
+                origin: Point::new(12.0, 24.0),
+            }
+        );
+
+        let brush = Brush::from("#ff0000");
+        assert_eq!(
+            brush.with_bounds(&Rectangle::new(10.0, 20.0, 100.0, 50.0)),
+            brush
+        );
+
+        // absolute gradients are not changed
+        let brush = Brush::LinearGradient {
+            start: Point::new(0.5, 0.0),
+            end: Point::new(0.5, 1.0),
+            stops: vec![],
+        };
+        assert_eq!(
+            brush.with_bounds(&Rectangle::new(10.0, 20.0, 100.0, 50.0)),
+            brush
+        );
+    }
 }
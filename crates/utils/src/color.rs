@@ -23,6 +23,33 @@ impl Color {
         }
     }
 
+    /// Create a new color from hue (in degrees), saturation and lightness (both from 0.0 to 100.0)
+    pub fn hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        Color::hsla(hue, saturation, lightness, 1.0)
+    }
+
+    /// Create a new color from hue (in degrees), saturation and lightness (both from 0.0 to 100.0)
+    /// and alpha (from 0.0 to 1.0)
+    pub fn hsla(hue: f64, saturation: f64, lightness: f64, alpha: f64) -> Self {
+        let hue = ((hue % 360.0) + 360.0) % 360.0 / 360.0;
+        let saturation = clamp(saturation / 100.0);
+        let lightness = clamp(lightness / 100.0);
+
+        let q = if lightness < 0.5 {
+            lightness * (1.0 + saturation)
+        } else {
+            lightness + saturation - lightness * saturation
+        };
+        let p = 2.0 * lightness - q;
+
+        Color::rgba(
+            to_channel(hue_to_rgb(p, q, hue + 1.0 / 3.0)),
+            to_channel(hue_to_rgb(p, q, hue)),
+            to_channel(hue_to_rgb(p, q, hue - 1.0 / 3.0)),
+            to_channel(alpha),
+        )
+    }
+
     /// Get the r value
     pub fn r(self) -> u8 {
         ((self.data & 0x00FF_0000) >> 16) as u8
@@ -57,6 +84,39 @@ impl Color {
     }
 }
 
+fn clamp(value: f64) -> f64 {
+    if value < 0.0 {
+        0.0
+    } else if value > 1.0 {
+        1.0
+    } else {
+        value
+    }
+}
+
+fn to_channel(value: f64) -> u8 {
+    (clamp(value) * 255.0 + 0.5) as u8
+}
+
+fn hue_to_rgb(p: f64, q: f64, mut t: f64) -> f64 {
+    if t < 0.0 {
+        t += 1.0;
+    }
+    if t > 1.0 {
+        t -= 1.0;
+    }
+
+    if t < 1.0 / 6.0 {
+        p + (q - p) * 6.0 * t
+    } else if t < 1.0 / 2.0 {
+        q
+    } else if t < 2.0 / 3.0 {
+        p + (q - p) * (2.0 / 3.0 - t) * 6.0
+    } else {
+        p
+    }
+}
+
 impl ToString for Color {
     fn to_string(&self) -> String {
         if self.a() == 0 {
@@ -122,4 +182,13 @@ mod tests {
         assert_eq!(false, Color::rgb(1, 2, 3) == Color::rgba(11, 2, 3, 200));
         assert_eq!(true, Color::rgba(1, 2, 3, 200) == Color::rgba(1, 2, 3, 200));
     }
+
+    #[test]
+    fn hsla() {
+        assert_eq!(Color::hsl(0.0, 100.0, 50.0), Color::rgb(255, 0, 0));
+        assert_eq!(Color::hsl(120.0, 100.0, 25.0), Color::rgb(0, 128, 0));
+        assert_eq!(Color::hsl(-120.0, 100.0, 50.0), Color::rgb(0, 0, 255));
+        assert_eq!(Color::hsl(0.0, 0.0, 100.0), Color::rgb(255, 255, 255));
+        assert_eq!(Color::hsla(0.0, 0.0, 0.0, 0.5).a(), 128);
+    }
 }
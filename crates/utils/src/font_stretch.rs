@@ -0,0 +1,102 @@
+/// Describes the width of a font face, like the css `font-stretch`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FontStretch {
+    UltraCondensed,
+    ExtraCondensed,
+    Condensed,
+    SemiCondensed,
+    Normal,
+    SemiExpanded,
+    Expanded,
+    ExtraExpanded,
+    UltraExpanded,
+}
+
+impl FontStretch {
+    /// Gets the width class from 1 (ultra condensed) to 9 (ultra expanded) as used by font files.
+    pub fn width_class(self) -> u16 {
+        self as u16 + 1
+    }
+
+    /// Gets the stretch of the given width class from 1 to 9.
+    pub fn from_width_class(width_class: u16) -> Self {
+        match width_class {
+            0 | 1 => FontStretch::UltraCondensed,
+            2 => FontStretch::ExtraCondensed,
+            3 => FontStretch::Condensed,
+            4 => FontStretch::SemiCondensed,
+            5 => FontStretch::Normal,
+            6 => FontStretch::SemiExpanded,
+            7 => FontStretch::Expanded,
+            8 => FontStretch::ExtraExpanded,
+            _ => FontStretch::UltraExpanded,
+        }
+    }
+
+    /// Gets the css keyword of the stretch.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            FontStretch::UltraCondensed => "ultra-condensed",
+            FontStretch::ExtraCondensed => "extra-condensed",
+            FontStretch::Condensed => "condensed",
+            FontStretch::SemiCondensed => "semi-condensed",
+            FontStretch::Normal => "normal",
+            FontStretch::SemiExpanded => "semi-expanded",
+            FontStretch::Expanded => "expanded",
+            FontStretch::ExtraExpanded => "extra-expanded",
+            FontStretch::UltraExpanded => "ultra-expanded",
+        }
+    }
+}
+
+impl Default for FontStretch {
+    fn default() -> Self {
+        FontStretch::Normal
+    }
+}
+
+// --- Conversions ---
+
+impl From<&str> for FontStretch {
+    fn from(t: &str) -> Self {
+        match t {
+            "UltraCondensed" | "ultra-condensed" => FontStretch::UltraCondensed,
+            "ExtraCondensed" | "extra-condensed" => FontStretch::ExtraCondensed,
+            "Condensed" | "condensed" => FontStretch::Condensed,
+            "SemiCondensed" | "semi-condensed" => FontStretch::SemiCondensed,
+            "SemiExpanded" | "semi-expanded" => FontStretch::SemiExpanded,
+            "Expanded" | "expanded" => FontStretch::Expanded,
+            "ExtraExpanded" | "extra-expanded" => FontStretch::ExtraExpanded,
+            "UltraExpanded" | "ultra-expanded" => FontStretch::UltraExpanded,
+            _ => FontStretch::Normal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into() {
+        let stretch: FontStretch = "condensed".into();
+        assert_eq!(stretch, FontStretch::Condensed);
+
+        let stretch: FontStretch = "UltraExpanded".into();
+        assert_eq!(stretch, FontStretch::UltraExpanded);
+
+        let stretch: FontStretch = "other".into();
+        assert_eq!(stretch, FontStretch::Normal);
+    }
+
+    #[test]
+    fn test_width_class() {
+        assert_eq!(FontStretch::UltraCondensed.width_class(), 1);
+        assert_eq!(FontStretch::Normal.width_class(), 5);
+        assert_eq!(FontStretch::from_width_class(7), FontStretch::Expanded);
+        assert_eq!(
+            FontStretch::from_width_class(12),
+            FontStretch::UltraExpanded
+        );
+    }
+}
@@ -0,0 +1,56 @@
+/// Describes the slant of a font, like the css `font-style`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FontStyle {
+    Normal,
+
+    /// Uses a face that is designed as italic.
+    Italic,
+
+    /// Uses a slanted version of the normal face.
+    Oblique,
+}
+
+impl Default for FontStyle {
+    fn default() -> Self {
+        FontStyle::Normal
+    }
+}
+
+impl ToString for FontStyle {
+    fn to_string(&self) -> String {
+        match self {
+            FontStyle::Normal => "normal".to_string(),
+            FontStyle::Italic => "italic".to_string(),
+            FontStyle::Oblique => "oblique".to_string(),
+        }
+    }
+}
+
+// --- Conversions ---
+
+impl From<&str> for FontStyle {
+    fn from(t: &str) -> Self {
+        match t {
+            "Italic" | "italic" => FontStyle::Italic,
+            "Oblique" | "oblique" => FontStyle::Oblique,
+            _ => FontStyle::Normal,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into() {
+        let style: FontStyle = "Italic".into();
+        assert_eq!(style, FontStyle::Italic);
+
+        let style: FontStyle = "oblique".into();
+        assert_eq!(style, FontStyle::Oblique);
+
+        let style: FontStyle = "other".into();
+        assert_eq!(style, FontStyle::Normal);
+    }
+}
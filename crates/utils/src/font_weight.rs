@@ -0,0 +1,68 @@
+/// Describes the weight (boldness) of a font from 1 to 1000, like the css `font-weight`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: FontWeight = FontWeight(100);
+    pub const EXTRA_LIGHT: FontWeight = FontWeight(200);
+    pub const LIGHT: FontWeight = FontWeight(300);
+    pub const NORMAL: FontWeight = FontWeight(400);
+    pub const MEDIUM: FontWeight = FontWeight(500);
+    pub const SEMI_BOLD: FontWeight = FontWeight(600);
+    pub const BOLD: FontWeight = FontWeight(700);
+    pub const EXTRA_BOLD: FontWeight = FontWeight(800);
+    pub const BLACK: FontWeight = FontWeight(900);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        FontWeight::NORMAL
+    }
+}
+
+// --- Conversions ---
+
+impl From<&str> for FontWeight {
+    fn from(t: &str) -> Self {
+        match t {
+            "Thin" | "thin" => FontWeight::THIN,
+            "ExtraLight" | "extra-light" => FontWeight::EXTRA_LIGHT,
+            "Light" | "light" => FontWeight::LIGHT,
+            "Medium" | "medium" => FontWeight::MEDIUM,
+            "SemiBold" | "semi-bold" => FontWeight::SEMI_BOLD,
+            "Bold" | "bold" => FontWeight::BOLD,
+            "ExtraBold" | "extra-bold" => FontWeight::EXTRA_BOLD,
+            "Black" | "black" => FontWeight::BLACK,
+            _ => t.parse::<u16>().map(FontWeight::from).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<u16> for FontWeight {
+    fn from(t: u16) -> Self {
+        FontWeight(t.max(1).min(1000))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into() {
+        let weight: FontWeight = "bold".into();
+        assert_eq!(weight, FontWeight::BOLD);
+
+        let weight: FontWeight = "Light".into();
+        assert_eq!(weight, FontWeight::LIGHT);
+
+        let weight: FontWeight = "650".into();
+        assert_eq!(weight, FontWeight(650));
+
+        let weight: FontWeight = 2000.into();
+        assert_eq!(weight, FontWeight(1000));
+
+        let weight: FontWeight = "other".into();
+        assert_eq!(weight, FontWeight::NORMAL);
+    }
+}
@@ -1,31 +1,43 @@
 pub use self::alignment::*;
 pub use self::border::*;
+pub use self::box_shadow::*;
 pub use self::brush::*;
 pub use self::color::*;
 pub use self::dirty_size::*;
+pub use self::font_stretch::*;
+pub use self::font_style::*;
+pub use self::font_weight::*;
 pub use self::orientation::*;
 pub use self::point::*;
 pub use self::rectangle::*;
 pub use self::selection_mode::*;
+pub use self::stretch::*;
 pub use self::string16::*;
 pub use self::text_alignment::*;
 pub use self::text_baseline::*;
+pub use self::text_wrap::*;
 pub use self::thickness::*;
 pub use self::visibility::*;
 
 mod alignment;
 mod border;
+mod box_shadow;
 mod brush;
 mod color;
 mod dirty_size;
+mod font_stretch;
+mod font_style;
+mod font_weight;
 mod orientation;
 mod point;
 pub mod prelude;
 mod rectangle;
 mod selection_mode;
 mod spacer;
+mod stretch;
 mod string16;
 mod text_alignment;
 mod text_baseline;
+mod text_wrap;
 mod thickness;
 mod visibility;
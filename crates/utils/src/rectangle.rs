@@ -107,6 +107,34 @@ impl Rectangle {
             || rect.y() >= (self.y + self.height)
             || self.y >= (rect.y() + rect.height()))
     }
+
+    /// Gets the area covered by this and the given rectangle. Returns an empty rectangle if
+    /// both do not intersect.
+    pub fn intersection(&self, rect: &Rectangle) -> Rectangle {
+        let x = self.x.max(rect.x);
+        let y = self.y.max(rect.y);
+        let width = (self.x + self.width).min(rect.x + rect.width) - x;
+        let height = (self.y + self.height).min(rect.y + rect.height) - y;
+
+        if width <= 0.0 || height <= 0.0 {
+            return Rectangle::new(x, y, 0.0, 0.0);
+        }
+
+        Rectangle::new(x, y, width, height)
+    }
+
+    /// Gets the smallest rectangle that contains both rectangles.
+    pub fn union(&self, rect: &Rectangle) -> Rectangle {
+        let x = self.x.min(rect.x);
+        let y = self.y.min(rect.y);
+
+        Rectangle::new(
+            x,
+            y,
+            (self.x + self.width).max(rect.x + rect.width) - x,
+            (self.y + self.height).max(rect.y + rect.height) - y,
+        )
+    }
 }
 
 // --- Conversions ---
@@ -136,4 +164,28 @@ mod tests {
         assert_eq!(rect.width, 20.0);
         assert_eq!(rect.height, 30.0);
     }
+
+    #[test]
+    fn test_intersection() {
+        let rect = Rectangle::new(0.0, 0.0, 20.0, 20.0);
+
+        assert_eq!(
+            rect.intersection(&Rectangle::new(10.0, 5.0, 20.0, 10.0)),
+            Rectangle::new(10.0, 5.0, 10.0, 10.0)
+        );
+        assert_eq!(
+            rect.intersection(&Rectangle::new(30.0, 5.0, 20.0, 10.0)).size(),
+            (0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_union() {
+        let rect = Rectangle::new(0.0, 0.0, 20.0, 20.0);
+
+        assert_eq!(
+            rect.union(&Rectangle::new(10.0, 5.0, 20.0, 10.0)),
+            Rectangle::new(0.0, 0.0, 30.0, 20.0)
+        );
+    }
 }
@@ -0,0 +1,255 @@
+use crate::{Rectangle, Thickness};
+
+/// Describes how an image is resized to fill the bounds of its widget.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Stretch {
+    /// The image keeps its size.
+    None,
+
+    /// The image fills the bounds, its aspect ratio is not kept.
+    Fill,
+
+    /// The image is resized to fit into the bounds keeping its aspect ratio.
+    Uniform,
+
+    /// The image is resized to fill the bounds keeping its aspect ratio. Parts that do not fit
+    /// into the bounds are clipped.
+    UniformToFill,
+}
+
+impl Stretch {
+    /// Gets the part of an image of the given size that is drawn and the rectangle it is drawn
+    /// into, for an image that is stretched into the given bounds.
+    pub fn rects(self, image_size: (f64, f64), bounds: Rectangle) -> (Rectangle, Rectangle) {
+        let (width, height) = image_size;
+        let image = Rectangle::new(0.0, 0.0, width, height);
+
+        if width <= 0.0 || height <= 0.0 {
+            return (image, Rectangle::new(bounds.x, bounds.y, 0.0, 0.0));
+        }
+
+        match self {
+            Stretch::None => (
+                Rectangle::new(0.0, 0.0, width.min(bounds.width), height.min(bounds.height)),
+                Rectangle::new(
+                    bounds.x,
+                    bounds.y,
+                    width.min(bounds.width),
+                    height.min(bounds.height),
+                ),
+            ),
+            Stretch::Fill => (image, bounds),
+            Stretch::Uniform => {
+                let scale = (bounds.width / width).min(bounds.height / height);
+
+                (
+                    image,
+                    Rectangle::new(
+                        bounds.x + (bounds.width - width * scale) / 2.0,
+                        bounds.y + (bounds.height - height * scale) / 2.0,
+                        width * scale,
+                        height * scale,
+                    ),
+                )
+            }
+            Stretch::UniformToFill => {
+                let scale = (bounds.width / width).max(bounds.height / height);
+                let (clip_width, clip_height) = (bounds.width / scale, bounds.height / scale);
+
+                (
+                    Rectangle::new(
+                        (width - clip_width) / 2.0,
+                        (height - clip_height) / 2.0,
+                        clip_width,
+                        clip_height,
+                    ),
+                    bounds,
+                )
+            }
+        }
+    }
+}
+
+impl ToString for Stretch {
+    fn to_string(&self) -> String {
+        match self {
+            Stretch::None => "none".to_string(),
+            Stretch::Fill => "fill".to_string(),
+            Stretch::Uniform => "uniform".to_string(),
+            Stretch::UniformToFill => "uniform-to-fill".to_string(),
+        }
+    }
+}
+
+impl Default for Stretch {
+    fn default() -> Self {
+        Stretch::None
+    }
+}
+
+/// Slices an image of the given size into nine parts like a nine-patch image. The corners given
+/// by the insets keep their size, the edges are stretched along the bounds and the center is
+/// stretched in both directions. If the bounds are smaller than the corners, the corners are
+/// shrunk. Returns the parts of the image and the rectangles they are drawn into.
+pub fn nine_patch(
+    image_size: (f64, f64),
+    insets: Thickness,
+    bounds: Rectangle,
+) -> Vec<(Rectangle, Rectangle)> {
+    let (width, height) = image_size;
+    let (left, right) = (
+        insets.left.min(width),
+        insets.right.min(width - insets.left).max(0.0),
+    );
+    let (top, bottom) = (
+        insets.top.min(height),
+        insets.bottom.min(height - insets.top).max(0.0),
+    );
+
+    let scale_x = if left + right > bounds.width {
+        bounds.width / (left + right)
+    } else {
+        1.0
+    };
+    let scale_y = if top + bottom > bounds.height {
+        bounds.height / (top + bottom)
+    } else {
+        1.0
+    };
+
+    let source_x = [0.0, left, width - right, width];
+    let source_y = [0.0, top, height - bottom, height];
+    let target_x = [
+        bounds.x,
+        bounds.x + left * scale_x,
+        bounds.x + bounds.width - right * scale_x,
+        bounds.x + bounds.width,
+    ];
+    let target_y = [
+        bounds.y,
+        bounds.y + top * scale_y,
+        bounds.y + bounds.height - bottom * scale_y,
+        bounds.y + bounds.height,
+    ];
+
+    let mut parts = vec![];
+
+    for row in 0..3 {
+        for column in 0..3 {
+            let source = Rectangle::new(
+                source_x[column],
+                source_y[row],
+                source_x[column + 1] - source_x[column],
+                source_y[row + 1] - source_y[row],
+            );
+            let target = Rectangle::new(
+                target_x[column],
+                target_y[row],
+                target_x[column + 1] - target_x[column],
+                target_y[row + 1] - target_y[row],
+            );
+
+            if source.width > 0.0
+                && source.height > 0.0
+                && target.width > 0.0
+                && target.height > 0.0
+            {
+                parts.push((source, target));
+            }
+        }
+    }
+
+    parts
+}
+
+// --- Conversions ---
+
+impl From<&str> for Stretch {
+    fn from(s: &str) -> Self {
+        match s {
+            "Fill" | "fill" => Stretch::Fill,
+            "Uniform" | "uniform" => Stretch::Uniform,
+            "UniformToFill" | "uniform-to-fill" | "uniform_to_fill" => Stretch::UniformToFill,
+            _ => Stretch::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into() {
+        let stretch: Stretch = "uniform-to-fill".into();
+        assert_eq!(stretch, Stretch::UniformToFill);
+
+        let stretch: Stretch = "fill".into();
+        assert_eq!(stretch, Stretch::Fill);
+
+        let stretch: Stretch = "unknown".into();
+        assert_eq!(stretch, Stretch::None);
+    }
+
+    #[test]
+    fn test_rects() {
+        let bounds = Rectangle::new(10.0, 10.0, 100.0, 50.0);
+
+        assert_eq!(
+            Stretch::Uniform.rects((20.0, 20.0), bounds),
+            (
+                Rectangle::new(0.0, 0.0, 20.0, 20.0),
+                Rectangle::new(35.0, 10.0, 50.0, 50.0)
+            )
+        );
+        assert_eq!(
+            Stretch::UniformToFill.rects((20.0, 20.0), bounds),
+            (
+                Rectangle::new(0.0, 5.0, 20.0, 10.0),
+                Rectangle::new(10.0, 10.0, 100.0, 50.0)
+            )
+        );
+        assert_eq!(
+            Stretch::None.rects((200.0, 20.0), bounds),
+            (
+                Rectangle::new(0.0, 0.0, 100.0, 20.0),
+                Rectangle::new(10.0, 10.0, 100.0, 20.0)
+            )
+        );
+    }
+
+    #[test]
+    fn test_nine_patch() {
+        let parts = nine_patch(
+            (30.0, 30.0),
+            Thickness::new(10.0, 10.0, 10.0, 10.0),
+            Rectangle::new(0.0, 0.0, 100.0, 40.0),
+        );
+
+        assert_eq!(parts.len(), 9);
+        assert_eq!(
+            parts[0],
+            (
+                Rectangle::new(0.0, 0.0, 10.0, 10.0),
+                Rectangle::new(0.0, 0.0, 10.0, 10.0)
+            )
+        );
+        assert_eq!(
+            parts[4],
+            (
+                Rectangle::new(10.0, 10.0, 10.0, 10.0),
+                Rectangle::new(10.0, 10.0, 80.0, 20.0)
+            )
+        );
+
+        // the corners are shrunk and the center is dropped
+        let parts = nine_patch(
+            (30.0, 30.0),
+            Thickness::new(10.0, 10.0, 10.0, 10.0),
+            Rectangle::new(0.0, 0.0, 10.0, 10.0),
+        );
+
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts[3].1, Rectangle::new(5.0, 5.0, 5.0, 5.0));
+    }
+}
@@ -19,3 +19,46 @@ impl ToString for TextAlignment {
         }
     }
 }
+
+impl Default for TextAlignment {
+    fn default() -> Self {
+        TextAlignment::Start
+    }
+}
+
+// --- Conversions ---
+
+impl From<&str> for TextAlignment {
+    fn from(t: &str) -> Self {
+        match t {
+            "Left" | "left" => TextAlignment::Left,
+            "Right" | "right" => TextAlignment::Right,
+            "Center" | "center" => TextAlignment::Center,
+            "End" | "end" => TextAlignment::End,
+            _ => TextAlignment::Start,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into() {
+        let alignment: TextAlignment = "Left".into();
+        assert_eq!(alignment, TextAlignment::Left);
+
+        let alignment: TextAlignment = "right".into();
+        assert_eq!(alignment, TextAlignment::Right);
+
+        let alignment: TextAlignment = "center".into();
+        assert_eq!(alignment, TextAlignment::Center);
+
+        let alignment: TextAlignment = "end".into();
+        assert_eq!(alignment, TextAlignment::End);
+
+        let alignment: TextAlignment = "other".into();
+        assert_eq!(alignment, TextAlignment::Start);
+    }
+}
@@ -0,0 +1,62 @@
+/// Describes if a text is wrapped into multiple lines if it does not fit its width.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TextWrap {
+    /// The text is only broken at explicit line breaks.
+    NoWrap,
+
+    /// The text is wrapped at word boundaries. Words that are wider than a line are broken.
+    Wrap,
+}
+
+impl ToString for TextWrap {
+    fn to_string(&self) -> String {
+        match self {
+            TextWrap::NoWrap => "no-wrap".to_string(),
+            TextWrap::Wrap => "wrap".to_string(),
+        }
+    }
+}
+
+impl Default for TextWrap {
+    fn default() -> Self {
+        TextWrap::NoWrap
+    }
+}
+
+// --- Conversions ---
+
+impl From<&str> for TextWrap {
+    fn from(t: &str) -> Self {
+        match t {
+            "Wrap" | "wrap" => TextWrap::Wrap,
+            _ => TextWrap::NoWrap,
+        }
+    }
+}
+
+impl From<bool> for TextWrap {
+    fn from(wrap: bool) -> Self {
+        if wrap {
+            TextWrap::Wrap
+        } else {
+            TextWrap::NoWrap
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into() {
+        let wrap: TextWrap = "wrap".into();
+        assert_eq!(wrap, TextWrap::Wrap);
+
+        let wrap: TextWrap = "no-wrap".into();
+        assert_eq!(wrap, TextWrap::NoWrap);
+
+        let wrap: TextWrap = true.into();
+        assert_eq!(wrap, TextWrap::Wrap);
+    }
+}
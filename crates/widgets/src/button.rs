@@ -138,3 +138,68 @@ impl Template for Button {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use orbtk_api::testing::TestDriver;
+
+    use super::*;
+
+    #[test]
+    fn test_click() {
+        let clicks = Rc::new(Cell::new(0));
+        let counter = clicks.clone();
+
+        let mut driver = TestDriver::new(move |ctx| {
+            let counter = counter.clone();
+
+            Window::new()
+                .child(
+                    Button::new()
+                        .id("button")
+                        .text("Click me")
+                        .on_click(move |_, _| {
+                            counter.set(counter.get() + 1);
+                            true
+                        })
+                        .build(ctx),
+                )
+                .build(ctx)
+        });
+
+        let button = driver.find_by_id("button").unwrap();
+        driver.click(button);
+        assert_eq!(clicks.get(), 1);
+
+        let center = driver.center_of(button);
+        driver.mouse_click(center.x, center.y);
+        assert_eq!(clicks.get(), 2);
+    }
+
+    #[test]
+    fn test_switch_theme() {
+        let mut driver = TestDriver::new(|ctx| {
+            Window::new()
+                .child(
+                    Button::new()
+                        .id("button")
+                        .h_align("start")
+                        .text("Click me")
+                        .build(ctx),
+                )
+                .build(ctx)
+        });
+
+        let button = driver.find_by_id("button").unwrap();
+        let width = driver.get::<Rectangle>(button, "bounds").width();
+
+        driver.switch_theme(
+            ThemeValue::create_from_css("button { background: #ff0000; padding: 0 40; }").build(),
+        );
+
+        driver.assert_property(button, "background", Brush::from("#ff0000"));
+        assert!(driver.get::<Rectangle>(button, "bounds").width() > width);
+    }
+}
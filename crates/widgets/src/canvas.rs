@@ -1,10 +1,14 @@
 use crate::prelude::*;
 
 widget!(
-    /// Canvas is used to render 3D graphics.
+    /// Canvas is used to render 3D graphics into a pixel buffer or to draw 2D graphics like charts
+    /// with the api of the render context.
     Canvas {
         /// Sets or shares the render pipeline.
-        render_pipeline: RenderPipeline
+        render_pipeline: RenderPipeline,
+
+        /// Sets or shares the 2D render pipeline. It is drawn on top of the render pipeline.
+        render_pipeline_2_d: RenderPipeline2D
     }
 );
 
@@ -428,3 +428,33 @@ impl Template for ComboBox {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use orbtk_api::testing::Snapshot;
+
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        Snapshot::new("combo_box")
+            .size(120.0, 32.0)
+            .tolerance(2)
+            .assert(|ctx| {
+                Window::new()
+                    .child(
+                        ComboBox::new()
+                            .items_builder(|bc, index| {
+                                TextBlock::new()
+                                    .v_align("center")
+                                    .text(format!("Item {}", index + 1))
+                                    .build(bc)
+                            })
+                            .count(3)
+                            .selected_index(0)
+                            .build(ctx),
+                    )
+                    .build(ctx)
+            });
+    }
+}
@@ -15,6 +15,12 @@ widget!(
         /// Sets or shares the border brush property.
         border_brush: Brush,
 
+        /// Sets or shares the box shadow property.
+        box_shadow: BoxShadow,
+
+        /// Sets or shares the radius the content below the widget is blurred with.
+        backdrop_blur: f64,
+
         /// Sets or shares the padding property.
         padding: Thickness
     }
@@ -28,6 +34,8 @@ impl Template for Container {
             .border_radius(0.0)
             .border_width(0.0)
             .border_brush("transparent")
+            .box_shadow(BoxShadow::default())
+            .backdrop_blur(0.0)
     }
 
     fn render_object(&self) -> Box<dyn RenderObject> {
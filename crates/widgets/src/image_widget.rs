@@ -1,23 +1,138 @@
-use crate::prelude::*;
+use crate::{prelude::*, shell::WindowRequest};
+
+/// The `ImageWidgetState` takes images that are decoded on a background thread as soon as they
+/// are ready and plays animated images.
+#[derive(Default, AsAny)]
+pub struct ImageWidgetState {
+    // time in milliseconds since the animation has started
+    position: f64,
+    last_time: Option<f64>,
+}
+
+impl ImageWidgetState {
+    fn play(&mut self, ctx: &mut Context) {
+        if !*ctx.widget().get::<bool>("playing") {
+            self.last_time = None;
+            return;
+        }
+
+        let now = now();
+        self.position += now - self.last_time.unwrap_or(now);
+        self.last_time = Some(now);
+
+        let (frame_index, finished) = {
+            let widget = ctx.widget();
+            widget
+                .get::<AnimatedImage>("animated_image")
+                .frame_at(self.position, *widget.get::<bool>("looping"))
+        };
+
+        if *ctx.widget().get::<usize>("frame_index") != frame_index {
+            ctx.widget().set("frame_index", frame_index);
+        }
+
+        if finished {
+            self.position = 0.0;
+            self.last_time = None;
+            ctx.widget().set("playing", false);
+        } else {
+            // keeps on updating while the animation is playing
+            ctx.send_window_request(WindowRequest::Redraw);
+        }
+    }
+}
+
+impl State for ImageWidgetState {
+    fn update(&mut self, _: &mut Registry, ctx: &mut Context) {
+        let (loading, decoded) = {
+            let widget = ctx.widget();
+            let image = widget.get::<Image>("image");
+            (image.is_loading(), image.is_decoded())
+        };
+
+        if decoded {
+            let mut image = ctx.widget().clone::<Image>("image");
+            image.poll();
+            ctx.widget().set("image", image);
+        } else if loading {
+            // the decoding thread wakes up the window to take the image as soon as it is ready
+            let sender = ctx.window_sender();
+            ctx.widget().get::<Image>("image").on_decoded(move || {
+                let _ = sender.send(WindowRequest::Redraw);
+            });
+        }
+
+        if ctx.widget().get::<AnimatedImage>("animated_image").len() > 1 {
+            self.play(ctx);
+        }
+    }
+}
 
 widget!(
     /// The `ImageWidget` widget is used to draw an image. It is not interactive.
     ///
     /// **CSS element:** `image-widget`
-    ImageWidget {
-        /// Sets or shares the image property.
+    ImageWidget<ImageWidgetState> {
+        /// Sets or shares the image property. Large image files are decoded on a background thread.
         ///
         /// Set image property:
         /// * &str: `Image::new().image("path/to/image.png").build(xt)`
         /// * String: `Image::new().image(String::from()).build(xt)`
         /// * (width: u32, height: u32, data: Vec<u32>): `Image::new().image((width, height, vec![0; width * height]));`
-        image: Image
+        image: Image,
+
+        /// Sets or shares the svg property. If it is set, the vector graphic is drawn instead of the image.
+        ///
+        /// Set svg property:
+        /// * &str: `ImageWidget::new().svg("path/to/image.svg").build(ctx)`
+        /// * Svg: `ImageWidget::new().svg(Svg::from_data(include_bytes!("image.svg")).unwrap()).build(ctx)`
+        svg: Svg,
+
+        /// Sets or shares the animated image property. If it is set, its frames are drawn one after another
+        /// instead of the image while `playing` is `true`.
+        ///
+        /// Set animated image property:
+        /// * &str: `ImageWidget::new().animated_image("path/to/image.gif").build(ctx)`
+        animated_image: AnimatedImage,
+
+        /// Sets or shares the playing property. The animation is paused if it is `false` and continues
+        /// where it has stopped if it is set to `true` again. It is set to `false` when an animation that
+        /// is not looping has finished.
+        playing: bool,
+
+        /// Sets or shares the looping property. If it is `true` the animation starts over after the last frame.
+        looping: bool,
+
+        /// Sets or shares the index of the frame of the animated image that is shown.
+        frame_index: usize,
+
+        /// Sets or shares the stretch property that describes how the image is resized to fill the widget.
+        /// A stretched image is sized by its alignment and constraint instead of the image size.
+        stretch: Stretch,
+
+        /// Sets or shares the nine patch property. If it is set, the image is sliced into nine parts: the corners
+        /// with the size of the insets keep their size, the edges and the center are stretched. Use it together
+        /// with a stretch other than `none`.
+        nine_patch: Thickness,
+
+        /// Sets or shares the placeholder property, it is drawn while the image is loading.
+        placeholder: Brush
     }
 );
 
 impl Template for ImageWidget {
     fn template(self, _: Entity, _: &mut BuildContext) -> Self {
-        self.name("ImageWidget").element("image-widget").image("")
+        self.name("ImageWidget")
+            .element("image-widget")
+            .image("")
+            .svg(Svg::default())
+            .animated_image(AnimatedImage::default())
+            .playing(true)
+            .looping(true)
+            .frame_index(0)
+            .stretch("none")
+            .nine_patch(0.0)
+            .placeholder("transparent")
     }
 
     fn render_object(&self) -> Box<dyn RenderObject> {
@@ -32,6 +32,7 @@ pub use self::scroll_indicator::*;
 pub use self::scroll_viewer::*;
 pub use self::slider::*;
 pub use self::stack::*;
+pub use self::svg_icon::*;
 pub use self::switch::*;
 pub use self::text_block::*;
 pub use self::text_box::*;
@@ -58,6 +59,7 @@ mod scroll_indicator;
 mod scroll_viewer;
 mod slider;
 mod stack;
+mod svg_icon;
 mod switch;
 mod text_block;
 mod text_box;
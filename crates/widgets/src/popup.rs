@@ -10,9 +10,12 @@ impl State for PopupState {
             ctx.widget().set("visibility", Visibility::Visible);
         } else {
             ctx.widget().set("visibility", Visibility::Collapsed);
-            {
-                ctx.widget().get_mut::<Rectangle>("bounds").set_width(0.0);
-                ctx.widget().get_mut::<Rectangle>("bounds").set_height(0.0);
+
+            let bounds: Rectangle = ctx.widget().clone("bounds");
+
+            if bounds.width() != 0.0 || bounds.height() != 0.0 {
+                ctx.widget()
+                    .set("bounds", Rectangle::new(bounds.x(), bounds.y(), 0.0, 0.0));
             }
         }
     }
@@ -26,12 +29,15 @@ impl State for PopupState {
             let target_position: Point = ctx.get_widget(target.into()).clone("position");
             let target_bounds: Rectangle = ctx.get_widget(target.into()).clone("bounds");
 
-            ctx.widget()
-                .get_mut::<Rectangle>("bounds")
-                .set_x(target_position.x + target_bounds.x());
-            ctx.widget()
-                .get_mut::<Rectangle>("bounds")
-                .set_y(1.0 + target_position.y + target_bounds.y() + target_bounds.height());
+            let old_bounds: Rectangle = ctx.widget().clone("bounds");
+            let mut bounds = old_bounds;
+            bounds.set_x(target_position.x + target_bounds.x());
+            bounds.set_y(1.0 + target_position.y + target_bounds.y() + target_bounds.height());
+
+            // the popup is only repainted if it is moved
+            if bounds != old_bounds {
+                ctx.widget().set("bounds", bounds);
+            }
         }
     }
 }
@@ -51,6 +57,12 @@ widget!(
         /// Sets or shares the border brush property.
         border_brush: Brush,
 
+        /// Sets or shares the box shadow property.
+        box_shadow: BoxShadow,
+
+        /// Sets or shares the radius the content below the widget is blurred with.
+        backdrop_blur: f64,
+
         /// Sets or shares the padding property.
         padding: Thickness,
 
@@ -72,6 +84,8 @@ impl Template for Popup {
             .border_radius(0.0)
             .border_width(0.0)
             .border_brush("transparent")
+            .box_shadow(BoxShadow::default())
+            .backdrop_blur(0.0)
             .on_mouse_down(|_, _| true)
     }
 
@@ -22,9 +22,11 @@ impl State for BarState {
         let max_width = ctx.widget().get::<Rectangle>("bounds").width();
         let new_width = calculate_width(val, max_width);
 
-        ctx.get_widget(self.indicator)
-            .get_mut::<Constraint>("constraint")
-            .set_width(new_width);
+        let mut constraint = ctx
+            .get_widget(self.indicator)
+            .clone::<Constraint>("constraint");
+        constraint.set_width(new_width);
+        ctx.get_widget(self.indicator).set("constraint", constraint);
     }
 }
 
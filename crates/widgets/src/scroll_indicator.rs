@@ -31,9 +31,14 @@ impl State for ScrollIndicatorState {
                         * vertical_p)
                         .max(vertical_min_height);
 
-                let scroll_bar_bounds = vertical_scroll_bar.get_mut::<Rectangle>("bounds");
+                let old_bounds: Rectangle = vertical_scroll_bar.clone("bounds");
+                let mut scroll_bar_bounds = old_bounds;
                 scroll_bar_bounds.height = height;
                 scroll_bar_bounds.y = -(scroll_offset.y as f64 * vertical_p);
+
+                if scroll_bar_bounds != old_bounds {
+                    vertical_scroll_bar.set("bounds", scroll_bar_bounds);
+                }
             } else {
                 vertical_scroll_bar.set("visibility", Visibility::from("collapsed"));
             }
@@ -52,9 +57,14 @@ impl State for ScrollIndicatorState {
                     ((bounds.width - padding.left - padding.right - scroll_bar_margin_right)
                         * horizontal_p)
                         .max(horizontal_min_width);
-                let scroll_bar_bounds = horizontal_scroll_bar.get_mut::<Rectangle>("bounds");
+                let old_bounds: Rectangle = horizontal_scroll_bar.clone("bounds");
+                let mut scroll_bar_bounds = old_bounds;
                 scroll_bar_bounds.width = width;
                 scroll_bar_bounds.x = -(scroll_offset.x as f64 * horizontal_p);
+
+                if scroll_bar_bounds != old_bounds {
+                    horizontal_scroll_bar.set("bounds", scroll_bar_bounds);
+                }
             } else {
                 horizontal_scroll_bar.set("visibility", Visibility::from("collapsed"));
             }
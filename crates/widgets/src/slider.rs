@@ -85,15 +85,15 @@ impl SliderState {
             .get::<Rectangle>("bounds")
             .width();
 
-        ctx.get_widget(self.thumb)
-            .get_mut::<Thickness>("margin")
-            .set_left(calculate_thumb_x_from_val(
-                val,
-                min,
-                max,
-                track_width,
-                thumb_width,
-            ));
+        let mut margin = *ctx.get_widget(self.thumb).get::<Thickness>("margin");
+        margin.set_left(calculate_thumb_x_from_val(
+            val,
+            min,
+            max,
+            track_width,
+            thumb_width,
+        ));
+        ctx.get_widget(self.thumb).set("margin", margin);
     }
 }
 
@@ -125,9 +125,9 @@ impl State for SliderState {
                         let thumb_x =
                             calculate_thumb_x(mouse_x, thumb_width, slider_x, track_width);
 
-                        ctx.get_widget(self.thumb)
-                            .get_mut::<Thickness>("margin")
-                            .set_left(thumb_x);
+                        let mut margin = *ctx.get_widget(self.thumb).get::<Thickness>("margin");
+                        margin.set_left(thumb_x);
+                        ctx.get_widget(self.thumb).set("margin", margin);
 
                         let min = *ctx.widget().get("min");
                         let max = *ctx.widget().get("max");
@@ -278,6 +278,8 @@ fn calculate_thumb_x_from_val(
 
 #[cfg(test)]
 mod tests {
+    use orbtk_api::testing::{render_snapshot, Snapshot};
+
     use super::*;
 
     #[test]
@@ -336,4 +338,31 @@ mod tests {
             calculate_thumb_x_from_val(100.0, 0.0, 100.0, 100.0, 32.0)
         );
     }
+
+    #[test]
+    fn test_snapshot() {
+        Snapshot::new("slider")
+            .size(120.0, 32.0)
+            .tolerance(2)
+            .assert(|ctx| {
+                Window::new()
+                    .child(Slider::new().val(25.0).build(ctx))
+                    .build(ctx)
+            });
+    }
+
+    #[test]
+    fn test_snapshot_scale_factor() {
+        let snapshot = render_snapshot((120.0, 32.0), |ctx| {
+            Window::new()
+                .scale_factor(2.0)
+                .child(Slider::new().val(25.0).build(ctx))
+                .build(ctx)
+        })
+        .unwrap();
+
+        // the frame has the physical size of the window
+        assert_eq!(snapshot.width(), 240.0);
+        assert_eq!(snapshot.height(), 64.0);
+    }
 }
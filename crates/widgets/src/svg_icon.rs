@@ -0,0 +1,41 @@
+use crate::prelude::*;
+
+widget!(
+    /// The `SvgIcon` widget is used to draw an icon from a SVG document. The icon is drawn as
+    /// vector graphic with the icon brush, so it stays sharp at every icon size. It is not
+    /// interactive.
+    ///
+    /// **CSS element:** `svg-icon`
+    SvgIcon {
+        /// Sets or shares the svg property.
+        ///
+        /// Set svg property:
+        /// * &str: `SvgIcon::new().svg("path/to/icon.svg").build(ctx)`
+        /// * Svg: `SvgIcon::new().svg(Svg::from_data(include_bytes!("icon.svg")).unwrap()).build(ctx)`
+        svg: Svg,
+
+        /// Sets or shares the icon brush property. Transparent parts of the icon keep their opacity.
+        icon_brush: Brush,
+
+        /// Sets or shares the icon size property, that is the height of the icon.
+        icon_size: f64
+    }
+);
+
+impl Template for SvgIcon {
+    fn template(self, _: Entity, _: &mut BuildContext) -> Self {
+        self.name("SvgIcon")
+            .element("svg-icon")
+            .svg(Svg::default())
+            .icon_brush(colors::LINK_WATER_COLOR)
+            .icon_size(fonts::ICON_FONT_SIZE_12)
+    }
+
+    fn render_object(&self) -> Box<dyn RenderObject> {
+        Box::new(ImageRenderObject)
+    }
+
+    fn layout(&self) -> Box<dyn Layout> {
+        Box::new(FixedSizeLayout::new())
+    }
+}
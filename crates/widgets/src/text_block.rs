@@ -1,7 +1,8 @@
 use crate::prelude::*;
 
 widget!(
-    /// The `TextBlock` widget is used to draw text. It is not interactive.
+    /// The `TextBlock` widget is used to draw text. It is not interactive. The text is broken
+    /// into lines at line breaks and, with `text_wrap`, at word boundaries.
     ///
     /// **CSS element:** `text-block`
     TextBlock {
@@ -18,7 +19,29 @@ widget!(
         font_size: f64,
 
         /// Sets or shares the font property.
-        font: String
+        font: String,
+
+        /// Sets or shares the font weight property.
+        font_weight: FontWeight,
+
+        /// Sets or shares the font style property.
+        font_style: FontStyle,
+
+        /// Sets or shares the font stretch property.
+        font_stretch: FontStretch,
+
+        /// Sets or shares the text alignment of the lines.
+        text_align: TextAlignment,
+
+        /// Sets or shares if the text is wrapped at the maximum width of its constraint.
+        text_wrap: TextWrap,
+
+        /// Sets or shares the line height. If it is not set the lines are as high as the font size.
+        line_height: f64,
+
+        /// Sets or shares the maximum number of lines, `0` means unlimited. A truncated text ends
+        /// with an ellipsis.
+        max_lines: usize
     }
 );
 
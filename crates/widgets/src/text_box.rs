@@ -97,12 +97,9 @@ impl TextBoxState {
         if *ctx.get_widget(self.cursor).get::<bool>("expanded")
             || *ctx.widget().get::<bool>("focused")
         {
+            let start_index = self.get_new_caret_position(ctx, p);
             ctx.widget()
-                .get_mut::<TextSelection>("text_selection")
-                .start_index = self.get_new_caret_position(ctx, p);
-            ctx.widget()
-                .get_mut::<TextSelection>("text_selection")
-                .length = 0;
+                .set("text_selection", TextSelection::from((start_index, 0)));
         }
     }
 
@@ -128,17 +125,23 @@ impl TextBoxState {
         // array which will hold char index and it's x position
         let mut position_index: Vec<(usize, f64)> = Vec::with_capacity(text.len());
         position_index.push((0, start_position));
-        // current text font family and size
+        // current text font, the text is measured with the font it is drawn with
         let font: String = ctx.widget().clone_or_default::<String>("font");
         let font_size: f64 = ctx.widget().clone_or_default::<f64>("font_size");
-
-        for (index, _) in text.chars().enumerate() {
-            let bound_width: f64 = ctx
-                .render_context_2_d()
-                .measure(&text[..index + 1], font_size, &font)
-                .width;
-            let next_position: f64 = start_position + bound_width;
-
+        let font_weight = ctx.widget().clone_or_default::<FontWeight>("font_weight");
+        let font_style = ctx.widget().clone_or_default::<FontStyle>("font_style");
+        let font_stretch = ctx.widget().clone_or_default::<FontStretch>("font_stretch");
+
+        ctx.render_context_2_d().set_font_weight(font_weight);
+        ctx.render_context_2_d().set_font_style(font_style);
+        ctx.render_context_2_d().set_font_stretch(font_stretch);
+        let advances = ctx
+            .render_context_2_d()
+            .measure_advances(&text, font_size, &font);
+        let mut next_position = start_position;
+
+        for (index, advance) in advances.iter().enumerate() {
+            next_position += advance;
             position_index.push((index + 1, next_position));
         }
 
@@ -165,71 +168,59 @@ impl TextBoxState {
     fn select_all(&self, ctx: &mut Context) {
         let len = ctx.widget().get::<String16>("text").len();
         ctx.widget()
-            .get_mut::<TextSelection>("text_selection")
-            .start_index = 0;
-        ctx.widget()
-            .get_mut::<TextSelection>("text_selection")
-            .length = len;
+            .set("text_selection", TextSelection::from((0, len)));
     }
 
     fn move_cursor_left(&mut self, ctx: &mut Context) {
-        if *ctx.get_widget(self.cursor).get::<bool>("expanded") {
-            if let Some(selection) = ctx
-                .get_widget(self.cursor)
-                .try_get_mut::<TextSelection>("text_selection")
-            {
-                selection.start_index = 0;
-                selection.length = 0;
-            }
-        }
+        let expanded = *ctx.get_widget(self.cursor).get::<bool>("expanded");
 
-        if let Some(selection) = ctx
+        if let Some(mut selection) = ctx
             .get_widget(self.cursor)
-            .try_get_mut::<TextSelection>("text_selection")
+            .try_clone::<TextSelection>("text_selection")
         {
+            if expanded {
+                selection.start_index = 0;
+            }
+
             selection.start_index = (selection.start_index as i32 - 1).max(0) as usize;
             selection.length = 0;
+            ctx.get_widget(self.cursor).set("text_selection", selection);
         }
     }
 
     fn move_cursor_right(&mut self, ctx: &mut Context) {
         let text_len = ctx.widget().get::<String16>("text").len();
 
-        if *ctx.get_widget(self.cursor).get::<bool>("expanded") {
-            if let Some(selection) = ctx
-                .get_widget(self.cursor)
-                .try_get_mut::<TextSelection>("text_selection")
-            {
-                selection.start_index = text_len;
-                selection.length = 0;
-            }
-
-            return;
-        }
+        let expanded = *ctx.get_widget(self.cursor).get::<bool>("expanded");
 
-        if let Some(selection) = ctx
+        if let Some(mut selection) = ctx
             .get_widget(self.cursor)
-            .try_get_mut::<TextSelection>("text_selection")
+            .try_clone::<TextSelection>("text_selection")
         {
-            if selection.start_index < text_len {
+            if expanded {
+                selection.start_index = text_len;
+            } else if selection.start_index < text_len {
                 selection.start_index = (selection.start_index + 1).min(text_len);
             }
+
             selection.length = 0;
+            ctx.get_widget(self.cursor).set("text_selection", selection);
         }
     }
 
     fn clear_selection(&mut self, ctx: &mut Context) {
-        let selection = ctx.widget().clone::<TextSelection>("text_selection");
+        let mut selection = ctx.widget().clone::<TextSelection>("text_selection");
 
-        if let Some(text) = ctx.widget().try_get_mut::<String16>("text") {
+        if let Some(mut text) = ctx.widget().try_clone::<String16>("text") {
             for i in (selection.start_index..(selection.start_index + selection.length)).rev() {
                 text.remove(i);
             }
+
+            ctx.widget().set("text", text);
         }
 
-        ctx.widget()
-            .get_mut::<TextSelection>("text_selection")
-            .length = 0;
+        selection.length = 0;
+        ctx.widget().set("text_selection", selection);
     }
 
     fn back_space(&mut self, ctx: &mut Context) {
@@ -239,15 +230,15 @@ impl TextBoxState {
             self.clear_selection(ctx);
             changed = true;
         } else {
-            let index = ctx
-                .widget()
-                .clone::<TextSelection>("text_selection")
-                .start_index;
+            let mut selection = ctx.widget().clone::<TextSelection>("text_selection");
+            let index = selection.start_index;
             if index > 0 {
-                ctx.widget().get_mut::<String16>("text").remove(index - 1);
-                ctx.widget()
-                    .get_mut::<TextSelection>("text_selection")
-                    .start_index = index - 1;
+                let mut text = ctx.widget().clone::<String16>("text");
+                text.remove(index - 1);
+                ctx.widget().set("text", text);
+
+                selection.start_index = index - 1;
+                ctx.widget().set("text_selection", selection);
 
                 changed = true;
             }
@@ -268,17 +259,16 @@ impl TextBoxState {
         if *ctx.get_widget(self.cursor).get::<bool>("expanded") {
             self.clear_selection(ctx);
         } else {
-            let index = ctx
-                .widget()
-                .clone::<TextSelection>("text_selection")
-                .start_index;
+            let mut selection = ctx.widget().clone::<TextSelection>("text_selection");
+            let index = selection.start_index;
             if index < ctx.widget().get::<String16>("text").len() {
-                ctx.widget().get_mut::<String16>("text").remove(index);
+                let mut text = ctx.widget().clone::<String16>("text");
+                text.remove(index);
+                ctx.widget().set("text", text);
                 changed = true;
 
-                ctx.widget()
-                    .get_mut::<TextSelection>("text_selection")
-                    .start_index = index;
+                selection.start_index = index;
+                ctx.widget().set("text_selection", selection);
             }
         }
 
@@ -310,27 +300,29 @@ impl TextBoxState {
 
         if *ctx.get_widget(self.cursor).get::<bool>("expanded") {
             ctx.widget().set("text", String16::from(key_event.text));
-            if let Some(selection) = ctx
+            if let Some(mut selection) = ctx
                 .get_widget(self.cursor)
-                .try_get_mut::<TextSelection>("text_selection")
+                .try_clone::<TextSelection>("text_selection")
             {
                 selection.start_index = 1;
-                selection.length = 0
+                selection.length = 0;
+                ctx.get_widget(self.cursor).set("text_selection", selection);
             }
         } else {
             let current_selection = *ctx
                 .get_widget(self.cursor)
                 .get::<TextSelection>("text_selection");
-            ctx.widget()
-                .get_mut::<String16>("text")
-                .insert_str(current_selection.start_index, key_event.text.as_str());
+            let mut text = ctx.widget().clone::<String16>("text");
+            text.insert_str(current_selection.start_index, key_event.text.as_str());
+            ctx.widget().set("text", text);
 
-            if let Some(selection) = ctx
+            if let Some(mut selection) = ctx
                 .get_widget(self.cursor)
-                .try_get_mut::<TextSelection>("text_selection")
+                .try_clone::<TextSelection>("text_selection")
             {
                 selection.start_index =
                     current_selection.start_index + key_event.text.encode_utf16().count();
+                ctx.get_widget(self.cursor).set("text_selection", selection);
             }
         }
 
@@ -394,6 +386,15 @@ widget!(
         /// Sets or shares the font property.
         font: String,
 
+        /// Sets or shares the font weight property.
+        font_weight: FontWeight,
+
+        /// Sets or shares the font style property.
+        font_style: FontStyle,
+
+        /// Sets or shares the font stretch property.
+        font_stretch: FontStretch,
+
         /// Sets or shares the background property.
         background: Brush,
 
@@ -427,6 +428,9 @@ impl Template for TextBox {
             .water_mark(id)
             .font(id)
             .font_size(id)
+            .font_weight(id)
+            .font_style(id)
+            .font_stretch(id)
             .build(ctx);
 
         self.name("TextBox")
@@ -491,3 +495,90 @@ impl Template for TextBox {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use orbtk_api::testing::{Snapshot, TestDriver};
+
+    use super::*;
+
+    #[test]
+    fn test_type_text() {
+        let mut driver = TestDriver::new(|ctx| {
+            Window::new()
+                .child(TextBox::new().id("input").build(ctx))
+                .build(ctx)
+        });
+
+        assert!(driver.find_by_id("missing").is_none());
+
+        let input = driver.find_by_id("input").unwrap();
+        driver.focus(input);
+        driver.assert_property(input, "focused", true);
+
+        driver.type_text("OrbTk");
+        driver.assert_property(input, "text", String16::from("OrbTk"));
+
+        driver.press_key(Key::Backspace, "");
+        driver.assert_property(input, "text", String16::from("OrbT"));
+    }
+
+    #[test]
+    fn test_text_block_grows() {
+        let mut driver = TestDriver::new(|ctx| {
+            Window::new()
+                .child(TextBox::new().id("input").build(ctx))
+                .build(ctx)
+        });
+
+        let input = driver.find_by_id("input").unwrap();
+        let cursor = driver.find_by_id(ID_CURSOR).unwrap();
+        let text_block = Entity(driver.get::<u32>(cursor, "text_block"));
+        let width = driver.get::<Rectangle>(text_block, "bounds").width();
+
+        driver.focus(input);
+        driver.type_text("OrbTk");
+
+        // the text block shares the text of the text box and has to be laid out again
+        assert!(driver.get::<Rectangle>(text_block, "bounds").width() > width);
+    }
+
+    #[test]
+    fn test_font_of_text_block() {
+        let mut driver = TestDriver::new(|ctx| {
+            Window::new()
+                .child(TextBox::new().id("input").build(ctx))
+                .build(ctx)
+        });
+
+        driver.switch_theme(
+            ThemeValue::create_from_css(
+                "text_box { font-weight: bold; font-style: italic; font-stretch: condensed; }",
+            )
+            .build(),
+        );
+
+        let input = driver.find_by_id("input").unwrap();
+        let cursor = driver.find_by_id(ID_CURSOR).unwrap();
+        let text_block = Entity(driver.get::<u32>(cursor, "text_block"));
+
+        // the caret positions are measured with the font of the text box, the text block draws
+        // the text with the same font
+        driver.assert_property(input, "font_weight", FontWeight::BOLD);
+        driver.assert_property(text_block, "font_weight", FontWeight::BOLD);
+        driver.assert_property(text_block, "font_style", FontStyle::Italic);
+        driver.assert_property(text_block, "font_stretch", FontStretch::Condensed);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        Snapshot::new("text_box")
+            .size(120.0, 32.0)
+            .tolerance(2)
+            .assert(|ctx| {
+                Window::new()
+                    .child(TextBox::new().text("OrbTk").build(ctx))
+                    .build(ctx)
+            });
+    }
+}
@@ -161,6 +161,11 @@ widget!(
         /// Sets or shares a value that describes if the current window is active.
         active: bool,
 
+        /// Sets or shares the scale factor, that is the number of physical pixels per logical pixel. If it is
+        /// set, it is used instead of the scale factor of the platform. It is updated with the scale factor
+        /// the window is drawn with. The `ORBTK_SCALE_FACTOR` environment variable overrides both.
+        scale_factor: f64,
+
         /// Sets or shares the theme property.
         theme: Theme
     }
@@ -0,0 +1,40 @@
+use orbtk_api::testing::TestDriver;
+use orbtk_widgets::prelude::*;
+
+widget!(
+    // A widget that is defined outside of orbtk with properties orbtk does not know.
+    Gauge {
+        needle_color: Brush,
+        needle_width: f64,
+        text: String16
+    }
+);
+
+impl Template for Gauge {
+    fn template(self, _: Entity, _: &mut BuildContext) -> Self {
+        self.element("gauge")
+    }
+}
+
+#[test]
+fn test_theme_custom_properties() {
+    let mut driver = TestDriver::new(|ctx| {
+        Window::new()
+            .child(Gauge::new().id("gauge").text("Speed").build(ctx))
+            .build(ctx)
+    });
+
+    driver.switch_theme(
+        ThemeValue::create_from_css(
+            "gauge { needle-color: #ff0000; needle-width: 3px; text: fast; }",
+        )
+        .build(),
+    );
+
+    let gauge = driver.find_by_id("gauge").unwrap();
+    driver.assert_property(gauge, "needle_color", Brush::from("#ff0000"));
+    driver.assert_property(gauge, "needle_width", 3.0);
+
+    // non visual properties are never set by a theme
+    driver.assert_property(gauge, "text", String16::from("Speed"));
+}
@@ -0,0 +1,78 @@
+use std::{env, fs, thread, time::Duration};
+
+use orbtk_api::testing::TestDriver;
+use orbtk_widgets::prelude::*;
+
+// Longer than the interval in which watched css files are checked for changes.
+const WATCH_DELAY: Duration = Duration::from_millis(1500);
+
+widget!(
+    // Watches the theme of the given css file and switches to a fixed theme once `switched` is set.
+    ThemeProbe<ThemeProbeState> {
+        css_path: String,
+        switched: bool,
+        background: Brush
+    }
+);
+
+impl Template for ThemeProbe {
+    fn template(self, _: Entity, _: &mut BuildContext) -> Self {
+        self.element("probe")
+    }
+}
+
+#[derive(Default, AsAny)]
+struct ThemeProbeState {
+    switched: bool,
+}
+
+impl State for ThemeProbeState {
+    fn init(&mut self, _: &mut Registry, ctx: &mut Context) {
+        let path = ctx.widget().clone::<String>("css_path");
+        ctx.watch_theme(ThemeValue::create_from_path(path));
+    }
+
+    fn update(&mut self, _: &mut Registry, ctx: &mut Context) {
+        if !self.switched && *ctx.widget().get::<bool>("switched") {
+            self.switched = true;
+            ctx.switch_theme(ThemeValue::create_from_css("probe { background: #0000ff; }").build());
+        }
+    }
+}
+
+#[test]
+fn test_switch_theme_stops_watching() {
+    let path = env::temp_dir().join(format!("orbtk_theme_switch_{}.css", std::process::id()));
+    fs::write(&path, "probe { background: #ff0000; }").unwrap();
+
+    let css_path = path.to_string_lossy().to_string();
+    let mut driver = TestDriver::new(move |ctx| {
+        Window::new()
+            .child(
+                ThemeProbe::new()
+                    .id("probe")
+                    .css_path(css_path.clone())
+                    .build(ctx),
+            )
+            .build(ctx)
+    });
+
+    let probe = driver.find_by_id("probe").unwrap();
+
+    // the watched theme is sent by the thread of the watcher
+    thread::sleep(WATCH_DELAY);
+    driver.step();
+    driver.assert_property(probe, "background", Brush::from("#ff0000"));
+
+    driver.set(probe, "switched", true);
+    driver.steps(2);
+    driver.assert_property(probe, "background", Brush::from("#0000ff"));
+
+    // the switched theme is kept if the watched file changes
+    fs::write(&path, "probe { background: #00ff00; }").unwrap();
+    thread::sleep(WATCH_DELAY);
+    driver.step();
+    driver.assert_property(probe, "background", Brush::from("#0000ff"));
+
+    fs::remove_file(&path).unwrap();
+}
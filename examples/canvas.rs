@@ -1,4 +1,4 @@
-use orbtk::{prelude::*, render::platform::RenderContext2D, utils};
+use orbtk::{prelude::*, render::RenderContext2D, utils};
 use std::cell::Cell;
 
 use euc::{buffer::Buffer2d, rasterizer, Pipeline};
@@ -132,24 +132,19 @@ impl render::RenderPipeline for CubePipeline {
 }
 
 // OrbTk 2D drawing
-#[derive(Clone, Default, PartialEq, Pipeline)]
+#[derive(Clone, Default, PartialEq, Pipeline2D)]
 struct Graphic2DPipeline;
 
-impl render::RenderPipeline for Graphic2DPipeline {
-    fn draw(&self, render_target: &mut render::RenderTarget) {
-        let mut render_context =
-            RenderContext2D::new(render_target.width(), render_target.height());
-
-        let width = 120.0;
-        let height = 120.0;
+impl render::RenderPipeline2D for Graphic2DPipeline {
+    fn draw(&self, render_context: &mut RenderContext2D, width: f64, height: f64) {
+        let size = 120.0;
 
-        let x = (render_target.width() - width) / 2.0;
-        let y = (render_target.height() - height) / 2.0;
-        // render_context.set_fill_style(utils::Brush::SolidColor(Color::from("#000000")));
+        let x = (width - size) / 2.0;
+        let y = (height - size) / 2.0;
 
         render_context.set_fill_style(utils::Brush::LinearGradient {
             start: Point::new(x, y),
-            end: Point::new(x + width, y + height),
+            end: Point::new(x + size, y + size),
             stops: vec![
                 LinearGradientStop {
                     position: 0.0,
@@ -165,8 +160,25 @@ impl render::RenderPipeline for Graphic2DPipeline {
                 },
             ],
         });
-        render_context.fill_rect(x, y, width, height);
-        // render_target.draw(render_context.data());
+        render_context.fill_rect(x, y, size, size);
+
+        // gauge
+        render_context.begin_path();
+        render_context.arc(
+            width / 2.0,
+            height / 2.0,
+            size / 2.0 + 16.0,
+            std::f64::consts::PI * 0.75,
+            std::f64::consts::PI * 1.75,
+        );
+        render_context.set_line_width(6.0);
+        render_context.set_stroke_style(utils::Brush::from("#EFD035"));
+        render_context.stroke();
+
+        render_context.set_font_family("Roboto Regular");
+        render_context.set_font_size(16.0);
+        render_context.set_fill_style(utils::Brush::from("#FFFFFF"));
+        render_context.fill_text("2D", x + 8.0, y + 8.0);
     }
 }
 
@@ -183,15 +195,13 @@ impl MainViewState {
 
 impl State for MainViewState {
     fn update(&mut self, _: &mut Registry, ctx: &mut Context) {
-        if let Some(cube) = ctx
-            .widget()
-            .get_mut::<RenderPipeline>("render_pipeline")
-            .0
-            .as_any()
-            .downcast_ref::<CubePipeline>()
-        {
+        let pipeline = ctx.widget().clone::<RenderPipeline>("render_pipeline");
+
+        if let Some(cube) = pipeline.0.as_any().downcast_ref::<CubePipeline>() {
             cube.spin.set(self.cube_spin);
         }
+
+        ctx.widget().set("render_pipeline", pipeline);
     }
 }
 
@@ -247,7 +257,9 @@ impl Template for MainView {
                     .child(
                         Canvas::new()
                             .attach(Grid::row(3))
-                            .render_pipeline(RenderPipeline(Box::new(Graphic2DPipeline::default())))
+                            .render_pipeline_2_d(RenderPipeline2D(Box::new(
+                                Graphic2DPipeline::default(),
+                            )))
                             .build(ctx),
                     )
                     .build(ctx),
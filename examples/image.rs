@@ -10,7 +10,24 @@ fn main() {
                 .title("OrbTk - image example")
                 .position((100.0, 100.0))
                 .size(800.0, 420.0)
-                .child(ImageWidget::new().image("res/orbtk-space.png").build(ctx))
+                .child(
+                    Stack::new()
+                        .child(
+                            ImageWidget::new()
+                                .image("res/orbtk-space.png")
+                                .stretch("uniform")
+                                .build(ctx),
+                        )
+                        .child(
+                            SvgIcon::new()
+                                .svg("res/star.svg")
+                                .icon_size(48.0)
+                                .icon_brush("#efd035")
+                                .margin(8.0)
+                                .build(ctx),
+                        )
+                        .build(ctx),
+                )
                 .build(ctx)
         })
         .run();